@@ -8,20 +8,31 @@ use acir::{
 };
 
 pub fn compile(acir: Circuit, np_language: Language) -> Circuit {
+    compile_with_gate_map(acir, np_language).0
+}
+
+/// Compiles the circuit like `compile`, and also returns for each gate of the optimised circuit
+/// the index of the gate in `acir` which it was derived from.
+pub fn compile_with_gate_map(acir: Circuit, np_language: Language) -> (Circuit, Vec<usize>) {
     // Instantiate the optimiser.
     // Currently the optimiser and reducer are one in the same
     // for CSAT
     let optimiser = match np_language {
-        crate::Language::R1CS => return optimise_r1cs(acir),
+        crate::Language::R1CS => {
+            // The R1CS optimisations do not add or remove gates
+            let gate_map = (0..acir.gates.len()).collect();
+            return (optimise_r1cs(acir), gate_map);
+        }
         crate::Language::PLONKCSat { width } => CSatOptimiser::new(width),
     };
 
     // Optimise the arithmetic gates by reducing them into the correct width and
     // creating intermediate variables when necessary
     let mut optimised_gates = Vec::new();
+    let mut gate_map = Vec::new();
 
     let mut next_witness_index = acir.current_witness_index + 1;
-    for gate in acir.gates {
+    for (index, gate) in acir.gates.into_iter().enumerate() {
         match gate {
             Gate::Arithmetic(arith_expr) => {
                 let mut intermediate_variables: IndexMap<Witness, Expression> = IndexMap::new();
@@ -40,19 +51,24 @@ pub fn compile(acir: Circuit, np_language: Language) -> Circuit {
                 new_gates.sort();
                 for gate in new_gates {
                     optimised_gates.push(Gate::Arithmetic(gate));
+                    gate_map.push(index);
                 }
             }
-            other_gate => optimised_gates.push(other_gate),
+            other_gate => {
+                optimised_gates.push(other_gate);
+                gate_map.push(index);
+            }
         }
     }
 
     let current_witness_index = next_witness_index - 1;
 
-    Circuit {
+    let circuit = Circuit {
         current_witness_index,
         gates: optimised_gates,
         public_inputs: acir.public_inputs, // The optimiser does not add public inputs
-    }
+    };
+    (circuit, gate_map)
 }
 
 // R1CS optimisations uses the general optimiser.
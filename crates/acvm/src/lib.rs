@@ -29,7 +29,8 @@ pub enum GateResolution {
     Skip,                      //Gate cannot be solved
     UnknownError(String),      //Generic error
     UnsupportedOpcode(OPCODE), //Unsupported Opcode
-    UnsatisfiedConstrain,      //Gate is not satisfied
+    //Gate is not satisfied, with the message of the constraint it was generated from if it has one
+    UnsatisfiedConstrain(Option<String>),
 }
 
 pub trait Backend: SmartContract + ProofSystemCompiler + PartialWitnessGenerator {}
@@ -43,138 +44,110 @@ pub trait PartialWitnessGenerator {
         initial_witness: &mut BTreeMap<Witness, FieldElement>,
         gates: Vec<Gate>,
     ) -> GateResolution {
-        if gates.is_empty() {
-            return GateResolution::Resolved;
-        }
-        let mut unsolved_gates: Vec<Gate> = Vec::new();
+        self.solve_with_gate_index(initial_witness, gates, &BTreeMap::new()).0
+    }
+
+    /// Solves the gates like `solve`, but if a gate stops the solver,
+    /// the index of that gate in `gates` is also returned.
+    ///
+    /// `assert_messages` maps the indices of gates to the messages of the constraints
+    /// they were generated from. If one of those gates is not satisfied, its message
+    /// is returned in the `UnsatisfiedConstrain` resolution.
+    fn solve_with_gate_index(
+        &self,
+        initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        gates: Vec<Gate>,
+        assert_messages: &BTreeMap<usize, String>,
+    ) -> (GateResolution, Option<usize>) {
+        let mut unsolved_gates: Vec<(usize, Gate)> = gates.into_iter().enumerate().collect();
 
-        for gate in gates.into_iter() {
-            let unsolved = match &gate {
-                Gate::Arithmetic(arith) => {
-                    let result = ArithmeticSolver::solve(initial_witness, arith);
-                    match result {
-                        GateResolution::Resolved => false,
-                        GateResolution::Skip => true,
-                        _ => return result,
+        while !unsolved_gates.is_empty() {
+            let mut skipped_gates = Vec::new();
+            for (index, gate) in unsolved_gates.into_iter() {
+                match Self::solve_gate(initial_witness, &gate) {
+                    GateResolution::Resolved => (),
+                    GateResolution::Skip => skipped_gates.push((index, gate)),
+                    GateResolution::UnsatisfiedConstrain(None) => {
+                        let message = assert_messages.get(&index).cloned();
+                        return (GateResolution::UnsatisfiedConstrain(message), Some(index));
                     }
+                    result => return (result, Some(index)),
                 }
-                Gate::Range(w, r) => {
-                    if let Some(w_value) = initial_witness.get(w) {
-                        if w_value.num_bits() > *r {
-                            return GateResolution::UnsatisfiedConstrain;
-                        }
-                        false
-                    } else {
-                        true
+            }
+            unsolved_gates = skipped_gates;
+        }
+        (GateResolution::Resolved, None)
+    }
+
+    /// Solves a single gate, or returns `Skip` if its inputs are not known yet
+    fn solve_gate(
+        initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        gate: &Gate,
+    ) -> GateResolution {
+        let unsolved = match gate {
+            Gate::Arithmetic(arith) => {
+                let result = ArithmeticSolver::solve(initial_witness, arith);
+                match result {
+                    GateResolution::Resolved => false,
+                    GateResolution::Skip => true,
+                    _ => return result,
+                }
+            }
+            Gate::Range(w, r) => {
+                if let Some(w_value) = initial_witness.get(w) {
+                    if w_value.num_bits() > *r {
+                        return GateResolution::UnsatisfiedConstrain(None);
                     }
+                    false
+                } else {
+                    true
                 }
-                Gate::And(and_gate) => {
-                    !LogicSolver::solve_and_gate(initial_witness, and_gate)
-                    // We compute the result because the other gates may want to use the assignment to generate their assignments
+            }
+            Gate::And(and_gate) => {
+                !LogicSolver::solve_and_gate(initial_witness, and_gate)
+                // We compute the result because the other gates may want to use the assignment to generate their assignments
+            }
+            Gate::Xor(xor_gate) => {
+                !LogicSolver::solve_xor_gate(initial_witness, xor_gate)
+                // We compute the result because the other gates may want to use the assignment to generate their assignments
+            }
+            Gate::GadgetCall(gc) => {
+                let mut unsolvable = false;
+                for i in &gc.inputs {
+                    if !initial_witness.contains_key(&i.witness) {
+                        unsolvable = true;
+                        break;
+                    }
                 }
-                Gate::Xor(xor_gate) => {
-                    !LogicSolver::solve_xor_gate(initial_witness, xor_gate)
-                    // We compute the result because the other gates may want to use the assignment to generate their assignments
+                if unsolvable {
+                    true
+                } else if let Err(op) = Self::solve_gadget_call(initial_witness, gc) {
+                    return GateResolution::UnsupportedOpcode(op);
+                } else {
+                    false
                 }
-                Gate::GadgetCall(gc) => {
-                    let mut unsolvable = false;
-                    for i in &gc.inputs {
-                        if !initial_witness.contains_key(&i.witness) {
-                            unsolvable = true;
-                            break;
-                        }
-                    }
-                    if unsolvable {
-                        true
-                    } else if let Err(op) = Self::solve_gadget_call(initial_witness, gc) {
-                        return GateResolution::UnsupportedOpcode(op);
-                    } else {
+            }
+            Gate::Directive(directive) => match directive {
+                Directive::Invert { x, result } => match initial_witness.get(x) {
+                    None => true,
+                    Some(val) => {
+                        let inverse = val.inverse();
+                        initial_witness.insert(*result, inverse);
                         false
                     }
-                }
-                Gate::Directive(directive) => match directive {
-                    Directive::Invert { x, result } => match initial_witness.get(x) {
-                        None => true,
-                        Some(val) => {
-                            let inverse = val.inverse();
-                            initial_witness.insert(*result, inverse);
-                            false
-                        }
-                    },
-                    Directive::Quotient { a, b, q, r } => {
-                        match (
-                            Self::get_value(a, initial_witness),
-                            Self::get_value(b, initial_witness),
-                        ) {
-                            (Some(val_a), Some(val_b)) => {
-                                let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
-                                let int_b = BigUint::from_bytes_be(&val_b.to_bytes());
-                                let int_r = &int_a % &int_b;
-                                let int_q = &int_a / &int_b;
-
-                                initial_witness.insert(
-                                    *q,
-                                    FieldElement::from_be_bytes_reduce(&int_q.to_bytes_be()),
-                                );
-                                initial_witness.insert(
-                                    *r,
-                                    FieldElement::from_be_bytes_reduce(&int_r.to_bytes_be()),
-                                );
-                                false
-                            }
-                            _ => true,
-                        }
-                    }
-                    Directive::Truncate { a, b, c, bit_size } => match initial_witness.get(a) {
-                        Some(val_a) => {
-                            let pow: BigUint = BigUint::one() << bit_size;
-
-                            let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
-                            let int_b: BigUint = &int_a % &pow;
-                            let int_c: BigUint = (&int_a - &int_b) / &pow;
-
-                            initial_witness.insert(
-                                *b,
-                                FieldElement::from_be_bytes_reduce(&int_b.to_bytes_be()),
-                            );
-                            initial_witness.insert(
-                                *c,
-                                FieldElement::from_be_bytes_reduce(&int_c.to_bytes_be()),
-                            );
-                            false
-                        }
-                        _ => true,
-                    },
-                    Directive::Split { a, b, bit_size } => match initial_witness.get(a) {
-                        Some(val_a) => {
-                            let a_big = BigUint::from_bytes_be(&val_a.to_bytes());
-                            for i in 0..*bit_size {
-                                let j = i as usize;
-                                let v = if a_big.bit(j as u64) {
-                                    FieldElement::one()
-                                } else {
-                                    FieldElement::zero()
-                                };
-                                initial_witness.insert(b[j], v);
-                            }
-                            false
-                        }
-                        _ => true,
-                    },
-                    Directive::Oddrange { a, b, r, bit_size } => match initial_witness.get(a) {
-                        Some(val_a) => {
+                },
+                Directive::Quotient { a, b, q, r } => {
+                    match (Self::get_value(a, initial_witness), Self::get_value(b, initial_witness))
+                    {
+                        (Some(val_a), Some(val_b)) => {
                             let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
-                            let pow: BigUint = BigUint::one() << (bit_size - 1);
-                            if int_a >= (&pow << 1) {
-                                return GateResolution::UnsatisfiedConstrain;
-                            }
-                            let bb = &int_a & &pow;
-                            let int_r = &int_a - &bb;
-                            let int_b = &bb >> (bit_size - 1);
+                            let int_b = BigUint::from_bytes_be(&val_b.to_bytes());
+                            let int_r = &int_a % &int_b;
+                            let int_q = &int_a / &int_b;
 
                             initial_witness.insert(
-                                *b,
-                                FieldElement::from_be_bytes_reduce(&int_b.to_bytes_be()),
+                                *q,
+                                FieldElement::from_be_bytes_reduce(&int_q.to_bytes_be()),
                             );
                             initial_witness.insert(
                                 *r,
@@ -183,14 +156,66 @@ pub trait PartialWitnessGenerator {
                             false
                         }
                         _ => true,
-                    },
+                    }
+                }
+                Directive::Truncate { a, b, c, bit_size } => match initial_witness.get(a) {
+                    Some(val_a) => {
+                        let pow: BigUint = BigUint::one() << bit_size;
+
+                        let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
+                        let int_b: BigUint = &int_a % &pow;
+                        let int_c: BigUint = (&int_a - &int_b) / &pow;
+
+                        initial_witness
+                            .insert(*b, FieldElement::from_be_bytes_reduce(&int_b.to_bytes_be()));
+                        initial_witness
+                            .insert(*c, FieldElement::from_be_bytes_reduce(&int_c.to_bytes_be()));
+                        false
+                    }
+                    _ => true,
                 },
-            };
-            if unsolved {
-                unsolved_gates.push(gate);
-            }
+                Directive::Split { a, b, bit_size } => match initial_witness.get(a) {
+                    Some(val_a) => {
+                        let a_big = BigUint::from_bytes_be(&val_a.to_bytes());
+                        for i in 0..*bit_size {
+                            let j = i as usize;
+                            let v = if a_big.bit(j as u64) {
+                                FieldElement::one()
+                            } else {
+                                FieldElement::zero()
+                            };
+                            initial_witness.insert(b[j], v);
+                        }
+                        false
+                    }
+                    _ => true,
+                },
+                Directive::Oddrange { a, b, r, bit_size } => match initial_witness.get(a) {
+                    Some(val_a) => {
+                        let int_a = BigUint::from_bytes_be(&val_a.to_bytes());
+                        let pow: BigUint = BigUint::one() << (bit_size - 1);
+                        if int_a >= (&pow << 1) {
+                            return GateResolution::UnsatisfiedConstrain(None);
+                        }
+                        let bb = &int_a & &pow;
+                        let int_r = &int_a - &bb;
+                        let int_b = &bb >> (bit_size - 1);
+
+                        initial_witness
+                            .insert(*b, FieldElement::from_be_bytes_reduce(&int_b.to_bytes_be()));
+                        initial_witness
+                            .insert(*r, FieldElement::from_be_bytes_reduce(&int_r.to_bytes_be()));
+                        false
+                    }
+                    _ => true,
+                },
+            },
+        };
+        if unsolved {
+            GateResolution::Skip
+        } else {
+            GateResolution::Resolved
         }
-        self.solve(initial_witness, unsolved_gates)
     }
 
     fn solve_gadget_call(
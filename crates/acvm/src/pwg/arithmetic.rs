@@ -40,7 +40,7 @@ impl ArithmeticSolver {
                     let total_sum = a + gate.q_c;
                     if (q + b).is_zero() {
                         if !total_sum.is_zero() {
-                            GateResolution::UnsatisfiedConstrain
+                            GateResolution::UnsatisfiedConstrain(None)
                         } else {
                             GateResolution::Resolved
                         }
@@ -62,7 +62,7 @@ impl ArithmeticSolver {
                 let total_sum = sum + gate.q_c;
                 if partial_prod.is_zero() {
                     if !total_sum.is_zero() {
-                        GateResolution::UnsatisfiedConstrain
+                        GateResolution::UnsatisfiedConstrain(None)
                     } else {
                         GateResolution::Resolved
                     }
@@ -77,7 +77,7 @@ impl ArithmeticSolver {
                 // All the variables in the MulTerm are solved and the Fan-in is also solved
                 // There is nothing to solve
                 if !(a + b + gate.q_c).is_zero() {
-                    GateResolution::UnsatisfiedConstrain
+                    GateResolution::UnsatisfiedConstrain(None)
                 } else {
                     GateResolution::Resolved
                 }
@@ -92,7 +92,7 @@ impl ArithmeticSolver {
                 let total_sum = total_prod + partial_sum + gate.q_c;
                 if coeff.is_zero() {
                     if !total_sum.is_zero() {
-                        GateResolution::UnsatisfiedConstrain
+                        GateResolution::UnsatisfiedConstrain(None)
                     } else {
                         GateResolution::Resolved
                     }
@@ -1,15 +1,152 @@
-cfg_if::cfg_if! {
-    if #[cfg(feature = "plonk_bn254")] {
+use std::{collections::BTreeMap, path::Path};
 
-        pub use aztec_backend::Plonk as ConcreteBackend;
+use acvm::acir::{
+    circuit::{Circuit, Gate},
+    native_types::Witness,
+};
+use acvm::{FieldElement, GateResolution, Language};
 
-    } else if #[cfg(feature = "marlin")] {
-        // R1CS_MARLIN_ARKWORKS
-        pub use marlin_arkworks_backend::Marlin as ConcreteBackend;
-    } else {
-        compile_error!("please specify a backend to compile with");
+use crate::errors::CliError;
+
+#[cfg(not(any(feature = "plonk_bn254", feature = "marlin")))]
+compile_error!("please specify a backend to compile with");
+
+/// The subset of `acvm::Backend` which nargo uses.
+///
+/// `acvm::Backend` cannot be made into a trait object, as the partial witness generator
+/// has associated functions. This trait forwards to it, so that the commands
+/// do not depend on the concrete backend type.
+pub trait Backend {
+    fn name(&self) -> &'static str;
+
+    fn np_language(&self) -> Language;
+
+    fn solve_with_gate_index(
+        &self,
+        initial_witness: &mut BTreeMap<Witness, FieldElement>,
+        gates: Vec<Gate>,
+        assert_messages: &BTreeMap<usize, String>,
+    ) -> (GateResolution, Option<usize>);
+
+    fn prove_with_meta(
+        &self,
+        circuit: Circuit,
+        witness_values: BTreeMap<Witness, FieldElement>,
+    ) -> Vec<u8>;
+
+    fn verify_from_cs(
+        &self,
+        proof: &[u8],
+        public_input: Vec<FieldElement>,
+        circuit: Circuit,
+    ) -> bool;
+
+    fn eth_contract_from_cs(&self, circuit: Circuit) -> String;
+}
+
+/// Implements `Backend` for a concrete backend by forwarding to its `acvm::Backend` impl
+macro_rules! impl_backend {
+    ($backend:ty, $name:literal) => {
+        impl Backend for $backend {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn np_language(&self) -> Language {
+                acvm::ProofSystemCompiler::np_language(self)
+            }
+
+            fn solve_with_gate_index(
+                &self,
+                initial_witness: &mut BTreeMap<Witness, FieldElement>,
+                gates: Vec<Gate>,
+                assert_messages: &BTreeMap<usize, String>,
+            ) -> (GateResolution, Option<usize>) {
+                acvm::PartialWitnessGenerator::solve_with_gate_index(
+                    self,
+                    initial_witness,
+                    gates,
+                    assert_messages,
+                )
+            }
+
+            fn prove_with_meta(
+                &self,
+                circuit: Circuit,
+                witness_values: BTreeMap<Witness, FieldElement>,
+            ) -> Vec<u8> {
+                acvm::ProofSystemCompiler::prove_with_meta(self, circuit, witness_values)
+            }
+
+            fn verify_from_cs(
+                &self,
+                proof: &[u8],
+                public_input: Vec<FieldElement>,
+                circuit: Circuit,
+            ) -> bool {
+                acvm::ProofSystemCompiler::verify_from_cs(self, proof, public_input, circuit)
+            }
+
+            fn eth_contract_from_cs(&self, circuit: Circuit) -> String {
+                acvm::SmartContract::eth_contract_from_cs(self, circuit)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "plonk_bn254")]
+impl_backend!(aztec_backend::Plonk, "plonk_bn254");
+
+// R1CS_MARLIN_ARKWORKS
+#[cfg(feature = "marlin")]
+impl_backend!(marlin_arkworks_backend::Marlin, "marlin");
+
+/// Returns every backend nargo was compiled with. The first one is the default backend.
+pub fn available_backends() -> Vec<Box<dyn Backend>> {
+    let mut backends: Vec<Box<dyn Backend>> = Vec::new();
+
+    #[cfg(feature = "plonk_bn254")]
+    backends.push(Box::new(aztec_backend::Plonk));
+
+    #[cfg(feature = "marlin")]
+    backends.push(Box::new(marlin_arkworks_backend::Marlin));
+
+    backends
+}
+
+/// Returns the backend with the given name, or the default backend if no name is given
+pub fn get_backend(name: Option<&str>) -> Result<Box<dyn Backend>, CliError> {
+    let mut backends = available_backends();
+
+    let name = match name {
+        Some(name) => name,
+        None => return Ok(backends.remove(0)),
+    };
+
+    match backends.iter().position(|backend| backend.name() == name) {
+        Some(index) => Ok(backends.remove(index)),
+        None => {
+            let names: Vec<_> = backends.iter().map(|backend| backend.name()).collect();
+            Err(CliError::Generic(format!(
+                "the backend `{}` is not available in this build of nargo, which was compiled with the backends: {}",
+                name,
+                names.join(", ")
+            )))
+        }
     }
 }
-// XXX: This works  because there are only two features, we want to say only one of these can be enabled. (feature xor)
-#[cfg(all(feature = "plonk", feature = "marlin"))]
-compile_error!("feature \"plonk\"  and feature \"marlin\" cannot be enabled at the same time");
+
+/// Returns the backend requested by the `--backend` flag if it was given,
+/// otherwise the backend in the package's Nargo.toml, otherwise the default backend.
+pub fn resolve_backend(
+    program_dir: &Path,
+    backend_flag: Option<&str>,
+) -> Result<Box<dyn Backend>, CliError> {
+    if backend_flag.is_some() {
+        return get_backend(backend_flag);
+    }
+
+    let cfg_path = crate::find_package_config(program_dir)?;
+    let cfg = crate::toml::parse(cfg_path)?;
+    get_backend(cfg.package.backend.as_deref())
+}
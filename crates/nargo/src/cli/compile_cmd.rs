@@ -1,15 +1,16 @@
 use std::path::PathBuf;
 
 use acvm::acir::native_types::Witness;
-use acvm::ProofSystemCompiler;
 
 use clap::ArgMatches;
+use noirc_abi::input_parser::Format;
+use noirc_driver::{CompiledProgram, Driver};
 
 use std::path::Path;
 
-use crate::{errors::CliError, resolver::Resolver};
+use crate::{backends::Backend, errors::CliError, resolver::Resolver};
 
-use super::{create_named_dir, write_to_file, BUILD_DIR};
+use super::{create_named_dir, write_to_file, ABI_EXT, BUILD_DIR};
 
 pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
     let args = args.subcommand_matches("compile").unwrap();
@@ -17,11 +18,18 @@ pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
     let witness = args.is_present("witness");
 
     let curr_dir = std::env::current_dir().unwrap();
+    let backend = crate::backends::resolve_backend(&curr_dir, args.value_of("backend"))?;
     let mut circuit_path = PathBuf::new();
     circuit_path.push(BUILD_DIR);
 
-    let result =
-        generate_circuit_and_witness_to_disk(circuit_name, curr_dir, circuit_path, witness);
+    let result = generate_circuit_and_witness_to_disk(
+        circuit_name,
+        curr_dir,
+        circuit_path,
+        witness,
+        super::format_flag(args),
+        backend.as_ref(),
+    );
     match result {
         Ok(_) => Ok(()),
         Err(e) => Err(e),
@@ -33,8 +41,10 @@ pub fn generate_circuit_and_witness_to_disk<P: AsRef<Path>>(
     program_dir: P,
     circuit_dir: P,
     generate_witness: bool,
+    format_flag: Option<Format>,
+    backend: &dyn Backend,
 ) -> Result<PathBuf, CliError> {
-    let compiled_program = compile_circuit(program_dir.as_ref(), false)?;
+    let (driver, compiled_program) = compile_circuit(program_dir.as_ref(), false, backend)?;
     let serialized = compiled_program.circuit.to_bytes();
 
     let mut circuit_path = create_named_dir(circuit_dir.as_ref(), "build");
@@ -44,8 +54,22 @@ pub fn generate_circuit_and_witness_to_disk<P: AsRef<Path>>(
     println!("Generated ACIR code into {}", path);
     println!("{:?}", std::fs::canonicalize(&circuit_path));
 
+    if let Some(abi_artifact) = compiled_program.abi_artifact() {
+        let mut abi_path = circuit_path.clone();
+        abi_path.set_extension(ABI_EXT);
+        let abi_json = serde_json::to_string_pretty(&abi_artifact).unwrap();
+        let path = write_to_file(abi_json.as_bytes(), &abi_path);
+        println!("Generated ABI into {}", path);
+    }
+
     if generate_witness {
-        let solved_witness = super::prove_cmd::solve_witness(program_dir, &compiled_program)?;
+        let solved_witness = super::prove_cmd::solve_witness(
+            program_dir,
+            &driver,
+            &compiled_program,
+            format_flag,
+            backend,
+        )?;
         let buf = Witness::to_bytes(&solved_witness);
 
         circuit_path.pop();
@@ -57,13 +81,15 @@ pub fn generate_circuit_and_witness_to_disk<P: AsRef<Path>>(
     Ok(circuit_path)
 }
 
+/// Compiles the program, also returning the driver so that it can be
+/// used to report errors found when solving the circuit
 pub fn compile_circuit<P: AsRef<Path>>(
     program_dir: P,
     show_ssa: bool,
-) -> Result<noirc_driver::CompiledProgram, CliError> {
-    let driver = Resolver::resolve_root_config(program_dir.as_ref())?;
-    let backend = crate::backends::ConcreteBackend;
-    let compiled_program = driver.into_compiled_program(backend.np_language(), show_ssa);
+    backend: &dyn Backend,
+) -> Result<(Driver, CompiledProgram), CliError> {
+    let mut driver = Resolver::resolve_root_config(program_dir.as_ref())?;
+    let compiled_program = driver.compile_main(backend.np_language(), show_ssa);
 
-    Ok(compiled_program)
+    Ok((driver, compiled_program))
 }
@@ -1,6 +1,5 @@
 use super::{create_named_dir, write_to_file, CONTRACT_DIR};
 use crate::{errors::CliError, resolver::Resolver};
-use acvm::{ProofSystemCompiler, SmartContract};
 use clap::ArgMatches;
 
 pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
@@ -12,7 +11,7 @@ pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
     };
     let driver = Resolver::resolve_root_config(&package_dir)?;
 
-    let backend = crate::backends::ConcreteBackend;
+    let backend = crate::backends::resolve_backend(&package_dir, cmd.value_of("backend"))?;
     let compiled_program = driver.into_compiled_program(backend.np_language(), false);
 
     let smart_contract_string = backend.eth_contract_from_cs(compiled_program.circuit);
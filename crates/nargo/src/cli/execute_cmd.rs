@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+
+use acvm::acir::native_types::Witness;
+use clap::ArgMatches;
+use noirc_abi::input_parser::{Format, InputValue};
+
+use crate::{backends::Backend, errors::CliError};
+
+use super::{create_named_dir, write_to_file, BUILD_DIR, WITNESS_EXT};
+
+pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
+    let args = args.subcommand_matches("execute").unwrap();
+    let witness_name = args.value_of("witness_name");
+    let show_ssa = args.is_present("show-ssa");
+
+    let curr_dir = std::env::current_dir().unwrap();
+    let backend = crate::backends::resolve_backend(&curr_dir, args.value_of("backend"))?;
+    let mut witness_dir = PathBuf::new();
+    witness_dir.push(BUILD_DIR);
+
+    let return_value = execute_with_path(
+        witness_name,
+        curr_dir,
+        witness_dir,
+        show_ssa,
+        super::format_flag(args),
+        backend.as_ref(),
+    )?;
+    println!("Circuit witness successfully solved");
+    if let Some(return_value) = return_value {
+        println!("Circuit output: {}", display_input_value(&return_value));
+    }
+    Ok(())
+}
+
+/// Solves the witness of the program without creating a proof.
+/// The solved witness is written to `witness_dir` if a `witness_name` is given.
+pub fn execute_with_path<P: AsRef<Path>>(
+    witness_name: Option<&str>,
+    program_dir: P,
+    witness_dir: P,
+    show_ssa: bool,
+    format_flag: Option<Format>,
+    backend: &dyn Backend,
+) -> Result<Option<InputValue>, CliError> {
+    let (driver, compiled_program) =
+        super::compile_cmd::compile_circuit(program_dir.as_ref(), show_ssa, backend)?;
+    let (solved_witness, return_value) = super::prove_cmd::execute_program(
+        program_dir,
+        &driver,
+        &compiled_program,
+        format_flag,
+        backend,
+    )?;
+
+    if let Some(witness_name) = witness_name {
+        let mut witness_path = create_named_dir(witness_dir.as_ref(), "build");
+        witness_path.push(witness_name);
+        witness_path.set_extension(WITNESS_EXT);
+
+        let buf = Witness::to_bytes(&solved_witness);
+        let path = write_to_file(buf.as_slice(), &witness_path);
+        println!("Witness saved to {}", path);
+    }
+
+    Ok(return_value)
+}
+
+fn display_input_value(value: &InputValue) -> String {
+    match value {
+        InputValue::Field(element) => element.to_string(),
+        InputValue::Vec(elements) => {
+            let elements: Vec<_> = elements.iter().map(|element| element.to_string()).collect();
+            format!("[{}]", elements.join(", "))
+        }
+        InputValue::Array(elements) => {
+            let elements: Vec<_> = elements.iter().map(display_input_value).collect();
+            format!("[{}]", elements.join(", "))
+        }
+        InputValue::Struct(fields) => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, display_input_value(value)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        InputValue::String(string) => format!("{:?}", string),
+        InputValue::Undefined => "undefined".to_owned(),
+    }
+}
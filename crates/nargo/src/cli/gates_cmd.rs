@@ -1,29 +1,30 @@
 use std::collections::HashMap;
 
-use acvm::ProofSystemCompiler;
 use clap::ArgMatches;
 use std::path::Path;
 
+use crate::backends::Backend;
 use crate::errors::CliError;
 use crate::resolver::Resolver;
 
 pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
     let args = args.subcommand_matches("gates").unwrap();
     let show_ssa = args.is_present("show-ssa");
-    count_gates(show_ssa)
+    count_gates(show_ssa, args.value_of("backend"))
 }
 
-pub fn count_gates(show_ssa: bool) -> Result<(), CliError> {
+pub fn count_gates(show_ssa: bool, backend_flag: Option<&str>) -> Result<(), CliError> {
     let curr_dir = std::env::current_dir().unwrap();
-    count_gates_with_path(curr_dir, show_ssa)
+    let backend = crate::backends::resolve_backend(&curr_dir, backend_flag)?;
+    count_gates_with_path(curr_dir, show_ssa, backend.as_ref())
 }
 
 pub fn count_gates_with_path<P: AsRef<Path>>(
     program_dir: P,
     show_ssa: bool,
+    backend: &dyn Backend,
 ) -> Result<(), CliError> {
     let driver = Resolver::resolve_root_config(program_dir.as_ref())?;
-    let backend = crate::backends::ConcreteBackend;
 
     let compiled_program = driver.into_compiled_program(backend.np_language(), show_ssa);
     let gates = compiled_program.circuit.gates;
@@ -1,10 +1,12 @@
 pub use build_cmd::build_from_path;
-use clap::{App, Arg};
+use clap::{App, Arg, ArgMatches};
+use noirc_abi::input_parser::Format;
 use std::{
     fs::File,
     io::Write,
     path::{Path, PathBuf},
 };
+pub use test_cmd::TestSummary;
 extern crate tempdir;
 use tempdir::TempDir;
 
@@ -13,9 +15,11 @@ use crate::errors::CliError;
 mod build_cmd;
 mod compile_cmd;
 mod contract_cmd;
+mod execute_cmd;
 mod gates_cmd;
 mod new_cmd;
 mod prove_cmd;
+mod test_cmd;
 mod verify_cmd;
 
 const CONTRACT_DIR: &str = "contract";
@@ -27,6 +31,7 @@ const PKG_FILE: &str = "Nargo.toml";
 const PROOF_EXT: &str = "proof";
 const BUILD_DIR: &str = "build";
 const ACIR_EXT: &str = "acir";
+const ABI_EXT: &str = "abi.json";
 const WITNESS_EXT: &str = "tr";
 
 pub fn start_cli() {
@@ -34,6 +39,21 @@ pub fn start_cli() {
         .about("Noir's package manager")
         .version("0.1")
         .author("Kevaundray Wedderburn <kevtheappdev@gmail.com>")
+        .arg(
+            Arg::with_name("backend")
+                .long("backend")
+                .takes_value(true)
+                .global(true)
+                .help("The proof system backend to use, overriding the one in Nargo.toml"),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .takes_value(true)
+                .possible_values(&["toml", "json"])
+                .global(true)
+                .help("The format of the Prover and Verifier files, detected from the files by default"),
+        )
         .subcommand(App::new("build").about("Builds the constraint system"))
         .subcommand(App::new("contract").about("Creates the smart contract code for circuit"))
         .subcommand(
@@ -71,6 +91,19 @@ pub fn start_cli() {
                         .help("Solve the witness and write it to file along with the ACIR"),
                 ),
         )
+        .subcommand(
+            App::new("execute")
+                .about("Solve the witness of the program without creating a proof")
+                .arg(
+                    Arg::with_name("witness_name")
+                        .help("Write the solved witness to a file with this name"),
+                )
+                .arg(
+                    Arg::with_name("show-ssa")
+                        .long("show-ssa")
+                        .help("Emit debug information for the intermediate SSA IR"),
+                ),
+        )
         .subcommand(
             App::new("gates").about("Counts the occurences of different gates in circuit").arg(
                 Arg::with_name("show-ssa")
@@ -78,6 +111,19 @@ pub fn start_cli() {
                     .help("Emit debug information for the intermediate SSA IR"),
             ),
         )
+        .subcommand(
+            App::new("test")
+                .about("Run the tests for this program")
+                .arg(
+                    Arg::with_name("test_name")
+                        .help("If given, only tests with names containing this string will be run"),
+                )
+                .arg(
+                    Arg::with_name("show-ssa")
+                        .long("show-ssa")
+                        .help("Emit debug information for the intermediate SSA IR"),
+                ),
+        )
         .get_matches();
 
     let result = match matches.subcommand_name() {
@@ -87,7 +133,9 @@ pub fn start_cli() {
         Some("prove") => prove_cmd::run(matches),
         Some("compile") => compile_cmd::run(matches),
         Some("verify") => verify_cmd::run(matches),
+        Some("execute") => execute_cmd::run(matches),
         Some("gates") => gates_cmd::run(matches),
+        Some("test") => test_cmd::run(matches),
         None => Err(CliError::Generic("No subcommand was used".to_owned())),
         Some(x) => Err(CliError::Generic(format!("unknown command : {}", x))),
     };
@@ -96,6 +144,28 @@ pub fn start_cli() {
     }
 }
 
+/// Returns the input file format requested by the `--format` flag, if any
+fn format_flag(args: &ArgMatches) -> Option<Format> {
+    args.value_of("format").map(|ext| Format::from_ext(ext).expect("unknown input file format"))
+}
+
+/// Returns the format of the input file named `file_name`.
+/// The format requested by the `--format` flag is used if there is one,
+/// otherwise the file is read as JSON if only a `.json` file exists.
+fn input_format(program_dir: &Path, file_name: &str, format_flag: Option<Format>) -> Format {
+    if let Some(format) = format_flag {
+        return format;
+    }
+
+    let file_exists =
+        |format: Format| program_dir.join(file_name).with_extension(format.ext()).exists();
+    if !file_exists(Format::Toml) && file_exists(Format::Json) {
+        Format::Json
+    } else {
+        Format::Toml
+    }
+}
+
 fn create_dir<P: AsRef<Path>>(dir_path: P) -> Result<PathBuf, std::io::Error> {
     let mut dir = std::path::PathBuf::new();
     dir.push(dir_path);
@@ -124,18 +194,37 @@ fn write_to_file(bytes: &[u8], path: &Path) -> String {
 // helper function which tests noir programs by trying to generate a proof and verify it
 pub fn prove_and_verify(proof_name: &str, prg_dir: &Path, show_ssa: bool) -> bool {
     let tmp_dir = TempDir::new("p_and_v_tests").unwrap();
-    let proof_path =
-        match prove_cmd::prove_with_path(proof_name, prg_dir, &tmp_dir.into_path(), show_ssa) {
-            Ok(p) => p,
-            Err(CliError::Generic(msg)) => {
-                println!("Error: {}", msg);
-                return false;
-            }
-            Err(CliError::DestinationAlreadyExists(str)) => {
-                println!("Error, destination {} already exists: ", str);
-                return false;
-            }
-        };
-
-    verify_cmd::verify_with_path(prg_dir, &proof_path, show_ssa).unwrap()
+    let backend = match crate::backends::resolve_backend(prg_dir, None) {
+        Ok(backend) => backend,
+        Err(err) => {
+            println!("Error: {:?}", err);
+            return false;
+        }
+    };
+    let proof_path = match prove_cmd::prove_with_path(
+        proof_name,
+        prg_dir,
+        &tmp_dir.into_path(),
+        show_ssa,
+        None,
+        backend.as_ref(),
+    ) {
+        Ok(p) => p,
+        Err(CliError::Generic(msg)) => {
+            println!("Error: {}", msg);
+            return false;
+        }
+        Err(CliError::DestinationAlreadyExists(str)) => {
+            println!("Error, destination {} already exists: ", str);
+            return false;
+        }
+    };
+
+    verify_cmd::verify_with_path(prg_dir, &proof_path, show_ssa, None, backend.as_ref()).unwrap()
+}
+
+// helper function which runs the test functions of a noir program, as `nargo test` does
+pub fn run_tests(prg_dir: &Path, show_ssa: bool) -> Result<TestSummary, CliError> {
+    let backend = crate::backends::resolve_backend(prg_dir, None)?;
+    test_cmd::run_tests(prg_dir, "", show_ssa, backend.as_ref())
 }
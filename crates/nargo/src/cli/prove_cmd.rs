@@ -2,14 +2,14 @@ use std::{collections::BTreeMap, path::PathBuf};
 
 use acvm::acir::native_types::Witness;
 use acvm::FieldElement;
-use acvm::ProofSystemCompiler;
-use acvm::{GateResolution, PartialWitnessGenerator};
+use acvm::GateResolution;
 use clap::ArgMatches;
-use noirc_abi::AbiType;
-use noirc_abi::{input_parser::InputValue, Abi};
+use noirc_abi::input_parser::{Format, InputValue};
+use noirc_abi::Abi;
+use noirc_driver::Driver;
 use std::path::Path;
 
-use crate::errors::CliError;
+use crate::{backends::Backend, errors::CliError};
 
 use super::{
     create_named_dir, write_to_file, PROOFS_DIR, PROOF_EXT, PROVER_INPUT_FILE, VERIFIER_INPUT_FILE,
@@ -19,18 +19,27 @@ pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
     let args = args.subcommand_matches("prove").unwrap();
     let proof_name = args.value_of("proof_name").unwrap();
     let show_ssa = args.is_present("show-ssa");
-    prove(proof_name, show_ssa)
+    let format_flag = super::format_flag(args);
+    let backend_flag = args.value_of("backend");
+    prove(proof_name, show_ssa, format_flag, backend_flag)
 }
 
 /// In Barretenberg, the proof system adds a zero witness in the first index,
 /// So when we add witness values, their index start from 1.
 const WITNESS_OFFSET: u32 = 1;
 
-fn prove(proof_name: &str, show_ssa: bool) -> Result<(), CliError> {
+fn prove(
+    proof_name: &str,
+    show_ssa: bool,
+    format_flag: Option<Format>,
+    backend_flag: Option<&str>,
+) -> Result<(), CliError> {
     let curr_dir = std::env::current_dir().unwrap();
+    let backend = crate::backends::resolve_backend(&curr_dir, backend_flag)?;
     let mut proof_path = PathBuf::new();
     proof_path.push(PROOFS_DIR);
-    let result = prove_with_path(proof_name, curr_dir, proof_path, show_ssa);
+    let result =
+        prove_with_path(proof_name, curr_dir, proof_path, show_ssa, format_flag, backend.as_ref());
     match result {
         Ok(_) => Ok(()),
         Err(e) => Err(e),
@@ -50,10 +59,7 @@ fn process_abi_with_input(
     let return_witness_len = if let Some(return_param) =
         abi.parameters.iter().find(|x| x.0 == noirc_frontend::hir_def::function::MAIN_RETURN_NAME)
     {
-        match &return_param.1 {
-            AbiType::Array { length, .. } => *length as u32,
-            AbiType::Integer { .. } | AbiType::Field(_) => 1,
-        }
+        return_param.1.field_count() as u32
     } else {
         0
     };
@@ -65,25 +71,11 @@ fn process_abi_with_input(
             })
             .clone();
 
-        if !value.matches_abi(param_type) {
-            return Err(CliError::Generic(format!("The parameters in the main do not match the parameters in the {}.toml file. \n Please check `{}` parameter ", PROVER_INPUT_FILE,param_name)));
+        if !value.matches_abi(&param_type) {
+            return Err(CliError::Generic(format!("The parameters in the main do not match the parameters in the {} file. \n Please check `{}` parameter ", PROVER_INPUT_FILE,param_name)));
         }
 
         match value {
-            InputValue::Field(element) => {
-                let old_value =
-                    solved_witness.insert(Witness::new(index + WITNESS_OFFSET), element);
-                assert!(old_value.is_none());
-                index += 1;
-            }
-            InputValue::Vec(arr) => {
-                for element in arr {
-                    let old_value =
-                        solved_witness.insert(Witness::new(index + WITNESS_OFFSET), element);
-                    assert!(old_value.is_none());
-                    index += 1;
-                }
-            }
             InputValue::Undefined => {
                 assert_eq!(
                     param_name,
@@ -101,7 +93,15 @@ fn process_abi_with_input(
                     ));
                 }
                 index += return_witness_len;
-                //XXX We do not support (yet) array of arrays
+            }
+            // Nested values are flattened in the order described by the ABI
+            value => {
+                for element in value.flatten(&param_type) {
+                    let old_value =
+                        solved_witness.insert(Witness::new(index + WITNESS_OFFSET), element);
+                    assert!(old_value.is_none());
+                    index += 1;
+                }
             }
         }
     }
@@ -111,23 +111,46 @@ fn process_abi_with_input(
 pub fn compile_circuit_and_witness<P: AsRef<Path>>(
     program_dir: P,
     show_ssa: bool,
+    format_flag: Option<Format>,
+    backend: &dyn Backend,
 ) -> Result<(noirc_driver::CompiledProgram, BTreeMap<Witness, FieldElement>), CliError> {
-    let compiled_program = super::compile_cmd::compile_circuit(program_dir.as_ref(), show_ssa)?;
-    let solved_witness = solve_witness(program_dir, &compiled_program)?;
+    let (driver, compiled_program) =
+        super::compile_cmd::compile_circuit(program_dir.as_ref(), show_ssa, backend)?;
+    let solved_witness =
+        solve_witness(program_dir, &driver, &compiled_program, format_flag, backend)?;
     Ok((compiled_program, solved_witness))
 }
 
 pub fn solve_witness<P: AsRef<Path>>(
     program_dir: P,
+    driver: &Driver,
     compiled_program: &noirc_driver::CompiledProgram,
+    format_flag: Option<Format>,
+    backend: &dyn Backend,
 ) -> Result<BTreeMap<Witness, FieldElement>, CliError> {
+    let (solved_witness, _) =
+        execute_program(program_dir, driver, compiled_program, format_flag, backend)?;
+    Ok(solved_witness)
+}
+
+/// Solves the witness for the inputs in the prover file and (over)writes the verifier file,
+/// using the same format as the prover file.
+/// Also returns the value returned by `main`, if there is one.
+pub fn execute_program<P: AsRef<Path>>(
+    program_dir: P,
+    driver: &Driver,
+    compiled_program: &noirc_driver::CompiledProgram,
+    format_flag: Option<Format>,
+    backend: &dyn Backend,
+) -> Result<(BTreeMap<Witness, FieldElement>, Option<InputValue>), CliError> {
+    let abi = compiled_program.abi.as_ref().unwrap();
+
     // Parse the initial witness values
-    let witness_map = noirc_abi::input_parser::Format::Toml
-        .parse(&program_dir, PROVER_INPUT_FILE)
-        .map_err(CliError::from)?;
+    let format = super::input_format(program_dir.as_ref(), PROVER_INPUT_FILE, format_flag);
+    let witness_map = format.parse(&program_dir, PROVER_INPUT_FILE, abi).map_err(CliError::from)?;
 
     // Check that enough witness values were supplied
-    let num_params = compiled_program.abi.as_ref().unwrap().num_parameters();
+    let num_params = abi.num_parameters();
     if num_params != witness_map.len() {
         panic!(
             "Expected {} number of values, but got {} number of values",
@@ -135,30 +158,41 @@ pub fn solve_witness<P: AsRef<Path>>(
             witness_map.len()
         )
     }
-    // Map initial witnesses with their values
-    let abi = compiled_program.abi.as_ref().unwrap();
     // Solve the remaining witnesses
     let (mut solved_witness, rv) = process_abi_with_input(abi.clone(), &witness_map)?;
 
-    let backend = crate::backends::ConcreteBackend;
-    let solver_res = backend.solve(&mut solved_witness, compiled_program.circuit.gates.clone());
-    // (over)writes verifier.toml
-    export_public_inputs(rv, &solved_witness, &witness_map, abi, &program_dir)
-        .map_err(CliError::from)?;
+    let (solver_res, gate_index) = backend.solve_with_gate_index(
+        &mut solved_witness,
+        compiled_program.circuit.gates.clone(),
+        &compiled_program.debug.assert_messages,
+    );
+    // (over)writes the verifier file
+    let public_inputs =
+        export_public_inputs(rv, &solved_witness, &witness_map, abi, format, &program_dir)
+            .map_err(CliError::from)?;
 
     match solver_res {
             GateResolution::UnsupportedOpcode(opcode) => return Err(CliError::Generic(format!(
                 "backend does not currently support the {} opcode. ACVM does not currently fall back to arithmetic gates.",
                 opcode
             ))),
-            GateResolution::UnsatisfiedConstrain => return Err(CliError::Generic(
-                "could not satisfy all constraints".to_string()
-            )),
+            GateResolution::UnsatisfiedConstrain(message) => {
+                // Point at the failing constraint in the source code, when it is known
+                if let Some(gate_index) = gate_index {
+                    driver.report_unsatisfied_gate(compiled_program, gate_index, message.as_deref());
+                }
+                return Err(CliError::Generic(match message {
+                    Some(message) => format!("could not satisfy all constraints: {}", message),
+                    None => "could not satisfy all constraints".to_string(),
+                }))
+            },
             GateResolution::Resolved => (),
             _ => unreachable!(),
         }
 
-    Ok(solved_witness)
+    let return_value =
+        public_inputs.get(noirc_frontend::hir_def::function::MAIN_RETURN_NAME).cloned();
+    Ok((solved_witness, return_value))
 }
 
 fn export_public_inputs<P: AsRef<Path>>(
@@ -166,8 +200,9 @@ fn export_public_inputs<P: AsRef<Path>>(
     solved_witness: &BTreeMap<Witness, FieldElement>,
     witness_map: &BTreeMap<String, InputValue>,
     abi: &Abi,
+    format: Format,
     path: P,
-) -> Result<(), noirc_abi::errors::InputParserError> {
+) -> Result<BTreeMap<String, InputValue>, noirc_abi::errors::InputParserError> {
     // generate a name->value map for the public inputs, using the ABI and witness_map:
     let mut public_inputs = BTreeMap::new();
     public_inputs
@@ -178,23 +213,19 @@ fn export_public_inputs<P: AsRef<Path>>(
 
             let iv = if matches!(*v, InputValue::Undefined) {
                 let w_ret = w_ret.unwrap();
-                match &i.1 {
-                    AbiType::Array { length, .. } => {
-                        let return_values = noirc_frontend::util::vecmap(0..*length, |i| {
-                            *solved_witness.get(&Witness::new(w_ret.0 + i as u32)).unwrap()
-                        });
-                        InputValue::Vec(return_values)
-                    }
-                    _ => InputValue::Field(*solved_witness.get(&w_ret).unwrap()),
-                }
+                let mut return_values = (w_ret.0..).map(|witness_index| {
+                    *solved_witness.get(&Witness::new(witness_index)).unwrap()
+                });
+                InputValue::from_flattened(&mut return_values, &i.1)
             } else {
                 v.clone()
             };
             public_inputs.insert(i.0.clone(), iv);
         }
     }
-    //serialise public inputs into verifier.toml
-    noirc_abi::input_parser::Format::Toml.serialise(&path, VERIFIER_INPUT_FILE, &public_inputs)
+    //serialise public inputs into the verifier file
+    format.serialise(&path, VERIFIER_INPUT_FILE, &public_inputs)?;
+    Ok(public_inputs)
 }
 
 pub fn prove_with_path<P: AsRef<Path>>(
@@ -202,10 +233,12 @@ pub fn prove_with_path<P: AsRef<Path>>(
     program_dir: P,
     proof_dir: P,
     show_ssa: bool,
+    format_flag: Option<Format>,
+    backend: &dyn Backend,
 ) -> Result<PathBuf, CliError> {
-    let (compiled_program, solved_witness) = compile_circuit_and_witness(program_dir, show_ssa)?;
+    let (compiled_program, solved_witness) =
+        compile_circuit_and_witness(program_dir, show_ssa, format_flag, backend)?;
 
-    let backend = crate::backends::ConcreteBackend;
     let proof = backend.prove_with_meta(compiled_program.circuit, solved_witness);
 
     let mut proof_path = create_named_dir(proof_dir.as_ref(), "proof");
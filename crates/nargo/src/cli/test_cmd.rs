@@ -0,0 +1,133 @@
+use std::{collections::BTreeMap, io::Write, path::Path};
+
+use acvm::GateResolution;
+use clap::ArgMatches;
+use noirc_driver::Driver;
+use noirc_frontend::node_interner::FuncId;
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+use crate::{backends::Backend, errors::CliError, resolver::Resolver};
+
+pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
+    let args = args.subcommand_matches("test").unwrap();
+    let test_name = args.value_of("test_name").unwrap_or("");
+    let show_ssa = args.is_present("show-ssa");
+
+    let curr_dir = std::env::current_dir().unwrap();
+    let backend = crate::backends::resolve_backend(&curr_dir, args.value_of("backend"))?;
+    run_tests(&curr_dir, test_name, show_ssa, backend.as_ref())?.into_result()
+}
+
+/// The number of test functions which passed and failed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+impl TestSummary {
+    /// Returns an error if any test failed, so that nargo exits with a non-zero code
+    pub fn into_result(self) -> Result<(), CliError> {
+        if self.failed == 0 {
+            return Ok(());
+        }
+        let plural = if self.failed == 1 { "" } else { "s" };
+        Err(CliError::Generic(format!("{} test{} failed", self.failed, plural)))
+    }
+}
+
+pub(crate) fn run_tests<P: AsRef<Path>>(
+    program_dir: P,
+    test_name: &str,
+    show_ssa: bool,
+    backend: &dyn Backend,
+) -> Result<TestSummary, CliError> {
+    let mut driver = Resolver::resolve_root_config(program_dir.as_ref())?;
+    driver.build();
+
+    let test_functions = driver.get_all_test_functions_in_crate_matching(test_name);
+    println!("Running {} test functions...", test_functions.len());
+
+    let writer = StandardStream::stderr(ColorChoice::Always);
+    let mut writer = writer.lock();
+
+    let mut summary = TestSummary::default();
+    for test_function in test_functions {
+        let test_name = driver.function_name(test_function).to_owned();
+        write!(writer, "Testing {}... ", test_name).expect("cannot write to stderr");
+        writer.flush().ok();
+
+        match run_test(&driver, test_function, show_ssa, backend) {
+            Ok(_) => {
+                writer.set_color(ColorSpec::new().set_fg(Some(Color::Green))).ok();
+                writeln!(writer, "ok").ok();
+                summary.passed += 1;
+            }
+            // Test failures are reported at the definition of the test function
+            Err(msg) => {
+                writer.set_color(ColorSpec::new().set_fg(Some(Color::Red))).ok();
+                writeln!(writer, "FAIL").ok();
+                driver.report_at_function(
+                    test_function,
+                    format!("test `{}` failed", test_name),
+                    msg,
+                );
+                summary.failed += 1;
+            }
+        }
+        writer.reset().ok();
+    }
+
+    if summary.failed == 0 {
+        writer.set_color(ColorSpec::new().set_fg(Some(Color::Green))).unwrap();
+        writeln!(writer, "All tests passed").ok();
+        writer.reset().ok();
+    }
+    Ok(summary)
+}
+
+/// Compiles the test function as if it were the main function of the program
+/// and then solves the resulting circuit. A test passes if every constraint is satisfied.
+fn run_test(
+    driver: &Driver,
+    test_function: FuncId,
+    show_ssa: bool,
+    backend: &dyn Backend,
+) -> Result<(), String> {
+    if !driver.function_has_empty_signature(test_function) {
+        return Err("test functions cannot have parameters or return values".to_owned());
+    }
+
+    let program = match driver.compile_no_check(backend.np_language(), show_ssa, test_function) {
+        Ok(program) => program,
+        Err(err) => {
+            driver.report_runtime_error(&err);
+            return Err("could not compile test function".to_owned());
+        }
+    };
+
+    let mut solved_witness = BTreeMap::new();
+    let (solver_res, gate_index) = backend.solve_with_gate_index(
+        &mut solved_witness,
+        program.circuit.gates.clone(),
+        &program.debug.assert_messages,
+    );
+    match solver_res {
+        GateResolution::Resolved => Ok(()),
+        GateResolution::UnsatisfiedConstrain(message) => {
+            if let Some(gate_index) = gate_index {
+                driver.report_unsatisfied_gate(&program, gate_index, message.as_deref());
+            }
+            match message {
+                Some(message) => Err(format!("could not satisfy all constraints: {}", message)),
+                None => Err("could not satisfy all constraints".to_owned()),
+            }
+        }
+        GateResolution::UnsupportedOpcode(opcode) => Err(format!(
+            "backend does not currently support the {} opcode. ACVM does not currently fall back to arithmetic gates.",
+            opcode
+        )),
+        GateResolution::UnknownError(msg) => Err(msg),
+        GateResolution::Skip => Err("could not solve all witnesses in the test circuit".to_owned()),
+    }
+}
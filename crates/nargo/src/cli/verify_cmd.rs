@@ -1,9 +1,9 @@
 use super::{PROOFS_DIR, PROOF_EXT, VERIFIER_INPUT_FILE};
-use crate::{errors::CliError, resolver::Resolver};
+use crate::{backends::Backend, errors::CliError, resolver::Resolver};
 use acvm::FieldElement;
-use acvm::ProofSystemCompiler;
 use clap::ArgMatches;
-use noirc_abi::{input_parser::InputValue, Abi};
+use noirc_abi::input_parser::{Format, InputValue};
+use noirc_abi::Abi;
 use std::{collections::BTreeMap, path::Path, path::PathBuf};
 
 // The verifier.toml file will by default have an
@@ -13,25 +13,31 @@ use std::{collections::BTreeMap, path::Path, path::PathBuf};
 pub const RESERVED_PUBLIC_ARR: &str = "setpub";
 
 pub(crate) fn run(args: ArgMatches) -> Result<(), CliError> {
-    let proof_name = args.subcommand_matches("verify").unwrap().value_of("proof").unwrap();
+    let args = args.subcommand_matches("verify").unwrap();
+    let proof_name = args.value_of("proof").unwrap();
     let mut proof_path = std::path::PathBuf::new();
     proof_path.push(Path::new(PROOFS_DIR));
 
     proof_path.push(Path::new(proof_name));
     proof_path.set_extension(PROOF_EXT);
 
-    let result = verify(proof_name)?;
+    let result = verify(proof_name, super::format_flag(args), args.value_of("backend"))?;
     println!("Proof verified : {}\n", result);
     Ok(())
 }
 
-fn verify(proof_name: &str) -> Result<bool, CliError> {
+fn verify(
+    proof_name: &str,
+    format_flag: Option<Format>,
+    backend_flag: Option<&str>,
+) -> Result<bool, CliError> {
     let curr_dir = std::env::current_dir().unwrap();
+    let backend = crate::backends::resolve_backend(&curr_dir, backend_flag)?;
     let mut proof_path = PathBuf::new(); //or cur_dir?
     proof_path.push(PROOFS_DIR);
     proof_path.push(Path::new(proof_name));
     proof_path.set_extension(PROOF_EXT);
-    verify_with_path(&curr_dir, &proof_path, false)
+    verify_with_path(&curr_dir, &proof_path, false, format_flag, backend.as_ref())
 }
 
 fn process_abi_with_verifier_input(
@@ -48,19 +54,18 @@ fn process_abi_with_verifier_input(
             })
             .clone();
 
-        if !value.matches_abi(param_type) && param_name != RESERVED_PUBLIC_ARR {
-            return Err(CliError::Generic(format!("The parameters in the main do not match the parameters in the {}.toml file. \n Please check `{}` parameter. ", VERIFIER_INPUT_FILE,param_name)));
+        if !value.matches_abi(&param_type) && param_name != RESERVED_PUBLIC_ARR {
+            return Err(CliError::Generic(format!("The parameters in the main do not match the parameters in the {} file. \n Please check `{}` parameter. ", VERIFIER_INPUT_FILE,param_name)));
         }
 
         match value {
-            InputValue::Field(elem) => public_inputs.push(elem),
-            InputValue::Vec(vec_elem) => public_inputs.extend(vec_elem),
             InputValue::Undefined => {
                 return Err(CliError::Generic(format!(
-                    "The parameter {} is not defined in the {}.toml file.",
+                    "The parameter {} is not defined in the {} file.",
                     param_name, VERIFIER_INPUT_FILE
                 )))
             }
+            value => public_inputs.extend(value.flatten(&param_type)),
         }
     }
 
@@ -87,9 +92,10 @@ pub fn verify_with_path<P: AsRef<Path>>(
     program_dir: P,
     proof_path: P,
     show_ssa: bool,
+    format_flag: Option<Format>,
+    backend: &dyn Backend,
 ) -> Result<bool, CliError> {
     let driver = Resolver::resolve_root_config(program_dir.as_ref())?;
-    let backend = crate::backends::ConcreteBackend;
 
     let compiled_program = driver.into_compiled_program(backend.np_language(), show_ssa);
 
@@ -98,16 +104,16 @@ pub fn verify_with_path<P: AsRef<Path>>(
     let num_pub_params = public_abi.num_parameters();
     let mut public_inputs = BTreeMap::new();
     if num_pub_params != 0 {
-        let curr_dir = program_dir;
-        public_inputs = noirc_abi::input_parser::Format::Toml
-            .parse(curr_dir, VERIFIER_INPUT_FILE)
-            .map_err(CliError::from)?;
+        let curr_dir = program_dir.as_ref();
+        let format = super::input_format(curr_dir, VERIFIER_INPUT_FILE, format_flag);
+        public_inputs =
+            format.parse(curr_dir, VERIFIER_INPUT_FILE, &public_abi).map_err(CliError::from)?;
     }
 
     if num_pub_params != public_inputs.len() {
         // return Err(CliError::Generic(format!("")));
         panic!(
-            "Expected {} number of values in {}.toml, but got {} number of values",
+            "Expected {} number of values in the {} file, but got {} number of values",
             num_pub_params,
             VERIFIER_INPUT_FILE,
             public_inputs.len()
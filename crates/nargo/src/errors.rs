@@ -23,7 +23,7 @@ impl CliError {
             .expect("cannot set color for stderr in StandardStream");
         writeln!(&mut stderr, "{}", msg).expect("cannot write to stderr");
 
-        std::process::exit(0)
+        std::process::exit(1)
     }
 }
 
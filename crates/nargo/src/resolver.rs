@@ -55,6 +55,12 @@ impl<'a> Resolver<'a> {
 
         let cfg_path = super::find_package_config(dir_path)?;
         let cfg = super::toml::parse(cfg_path)?;
+        if let Some(max_recursion_depth) = cfg.package.max_recursion_depth {
+            driver.set_max_recursion_depth(max_recursion_depth);
+        }
+        if let Some(checked_arithmetic) = cfg.package.checked_arithmetic {
+            driver.set_checked_arithmetic(checked_arithmetic);
+        }
 
         let crate_id = driver.create_local_crate(entry_path, crate_type);
 
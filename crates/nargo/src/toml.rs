@@ -36,6 +36,10 @@ pub struct Package {
     // so you will not need to supply an ACIR and compiler version
     pub compiler_version: Option<String>,
     pub backend: Option<String>,
+    // The limit on the number of nested calls to recursive functions
+    pub max_recursion_depth: Option<u32>,
+    // Whether integer arithmetic is checked for overflows, unless a function selects otherwise
+    pub checked_arithmetic: Option<bool>,
     pub license: Option<String>,
 }
 
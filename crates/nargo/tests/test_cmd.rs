@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use nargo::cli::TestSummary;
+
+const TEST_DIR: &str = "tests";
+const TEST_DATA_DIR: &str = "test_data";
+
+mod tests {
+    use super::*;
+
+    fn test_data_dir(name: &str) -> PathBuf {
+        let mut dir = std::env::current_dir().unwrap();
+        dir.push(TEST_DIR);
+        dir.push(TEST_DATA_DIR);
+        dir.push(name);
+        dir
+    }
+
+    fn nargo_test_succeeds(name: &str) -> bool {
+        let status = Command::new(env!("CARGO_BIN_EXE_nargo"))
+            .arg("test")
+            .current_dir(test_data_dir(name))
+            .status()
+            .expect("failed to run nargo");
+        status.success()
+    }
+
+    #[test]
+    fn passing_unit_tests() {
+        let summary = nargo::cli::run_tests(&test_data_dir("unit_tests"), false).unwrap();
+        assert_eq!(summary, TestSummary { passed: 2, failed: 0 });
+        assert!(summary.into_result().is_ok());
+        assert!(nargo_test_succeeds("unit_tests"));
+    }
+
+    #[test]
+    fn failing_unit_tests() {
+        // The failing test is reported without stopping the test run after it
+        let summary = nargo::cli::run_tests(&test_data_dir("failing_unit_tests"), false).unwrap();
+        assert_eq!(summary, TestSummary { passed: 1, failed: 1 });
+        assert!(summary.into_result().is_err());
+        assert!(!nargo_test_succeeds("failing_unit_tests"));
+    }
+}
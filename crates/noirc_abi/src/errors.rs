@@ -1,24 +1,32 @@
 use std::path::PathBuf;
 
+use crate::{input_parser::Format, AbiType};
+
 #[derive(Debug)]
 pub enum InputParserError {
-    MissingTomlFile(PathBuf),
+    MissingInputFile(Format, PathBuf),
     ParseTomlMap(String),
-    SaveTomlFile(std::io::Error),
+    ParseJsonMap(String),
+    SaveInputFile(Format, std::io::Error),
     ParseStr(String),
     ParseHexStr(String),
     DuplicateVariableName(String),
+    UnknownParameter(String),
+    AbiTypeMismatch(AbiType),
 }
 
 impl std::fmt::Display for InputParserError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            InputParserError::MissingTomlFile(path) => write!(f, "cannot find input file located at {:?}, run nargo build to generate the missing Prover and/or Verifier toml files", path),
+            InputParserError::MissingInputFile(format, path) => write!(f, "cannot find input file located at {:?}, run nargo build to generate the missing Prover and/or Verifier {} files", path, format.ext()),
             InputParserError::ParseTomlMap(err_msg) => write!(f, "input.toml file is badly formed, could not parse, {}", err_msg),
-            InputParserError::SaveTomlFile(err) => write!(f, "could not save file to disk, {}", err),
+            InputParserError::ParseJsonMap(err_msg) => write!(f, "input.json file is badly formed, could not parse, {}", err_msg),
+            InputParserError::SaveInputFile(format, err) => write!(f, "could not save {} file to disk, {}", format.ext(), err),
             InputParserError::ParseStr(err_msg) => write!(f, "Expected witness values to be integers, provided value causes `{}` error", err_msg),
             InputParserError::ParseHexStr(err_msg) => write!(f, "Could not parse hex value {}", err_msg),
-            InputParserError::DuplicateVariableName(err_msg) => write!(f, "duplicate variable name {}", err_msg)
+            InputParserError::DuplicateVariableName(err_msg) => write!(f, "duplicate variable name {}", err_msg),
+            InputParserError::UnknownParameter(name) => write!(f, "{} is not a parameter of the program", name),
+            InputParserError::AbiTypeMismatch(typ) => write!(f, "input value does not match the parameter type {:?}", typ),
         }
     }
 }
@@ -0,0 +1,180 @@
+use super::InputValue;
+use crate::errors::InputParserError;
+use crate::{AbiType, Sign};
+use acvm::FieldElement;
+use serde::Serialize;
+use serde_derive::Deserialize;
+use std::collections::BTreeMap;
+
+/// Converts the parsed mapping to the native representation that the compiler
+/// understands for Inputs. Each value is parsed according to the type of the
+/// parameter of the same name.
+pub(super) fn map_to_field(
+    input_map: BTreeMap<String, InputTypes>,
+    parameters: &[(String, AbiType)],
+) -> Result<BTreeMap<String, InputValue>, InputParserError> {
+    let mut field_map = BTreeMap::new();
+    for (parameter, value) in input_map {
+        let param_type = match parameters.iter().find(|(name, _)| *name == parameter) {
+            Some((_, param_type)) => param_type,
+            None => return Err(InputParserError::UnknownParameter(parameter)),
+        };
+        let new_value = to_input_value(value, param_type)?;
+        check_map_duplicates(&mut field_map, parameter, new_value)?
+    }
+
+    Ok(field_map)
+}
+
+fn to_input_value(value: InputTypes, param_type: &AbiType) -> Result<InputValue, InputParserError> {
+    // Arrays of numbers and strings are only special cases of arrays
+    let value = match value {
+        InputTypes::ArrayNum(arr_num) => {
+            InputTypes::Array(arr_num.into_iter().map(InputTypes::Integer).collect())
+        }
+        InputTypes::ArrayString(arr_str) => {
+            InputTypes::Array(arr_str.into_iter().map(InputTypes::String).collect())
+        }
+        other => other,
+    };
+
+    let input_value = match (value, param_type) {
+        // Values are left empty when they are solved for, such as the return value of `main`
+        (InputTypes::String(string), _) if string.is_empty() => InputValue::Undefined,
+        (InputTypes::String(string), AbiType::String { .. }) => InputValue::String(string),
+        (InputTypes::String(string), param_type) if param_type.is_scalar() => {
+            match parse_str(&string)? {
+                Some(new_value) => InputValue::Field(new_value),
+                None => InputValue::Undefined,
+            }
+        }
+        (InputTypes::Integer(integer), param_type) if param_type.is_scalar() => {
+            InputValue::Field(FieldElement::from(integer as u128))
+        }
+        (InputTypes::Bool(boolean), param_type) if param_type.is_scalar() => {
+            let new_value = if boolean { FieldElement::one() } else { FieldElement::zero() };
+            InputValue::Field(new_value)
+        }
+        (InputTypes::Array(array), AbiType::Array { typ, .. }) => {
+            let elements = array
+                .into_iter()
+                .map(|elem| to_input_value(elem, typ))
+                .collect::<Result<Vec<_>, _>>()?;
+            flat_or_nested(elements)
+        }
+        // A string may also be given as an array of its bytes
+        (InputTypes::Array(array), AbiType::String { .. }) => {
+            let byte_type = AbiType::Integer {
+                visibility: param_type.visibility(),
+                sign: Sign::Unsigned,
+                width: 8,
+            };
+            let elements = array
+                .into_iter()
+                .map(|elem| to_input_value(elem, &byte_type))
+                .collect::<Result<Vec<_>, _>>()?;
+            flat_or_nested(elements)
+        }
+        (InputTypes::Array(array), AbiType::Tuple { fields, .. }) => {
+            if array.len() != fields.len() {
+                return Err(InputParserError::AbiTypeMismatch(param_type.clone()));
+            }
+            let elements = array
+                .into_iter()
+                .zip(fields)
+                .map(|(elem, typ)| to_input_value(elem, typ))
+                .collect::<Result<Vec<_>, _>>()?;
+            flat_or_nested(elements)
+        }
+        (InputTypes::Table(table), AbiType::Struct { fields, .. }) => {
+            InputValue::Struct(map_to_field(table, fields)?)
+        }
+        (_, param_type) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
+    };
+    Ok(input_value)
+}
+
+/// Arrays of field elements keep their flat representation
+fn flat_or_nested(elements: Vec<InputValue>) -> InputValue {
+    let fields: Option<Vec<_>> = elements
+        .iter()
+        .map(|elem| match elem {
+            InputValue::Field(field) => Some(*field),
+            _ => None,
+        })
+        .collect();
+    match fields {
+        Some(fields) => InputValue::Vec(fields),
+        None => InputValue::Array(elements),
+    }
+}
+
+pub(super) fn remap(map: &BTreeMap<String, InputValue>) -> BTreeMap<String, InputTypes> {
+    map.iter().map(|(parameter, value)| (parameter.clone(), from_input_value(value))).collect()
+}
+
+fn from_input_value(value: &InputValue) -> InputTypes {
+    match value {
+        InputValue::Field(f) => InputTypes::String(format!("0x{}", f.to_hex())),
+        InputValue::Vec(v) => {
+            let array = v.iter().map(|i| format!("0x{}", i.to_hex())).collect();
+            InputTypes::ArrayString(array)
+        }
+        InputValue::Array(elements) => {
+            InputTypes::Array(elements.iter().map(from_input_value).collect())
+        }
+        InputValue::Struct(fields) => InputTypes::Table(remap(fields)),
+        InputValue::String(string) => InputTypes::String(string.clone()),
+        InputValue::Undefined => unreachable!(),
+    }
+}
+
+fn check_map_duplicates(
+    field_map: &mut BTreeMap<String, InputValue>,
+    parameter: String,
+    new_value: InputValue,
+) -> Result<(), InputParserError> {
+    match field_map.insert(parameter.clone(), new_value) {
+        Some(_) => Err(InputParserError::DuplicateVariableName(parameter)),
+        None => Ok(()),
+    }
+}
+
+/// The values which can appear in an input file, independently of its format
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub(super) enum InputTypes {
+    // A decimal or hex string for a field element or integer,
+    // or the UTF-8 value of a `str` parameter
+    String(String),
+    // Just a regular integer, that can fit in 128 bits
+    Integer(u64),
+    // Simple boolean flag
+    Bool(bool),
+    // Array of regular integers
+    ArrayNum(Vec<u64>),
+    // Array of hexadecimal integers
+    ArrayString(Vec<String>),
+    // Array of mixed or nested values, such as an array of structs
+    Array(Vec<InputTypes>),
+    // Struct
+    Table(BTreeMap<String, InputTypes>),
+}
+
+fn parse_str(value: &str) -> Result<Option<FieldElement>, InputParserError> {
+    if value.is_empty() {
+        Ok(None)
+    } else if value.starts_with("0x") {
+        let result = FieldElement::from_hex(value);
+        if result.is_some() {
+            Ok(result)
+        } else {
+            Err(InputParserError::ParseHexStr(value.to_owned()))
+        }
+    } else {
+        let val: i128 = value
+            .parse::<i128>()
+            .map_err(|err_msg| InputParserError::ParseStr(err_msg.to_string()))?;
+        Ok(Some(FieldElement::from(val)))
+    }
+}
@@ -0,0 +1,38 @@
+use super::input_types::{map_to_field, remap, InputTypes};
+use super::{Format, InputValue};
+use crate::errors::InputParserError;
+use crate::Abi;
+use std::{collections::BTreeMap, path::Path};
+
+pub(crate) fn parse<P: AsRef<Path>>(
+    path_to_json: P,
+    abi: &Abi,
+) -> Result<BTreeMap<String, InputValue>, InputParserError> {
+    let path_to_json = path_to_json.as_ref();
+    if !path_to_json.exists() {
+        return Err(InputParserError::MissingInputFile(Format::Json, path_to_json.to_path_buf()));
+    }
+    let input_as_string = std::fs::read_to_string(path_to_json).unwrap();
+
+    // Parse the json object into a BTreeMap, converting the argument to field elements
+    let data: BTreeMap<String, InputTypes> = serde_json::from_str(&input_as_string)
+        .map_err(|err_msg| InputParserError::ParseJsonMap(err_msg.to_string()))?;
+    map_to_field(data, &abi.parameters)
+}
+
+pub fn serialise<P: AsRef<Path>>(
+    path_to_json: P,
+    w_map: &BTreeMap<String, InputValue>,
+) -> Result<(), InputParserError> {
+    let path_to_json = path_to_json.as_ref();
+    if !path_to_json.exists() {
+        return Err(InputParserError::MissingInputFile(Format::Json, path_to_json.to_path_buf()));
+    }
+    let to_map = remap(w_map);
+    let json_string = serde_json::to_string_pretty(&to_map)
+        .map_err(|err_msg| InputParserError::ParseJsonMap(err_msg.to_string()))?;
+    std::fs::write(path_to_json, json_string)
+        .map_err(|err| InputParserError::SaveInputFile(Format::Json, err))?;
+
+    Ok(())
+}
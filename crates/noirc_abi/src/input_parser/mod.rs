@@ -1,3 +1,5 @@
+mod input_types;
+mod json;
 mod toml;
 
 use std::{collections::BTreeMap, path::Path};
@@ -6,33 +8,131 @@ use acvm::FieldElement;
 use serde::Serialize;
 
 use crate::errors::InputParserError;
-use crate::AbiType;
+use crate::{Abi, AbiType};
 /// This is what all formats eventually transform into
 /// For example, a toml file will parse into TomlTypes
 /// and those TomlTypes will be mapped to Value
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum InputValue {
     Field(FieldElement),
     Vec(Vec<FieldElement>),
+    /// An array or tuple, whose elements are not all field elements
+    Array(Vec<InputValue>),
+    Struct(BTreeMap<String, InputValue>),
+    /// The value of a `str` parameter
+    String(String),
     Undefined,
 }
 
 impl InputValue {
     /// Checks whether the ABI type matches the InputValue type
     /// and also their arity
-    pub fn matches_abi(&self, abi_param: AbiType) -> bool {
+    pub fn matches_abi(&self, abi_param: &AbiType) -> bool {
         match (self, abi_param) {
-            (InputValue::Field(_), AbiType::Field(_)) => true,
-            (InputValue::Field(_), AbiType::Array { .. }) => false,
-            (InputValue::Field(_), AbiType::Integer { .. }) => true,
-            (InputValue::Vec(_), AbiType::Field(_)) => false,
-            (InputValue::Vec(x), AbiType::Array { length, .. }) => x.len() == length as usize,
-            (InputValue::Vec(_), AbiType::Integer { .. }) => false,
             (InputValue::Undefined, _) => true,
+            (InputValue::Field(_), abi_param) => abi_param.is_scalar(),
+            (InputValue::Vec(x), AbiType::Array { length, typ, .. }) => {
+                x.len() == *length as usize && typ.is_scalar()
+            }
+            (InputValue::String(string), AbiType::String { length, .. }) => {
+                string.len() == *length as usize
+            }
+            // A string may also be given as an array of its bytes
+            (InputValue::Vec(x), AbiType::String { length, .. }) => x.len() == *length as usize,
+            (InputValue::Vec(x), AbiType::Tuple { fields, .. }) => {
+                x.len() == fields.len() && fields.iter().all(AbiType::is_scalar)
+            }
+            (InputValue::Array(x), AbiType::Array { length, typ, .. }) => {
+                x.len() == *length as usize && x.iter().all(|elem| elem.matches_abi(typ))
+            }
+            (InputValue::Array(x), AbiType::Tuple { fields, .. }) => {
+                x.len() == fields.len()
+                    && x.iter().zip(fields).all(|(elem, typ)| elem.matches_abi(typ))
+            }
+            (InputValue::Struct(map), AbiType::Struct { fields, .. }) => {
+                map.len() == fields.len()
+                    && fields.iter().all(|(name, typ)| {
+                        map.get(name).map_or(false, |value| value.matches_abi(typ))
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    /// Flattens the value into field elements, in the order described by `AbiType`.
+    ///
+    /// The value must match the ABI type and must not be undefined.
+    pub fn flatten(&self, abi_param: &AbiType) -> Vec<FieldElement> {
+        match (self, abi_param) {
+            (InputValue::Field(element), _) => vec![*element],
+            (InputValue::Vec(elements), _) => elements.clone(),
+            (InputValue::String(string), _) => {
+                string.bytes().map(|byte| FieldElement::from(byte as u128)).collect()
+            }
+            (InputValue::Array(elements), AbiType::Array { typ, .. }) => {
+                elements.iter().flat_map(|elem| elem.flatten(typ)).collect()
+            }
+            (InputValue::Array(elements), AbiType::Tuple { fields, .. }) => {
+                elements.iter().zip(fields).flat_map(|(elem, typ)| elem.flatten(typ)).collect()
+            }
+            (InputValue::Struct(map), AbiType::Struct { fields, .. }) => {
+                fields.iter().flat_map(|(name, typ)| map[name].flatten(typ)).collect()
+            }
+            (InputValue::Undefined, _) => unreachable!("cannot flatten an undefined value"),
+            _ => unreachable!("input value does not match the ABI type {:?}", abi_param),
+        }
+    }
+
+    /// Rebuilds a value of the given ABI type from its flattened field elements.
+    /// This is the inverse of `InputValue::flatten`.
+    pub fn from_flattened(
+        elements: &mut impl Iterator<Item = FieldElement>,
+        abi_param: &AbiType,
+    ) -> InputValue {
+        match abi_param {
+            AbiType::Field(_) | AbiType::Integer { .. } | AbiType::Boolean(_) => {
+                InputValue::Field(next_element(elements))
+            }
+            AbiType::Array { length, typ, .. } if typ.is_scalar() => {
+                InputValue::Vec((0..*length).map(|_| next_element(elements)).collect())
+            }
+            AbiType::Array { length, typ, .. } => InputValue::Array(
+                (0..*length).map(|_| InputValue::from_flattened(elements, typ)).collect(),
+            ),
+            AbiType::String { length, .. } => {
+                let elements: Vec<_> = (0..*length).map(|_| next_element(elements)).collect();
+                let bytes: Option<Vec<u8>> = elements
+                    .iter()
+                    .map(|element| element.try_to_u64().filter(|byte| *byte <= u8::MAX as u64))
+                    .map(|byte| byte.map(|byte| byte as u8))
+                    .collect();
+
+                // Fall back to the raw elements if they are not the bytes of a utf-8 string
+                match bytes.and_then(|bytes| String::from_utf8(bytes).ok()) {
+                    Some(string) => InputValue::String(string),
+                    None => InputValue::Vec(elements),
+                }
+            }
+            AbiType::Tuple { fields, .. } if fields.iter().all(AbiType::is_scalar) => {
+                InputValue::Vec(fields.iter().map(|_| next_element(elements)).collect())
+            }
+            AbiType::Tuple { fields, .. } => InputValue::Array(
+                fields.iter().map(|typ| InputValue::from_flattened(elements, typ)).collect(),
+            ),
+            AbiType::Struct { fields, .. } => InputValue::Struct(
+                fields
+                    .iter()
+                    .map(|(name, typ)| (name.clone(), InputValue::from_flattened(elements, typ)))
+                    .collect(),
+            ),
         }
     }
 }
 
+fn next_element(elements: &mut impl Iterator<Item = FieldElement>) -> FieldElement {
+    elements.next().expect("not enough field elements for the ABI type")
+}
+
 /// Parses the initial Witness Values that are needed to seed the
 /// Partial Witness generator
 pub trait InitialWitnessParser {
@@ -41,31 +141,44 @@ pub trait InitialWitnessParser {
 
 /// The different formats that are supported when parsing
 /// the initial witness values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Format {
     Toml,
+    Json,
 }
 
 impl Format {
     pub fn ext(&self) -> &'static str {
         match self {
             Format::Toml => "toml",
+            Format::Json => "json",
+        }
+    }
+
+    /// Returns the format which uses the given file extension
+    pub fn from_ext(ext: &str) -> Option<Format> {
+        match ext {
+            "toml" => Some(Format::Toml),
+            "json" => Some(Format::Json),
+            _ => None,
         }
     }
 }
 
 impl Format {
+    /// Parses the input file, reading each value as the type of the ABI parameter of the same name
     pub fn parse<P: AsRef<Path>>(
         &self,
         path: P,
         file_name: &str,
+        abi: &Abi,
     ) -> Result<BTreeMap<String, InputValue>, InputParserError> {
+        let mut dir_path = path.as_ref().to_path_buf();
+        dir_path.push(file_name);
+        dir_path.set_extension(self.ext());
         match self {
-            Format::Toml => {
-                let mut dir_path = path.as_ref().to_path_buf();
-                dir_path.push(file_name);
-                dir_path.set_extension(self.ext());
-                toml::parse(dir_path)
-            }
+            Format::Toml => toml::parse(dir_path, abi),
+            Format::Json => json::parse(dir_path, abi),
         }
     }
 
@@ -75,14 +188,91 @@ impl Format {
         file_name: &str,
         w_map: &BTreeMap<String, InputValue>,
     ) -> Result<(), InputParserError> {
+        let mut dir_path = path.as_ref().to_path_buf();
+        dir_path.push(file_name);
+        dir_path.set_extension(self.ext());
         match self {
-            Format::Toml => {
-                let mut dir_path = path.as_ref().to_path_buf();
-                dir_path.push(file_name);
-                dir_path.set_extension(self.ext());
-                toml::serialise(dir_path, w_map)?;
-            }
+            Format::Toml => toml::serialise(dir_path, w_map),
+            Format::Json => json::serialise(dir_path, w_map),
         }
-        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::FieldElement;
+
+    use super::{Format, InputValue};
+    use crate::errors::InputParserError;
+    use crate::{Abi, AbiFEType, AbiType};
+
+    fn round_trip(format: Format) {
+        let mut point = BTreeMap::new();
+        point.insert("x".to_owned(), InputValue::Field(FieldElement::from(3_u128)));
+        point.insert("y".to_owned(), InputValue::Field(FieldElement::from(4_u128)));
+
+        let mut values = BTreeMap::new();
+        values.insert("a".to_owned(), InputValue::Field(FieldElement::from(7_u128)));
+        values.insert(
+            "b".to_owned(),
+            InputValue::Vec(vec![FieldElement::from(1_u128), FieldElement::from(2_u128)]),
+        );
+        // Strings which look like numbers are still read as strings
+        values.insert("c".to_owned(), InputValue::String("12345".to_owned()));
+        values.insert("d".to_owned(), InputValue::Struct(point));
+
+        let private = AbiFEType::Private;
+        let field = AbiType::Field(private);
+        let point_fields = vec![("x".to_owned(), field.clone()), ("y".to_owned(), field.clone())];
+        let abi = Abi {
+            parameters: vec![
+                ("a".to_owned(), field.clone()),
+                (
+                    "b".to_owned(),
+                    AbiType::Array { visibility: private, length: 2, typ: Box::new(field) },
+                ),
+                ("c".to_owned(), AbiType::String { visibility: private, length: 5 }),
+                ("d".to_owned(), AbiType::Struct { visibility: private, fields: point_fields }),
+            ],
+        };
+
+        let dir = std::env::temp_dir().join(format!("noirc_abi_round_trip_{}", format.ext()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Only existing files are serialised to
+        let file_name = "Verifier";
+        std::fs::write(dir.join(file_name).with_extension(format.ext()), "").unwrap();
+
+        format.serialise(&dir, file_name, &values).unwrap();
+        let parsed = format.parse(&dir, file_name, &abi).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(parsed, values);
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        round_trip(Format::Toml);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        round_trip(Format::Json);
+    }
+
+    #[test]
+    fn malformed_field() {
+        let abi = Abi { parameters: vec![("a".to_owned(), AbiType::Field(AbiFEType::Private))] };
+
+        let dir = std::env::temp_dir().join("noirc_abi_malformed_field");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("Prover.toml"), "a = \"hello\"").unwrap();
+
+        let parsed = Format::Toml.parse(&dir, "Prover", &abi);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(parsed, Err(InputParserError::ParseStr(_))));
     }
 }
@@ -1,4 +1,6 @@
+use acvm::acir::native_types::Witness;
 use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
 
 // This is the ABI used to bridge the different TOML formats for the initial
 // witness, the partial witness generator and the interpreter.
@@ -11,18 +13,20 @@ pub mod input_parser;
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 /// Types that are allowed in the (main function in binary)
 ///
-/// we use this separation so that we can have types like Strings
-/// without needing to introduce this in the Noir types
+/// we use this separation so that the ABI does not need to depend on the Noir types
 ///
-/// NOTE: If Strings are introduced as a native type, the translation will
-/// be straightforward. Whether exotic types like String will be natively supported
-/// depends on the types of programs that users want to do. I don't envision string manipulation
-/// in programs, however it is possible to support, with many complications like encoding character set
-/// support.
+/// Values are flattened into witnesses depth-first: the elements of arrays and tuples are
+/// laid out in order, and the fields of structs in the order in which they appear in
+/// `fields`, which is sorted by field name. A `bool` takes a single witness and a string
+/// takes one witness per byte of its utf-8 encoding.
 pub enum AbiType {
     Field(AbiFEType),
     Array { visibility: AbiFEType, length: u128, typ: Box<AbiType> },
     Integer { visibility: AbiFEType, sign: Sign, width: u32 },
+    Boolean(AbiFEType),
+    String { visibility: AbiFEType, length: u128 },
+    Struct { visibility: AbiFEType, fields: Vec<(String, AbiType)> },
+    Tuple { visibility: AbiFEType, fields: Vec<AbiType> },
 }
 /// This is the same as the FieldElementType in AST, without constants.
 /// We don't want the ABI to depend on Noir, so types are not shared between the two
@@ -57,18 +61,44 @@ pub enum Sign {
 impl AbiType {
     pub fn num_elements(&self) -> usize {
         match self {
-            AbiType::Field(_) | AbiType::Integer { .. } => 1,
+            AbiType::Field(_) | AbiType::Integer { .. } | AbiType::Boolean(_) => 1,
             AbiType::Array { visibility: _, length, typ: _ } => *length as usize,
+            AbiType::String { length, .. } => *length as usize,
+            AbiType::Struct { fields, .. } => fields.len(),
+            AbiType::Tuple { fields, .. } => fields.len(),
         }
     }
 
-    pub fn is_public(&self) -> bool {
+    /// Returns the number of field elements (and so witnesses) needed to represent this type
+    pub fn field_count(&self) -> usize {
         match self {
-            AbiType::Field(fe_type) => fe_type == &AbiFEType::Public,
-            AbiType::Array { visibility, length: _, typ: _ } => visibility == &AbiFEType::Public,
-            AbiType::Integer { visibility, sign: _, width: _ } => visibility == &AbiFEType::Public,
+            AbiType::Field(_) | AbiType::Integer { .. } | AbiType::Boolean(_) => 1,
+            AbiType::Array { length, typ, .. } => *length as usize * typ.field_count(),
+            AbiType::String { length, .. } => *length as usize,
+            AbiType::Struct { fields, .. } => fields.iter().map(|(_, typ)| typ.field_count()).sum(),
+            AbiType::Tuple { fields, .. } => fields.iter().map(AbiType::field_count).sum(),
         }
     }
+
+    /// Returns true if this type is represented by a single field element
+    pub fn is_scalar(&self) -> bool {
+        matches!(self, AbiType::Field(_) | AbiType::Integer { .. } | AbiType::Boolean(_))
+    }
+
+    pub fn visibility(&self) -> AbiFEType {
+        match self {
+            AbiType::Field(fe_type) | AbiType::Boolean(fe_type) => *fe_type,
+            AbiType::Array { visibility, .. }
+            | AbiType::Integer { visibility, .. }
+            | AbiType::String { visibility, .. }
+            | AbiType::Struct { visibility, .. }
+            | AbiType::Tuple { visibility, .. } => *visibility,
+        }
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.visibility() == AbiFEType::Public
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -98,15 +128,101 @@ impl Serialize for Abi {
     where
         S: Serializer,
     {
-        let vec: Vec<u8> = Vec::new();
         let mut map = serializer.serialize_map(Some(self.parameters.len()))?;
-        for (param_name, param_type) in &self.parameters {
-            match param_type {
-                AbiType::Field(_) => map.serialize_entry(&param_name, "")?,
-                AbiType::Array { .. } => map.serialize_entry(&param_name, &vec)?,
-                AbiType::Integer { .. } => map.serialize_entry(&param_name, "")?,
-            };
+        // TOML requires tables to come after all of the other values,
+        // so struct parameters are serialised last
+        let (tables, values): (Vec<_>, Vec<_>) = self
+            .parameters
+            .iter()
+            .partition(|(_, param_type)| matches!(param_type, AbiType::Struct { .. }));
+        for (param_name, param_type) in values.into_iter().chain(tables) {
+            map.serialize_entry(&param_name, &TemplateValue(param_type))?;
         }
         map.end()
     }
 }
+
+/// The empty value which is written for each parameter when creating an input file template
+struct TemplateValue<'a>(&'a AbiType);
+
+impl Serialize for TemplateValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            AbiType::Field(_)
+            | AbiType::Integer { .. }
+            | AbiType::Boolean(_)
+            | AbiType::String { .. } => serializer.serialize_str(""),
+            AbiType::Array { .. } | AbiType::Tuple { .. } => {
+                let vec: Vec<u8> = Vec::new();
+                vec.serialize(serializer)
+            }
+            AbiType::Struct { fields, .. } => {
+                let (tables, values): (Vec<_>, Vec<_>) =
+                    fields.iter().partition(|(_, typ)| matches!(typ, AbiType::Struct { .. }));
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (field_name, field_type) in values.into_iter().chain(tables) {
+                    map.serialize_entry(field_name, &TemplateValue(field_type))?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// The version of the format of `AbiArtifact`.
+/// This is increased whenever a change to the format is not backwards compatible.
+pub const ABI_ARTIFACT_VERSION: u32 = 1;
+
+/// A complete description of the ABI of a program, which is written out alongside the circuit.
+///
+/// Unlike `Abi`, which serialises into a template for the input files, this keeps the type
+/// of each parameter so that other tools can encode inputs without parsing Noir.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiArtifact {
+    pub version: u32,
+    pub parameters: Vec<AbiParameter>,
+    pub return_value: Option<AbiParameter>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiParameter {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: AbiType,
+    pub visibility: AbiFEType,
+    /// The witnesses which hold the value of the parameter, in the order
+    /// in which the value is flattened
+    pub witnesses: Vec<Witness>,
+}
+
+impl AbiArtifact {
+    /// Creates the artifact for the given ABI.
+    /// The parameter named `return_name` is the value returned by main.
+    pub fn new(
+        abi: &Abi,
+        param_witnesses: &BTreeMap<String, Vec<Witness>>,
+        return_name: &str,
+    ) -> AbiArtifact {
+        let mut parameters = Vec::with_capacity(abi.parameters.len());
+        let mut return_value = None;
+
+        for (name, typ) in &abi.parameters {
+            let parameter = AbiParameter {
+                name: name.clone(),
+                typ: typ.clone(),
+                visibility: typ.visibility(),
+                witnesses: param_witnesses.get(name).cloned().unwrap_or_default(),
+            };
+            if name == return_name {
+                return_value = Some(parameter);
+            } else {
+                parameters.push(parameter);
+            }
+        }
+
+        AbiArtifact { version: ABI_ARTIFACT_VERSION, parameters, return_value }
+    }
+}
@@ -1,29 +1,60 @@
 use acvm::acir::circuit::Circuit;
 use fm::FileType;
-use noirc_abi::Abi;
-use noirc_errors::{DiagnosableError, Reporter};
-use noirc_evaluator::create_circuit;
+use noirc_abi::{Abi, AbiArtifact};
+use noirc_errors::{CustomDiagnostic, DiagnosableError, Reporter};
+use noirc_evaluator::{create_circuit, DebugInfo, RuntimeError, DEFAULT_MAX_RECURSION_DEPTH};
 use noirc_frontend::graph::{CrateId, CrateName, CrateType, LOCAL_CRATE};
 use noirc_frontend::hir::def_map::CrateDefMap;
 use noirc_frontend::hir::Context;
+use noirc_frontend::hir_def::function::MAIN_RETURN_NAME;
 use noirc_frontend::monomorphisation::monomorphise;
+use noirc_frontend::node_interner::FuncId;
+use noirc_frontend::Type;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub struct Driver {
     context: Context,
+    max_recursion_depth: u32,
+    checked_arithmetic: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CompiledProgram {
     pub circuit: Circuit,
     pub abi: Option<noirc_abi::Abi>,
+    #[serde(skip)]
+    pub debug: DebugInfo,
+}
+
+impl CompiledProgram {
+    /// Returns the full description of the ABI of the program, if it has one
+    pub fn abi_artifact(&self) -> Option<AbiArtifact> {
+        let abi = self.abi.as_ref()?;
+        Some(AbiArtifact::new(abi, &self.debug.param_witnesses, MAIN_RETURN_NAME))
+    }
 }
 
 impl Driver {
     pub fn new() -> Self {
-        Driver { context: Context::default() }
+        Driver {
+            context: Context::default(),
+            max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
+            checked_arithmetic: false,
+        }
+    }
+
+    /// Sets the limit on the number of nested calls to recursive functions
+    /// which are expanded when compiling the program
+    pub fn set_max_recursion_depth(&mut self, max_recursion_depth: u32) {
+        self.max_recursion_depth = max_recursion_depth;
+    }
+
+    /// Sets whether integer additions, subtractions and multiplications are constrained
+    /// not to overflow in the functions which are not marked `#[checked]` or `#[unchecked]`
+    pub fn set_checked_arithmetic(&mut self, checked_arithmetic: bool) {
+        self.checked_arithmetic = checked_arithmetic;
     }
 
     // This is here for backwards compatibility
@@ -139,6 +170,13 @@ impl Driver {
         np_language: acvm::Language,
         show_ssa: bool,
     ) -> CompiledProgram {
+        self.compile_main(np_language, show_ssa)
+    }
+
+    /// Compiles the main function of the local crate.
+    /// Unlike `into_compiled_program`, this keeps the driver around so that
+    /// it can be used to report errors found when solving the circuit.
+    pub fn compile_main(&mut self, np_language: acvm::Language, show_ssa: bool) -> CompiledProgram {
         self.build();
 
         // Check the crate type
@@ -156,29 +194,105 @@ impl Driver {
         let main_function =
             local_crate.main_function().expect("cannot compile a program with no main function");
 
-        // Create ABI for main function
-        let func_meta = self.context.def_interner.function_meta(&main_function);
-        let abi = func_meta.into_abi(&self.context.def_interner);
-
-        let ast = monomorphise(main_function, self.context.def_interner);
-
-        // Compile Program
-        let circuit = match create_circuit(ast, np_language, show_ssa) {
-            Ok(circuit) => circuit,
+        match self.compile_no_check(np_language, show_ssa, main_function) {
+            Ok(program) => program,
             Err(err) => {
-                // The FileId here will be the file id of the file with the main file
-                // Errors will be shown at the callsite without a stacktrace
-                Reporter::with_diagnostics(
-                    err.location.file,
-                    &self.context.file_manager,
-                    &[err.to_diagnostic()],
-                );
+                self.report_runtime_error(&err);
                 Reporter::finish(1);
                 unreachable!("reporter will exit before this point")
             }
+        }
+    }
+
+    /// Compiles the function `func_id` as if it were the entry point of the program.
+    /// This assumes that `build` has already been called and that the crate type has been checked.
+    pub fn compile_no_check(
+        &self,
+        np_language: acvm::Language,
+        show_ssa: bool,
+        func_id: FuncId,
+    ) -> Result<CompiledProgram, RuntimeError> {
+        // Create ABI for the entry point
+        let func_meta = self.context.def_interner.function_meta(&func_id);
+        let abi = func_meta.into_abi(&self.context.def_interner);
+
+        let ast = monomorphise(func_id, &self.context.def_interner);
+
+        // Compile Program
+        let (circuit, debug) = create_circuit(
+            ast,
+            np_language,
+            show_ssa,
+            self.max_recursion_depth,
+            self.checked_arithmetic,
+        )?;
+
+        Ok(CompiledProgram { circuit, abi: Some(abi), debug })
+    }
+
+    /// Reports an error found while compiling a program
+    pub fn report_runtime_error(&self, err: &RuntimeError) {
+        // The FileId here will be the file id of the file with the main file
+        // Errors will be shown at the callsite without a stacktrace
+        Reporter::with_diagnostics(
+            err.location.file,
+            &self.context.file_manager,
+            &[err.to_diagnostic()],
+        );
+    }
+
+    /// Reports the constraint which the gate at `gate_index` of the program was generated from,
+    /// along with the message of that constraint if it has one.
+    /// Returns false if there is no known location for this gate.
+    pub fn report_unsatisfied_gate(
+        &self,
+        program: &CompiledProgram,
+        gate_index: usize,
+        message: Option<&str>,
+    ) -> bool {
+        let location = match program.debug.gate_location(gate_index) {
+            Some(location) => location,
+            None => return false,
         };
 
-        CompiledProgram { circuit, abi: Some(abi) }
+        let secondary = message.unwrap_or("this constraint is not satisfied");
+        let diagnostic = CustomDiagnostic::simple_error(
+            "could not satisfy all constraints".to_owned(),
+            secondary.to_owned(),
+            location.span,
+        );
+        Reporter::with_diagnostics(location.file, &self.context.file_manager, &[diagnostic]);
+        true
+    }
+
+    /// Returns a list of all functions in the current crate marked with #[test]
+    /// whose names contain the given pattern string. An empty pattern string
+    /// will return all functions marked with #[test].
+    pub fn get_all_test_functions_in_crate_matching(&self, pattern: &str) -> Vec<FuncId> {
+        let interner = &self.context.def_interner;
+        self.context
+            .def_map(LOCAL_CRATE)
+            .expect("The local crate should be analysed already")
+            .get_all_test_functions(interner)
+            .filter(|func_id| interner.function_name(func_id).contains(pattern))
+            .collect()
+    }
+
+    pub fn function_name(&self, id: FuncId) -> &str {
+        self.context.def_interner.function_name(&id)
+    }
+
+    /// Returns true if the function `id` takes no parameters and returns unit
+    pub fn function_has_empty_signature(&self, id: FuncId) -> bool {
+        let func_meta = self.context.def_interner.function_meta(&id);
+        func_meta.parameters.0.is_empty() && func_meta.return_type() == &Type::Unit
+    }
+
+    /// Reports a diagnostic pointing at the name of the function `id`
+    pub fn report_at_function(&self, id: FuncId, primary_message: String, secondary: String) {
+        let location = self.context.def_interner.function_meta(&id).location;
+        let diagnostic = CustomDiagnostic::simple_error(primary_message, secondary, location.span);
+        Reporter::with_diagnostics(location.file, &self.context.file_manager, &[diagnostic]);
     }
 
     #[cfg(not(feature = "std"))]
@@ -91,6 +91,9 @@ impl<'a> Interpreter<'a> {
                     "Bit shift operations are not currently implemented.".to_owned(),
                 ))
             }
+            BinaryOpKind::Modulo => Err(RuntimeErrorKind::Unimplemented(
+                "The remainder operation is not currently implemented.".to_owned(),
+            )),
         }
         .map_err(|kind| kind.add_location(op.location))
     }
@@ -206,6 +209,9 @@ impl<'a> Interpreter<'a> {
                     self.push_public_input(witness);
                     self.evaluator.add_witness_to_env(param_name, witness, env);
                 }
+                _ => unimplemented!(
+                    "currently we only support integer, field element and array parameters"
+                ),
             }
         }
 
@@ -220,6 +226,7 @@ impl<'a> Interpreter<'a> {
             HirPattern::Mutable(pattern, _) => self.pattern_name(pattern),
             HirPattern::Tuple(_, _) => todo!("Implement tuples in the backend"),
             HirPattern::Struct(_, _, _) => todo!("Implement structs in the backend"),
+            HirPattern::Variant(..) => todo!("Implement enums in the backend"),
         }
     }
 
@@ -493,12 +500,18 @@ impl<'a> Interpreter<'a> {
             }
             HirExpression::For(for_expr) => self.handle_for_expr(env,for_expr).map_err(|kind|kind.add_location(loc)),
             HirExpression::If(_) => todo!("If expressions are currently unimplemented"),
+            HirExpression::Match(_) => todo!("Match expressions are currently unimplemented"),
+            HirExpression::While(_) | HirExpression::Break | HirExpression::Continue => todo!("While loops are currently unimplemented"),
+            HirExpression::Return(_) => todo!("Return expressions are currently unimplemented"),
             HirExpression::Prefix(_) => todo!("Prefix expressions are currently unimplemented"),
             HirExpression::Literal(HirLiteral::Str(_)) => todo!("string literals are currently unimplemented"),
             HirExpression::Literal(HirLiteral::Bool(_)) => todo!("boolean literals are currently unimplemented"),
             HirExpression::Block(_) => todo!("currently block expressions not in for/if branches are not being evaluated. In the future, we should be able to unify the eval_block and all places which require block_expr here"),
             HirExpression::Constructor(_) => todo!("Constructor expressions are unimplemented in the noir backend"),
+            HirExpression::Variant(_) => todo!("Enum variant expressions are unimplemented in the noir backend"),
             HirExpression::Tuple(_) => todo!("Tuple expressions are unimplemented in the noir backend"),
+            HirExpression::Lambda(_) | HirExpression::FunctionRef(_) | HirExpression::CallValue(_) => todo!("Function values are unimplemented in the noir backend"),
+            HirExpression::NumericGeneric(_) => todo!("Numeric generics are unimplemented in the noir backend"),
             HirExpression::MemberAccess(_) => todo!("Member access expressions are unimplemented in the noir backend"),
             HirExpression::MethodCall(expr) => unreachable!("Method call expressions should have been desugared into call expressions before reaching the backend: {:#?}", expr),
             HirExpression::Error => unreachable!("Tried to evaluate an Expression::Error node"),
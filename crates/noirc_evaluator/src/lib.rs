@@ -16,12 +16,19 @@ use acvm::acir::native_types::{Expression, Linear, Witness};
 use acvm::FieldElement;
 use acvm::Language;
 use environment::{Environment, FuncContext};
-use errors::{RuntimeError, RuntimeErrorKind};
-use noirc_abi::{AbiFEType, AbiType};
+pub use errors::RuntimeError;
+use errors::RuntimeErrorKind;
+use noirc_abi::{AbiType, Sign};
+use noirc_errors::Location;
 use noirc_frontend::monomorphisation::ast::*;
 
 use object::{Array, Integer, Object};
-use ssa::{code_gen::IRGenerator, node};
+use ssa::code_gen::{IRGenerator, Value};
+use ssa::long_integer::abi_long_integer_bits;
+use std::collections::BTreeMap;
+
+/// The default limit on the number of nested calls to recursive functions
+pub const DEFAULT_MAX_RECURSION_DEPTH: u32 = 256;
 
 pub struct Evaluator {
     // Why is this not u64?
@@ -32,6 +39,32 @@ pub struct Evaluator {
     current_witness_index: u32,
     public_inputs: Vec<Witness>,
     gates: Vec<Gate>,
+    // Maps the index of a gate to the location of the constraint it was generated from
+    gate_locations: BTreeMap<usize, Location>,
+    // Maps the index of a gate to the message of the constraint it was generated from, if any
+    assert_messages: BTreeMap<usize, String>,
+    // Maps each parameter of main to its witnesses
+    param_witnesses: BTreeMap<String, Vec<Witness>>,
+}
+
+/// Debug information which is produced alongside the optimised circuit
+#[derive(Debug, Default, Clone)]
+pub struct DebugInfo {
+    /// Maps the index of a gate in the optimised circuit to the location
+    /// of the constraint in the source code which generated it
+    pub gate_locations: BTreeMap<usize, Location>,
+    /// Maps the index of a gate in the optimised circuit to the message
+    /// of the `constrain` statement which generated it, if it has one
+    pub assert_messages: BTreeMap<usize, String>,
+    /// The witnesses of each parameter of main, in the order in which
+    /// the parameter is flattened by the ABI
+    pub param_witnesses: BTreeMap<String, Vec<Witness>>,
+}
+
+impl DebugInfo {
+    pub fn gate_location(&self, gate_index: usize) -> Option<Location> {
+        self.gate_locations.get(&gate_index).copied()
+    }
 }
 
 /// Compiles the Program into ACIR and applies optimisations to the arithmetic gates
@@ -43,18 +76,26 @@ pub fn create_circuit(
     program: Program,
     np_language: Language,
     enable_logging: bool,
-) -> Result<Circuit, RuntimeError> {
+    max_recursion_depth: u32,
+    checked_arithmetic: bool,
+) -> Result<(Circuit, DebugInfo), RuntimeError> {
     let mut evaluator = Evaluator::new();
 
     // create a new environment for the main context
     let mut env = Environment::new(FuncContext::Main);
 
     // First evaluate the main function
-    evaluator.evaluate_main_alt(&mut env, program, enable_logging)?;
+    evaluator.evaluate_main_alt(
+        &mut env,
+        program,
+        enable_logging,
+        max_recursion_depth,
+        checked_arithmetic,
+    )?;
 
     let witness_index = evaluator.current_witness_index();
 
-    let optimised_circuit = acvm::compiler::compile(
+    let (optimised_circuit, gate_map) = acvm::compiler::compile_with_gate_map(
         Circuit {
             current_witness_index: witness_index,
             gates: evaluator.gates,
@@ -63,7 +104,20 @@ pub fn create_circuit(
         np_language,
     );
 
-    Ok(optimised_circuit)
+    // The optimiser may split a gate into several gates, so we map the locations
+    // onto the indices of the optimised gates
+    let mut debug_info =
+        DebugInfo { param_witnesses: evaluator.param_witnesses, ..DebugInfo::default() };
+    for (index, original_index) in gate_map.into_iter().enumerate() {
+        if let Some(location) = evaluator.gate_locations.get(&original_index) {
+            debug_info.gate_locations.insert(index, *location);
+        }
+        if let Some(message) = evaluator.assert_messages.get(&original_index) {
+            debug_info.assert_messages.insert(index, message.clone());
+        }
+    }
+
+    Ok((optimised_circuit, debug_info))
 }
 
 impl Evaluator {
@@ -80,6 +134,9 @@ impl Evaluator {
             //
             current_witness_index: 0,
             gates: Vec::new(),
+            gate_locations: BTreeMap::new(),
+            assert_messages: BTreeMap::new(),
+            param_witnesses: BTreeMap::new(),
         }
     }
 
@@ -101,6 +158,20 @@ impl Evaluator {
         value
     }
 
+    /// Marks the gates from `first_gate` onwards as generated from the constraint at `location`
+    fn set_gate_locations(&mut self, first_gate: usize, location: Location) {
+        for index in first_gate..self.gates.len() {
+            self.gate_locations.insert(index, location);
+        }
+    }
+
+    /// Marks the gates from `first_gate` onwards as generated from a constraint with the given message
+    fn set_assert_message(&mut self, first_gate: usize, message: &str) {
+        for index in first_gate..self.gates.len() {
+            self.assert_messages.insert(index, message.to_owned());
+        }
+    }
+
     pub fn current_witness_index(&self) -> u32 {
         self.current_witness_index
     }
@@ -111,8 +182,10 @@ impl Evaluator {
         env: &mut Environment,
         program: Program,
         enable_logging: bool,
+        max_recursion_depth: u32,
+        checked_arithmetic: bool,
     ) -> Result<(), RuntimeError> {
-        let mut igen = IRGenerator::new(program);
+        let mut igen = IRGenerator::new(program, max_recursion_depth, checked_arithmetic);
         self.parse_abi_alt(&mut igen);
 
         // Now call the main function
@@ -147,49 +220,93 @@ impl Evaluator {
         param_type: &AbiType,
         igen: &mut IRGenerator,
     ) -> Result<(), RuntimeErrorKind> {
-        match param_type {
-            AbiType::Field(visibility) => {
-                let witness = self.add_witness_to_cs();
-                if *visibility == AbiFEType::Public {
-                    self.public_inputs.push(witness);
-                }
-                igen.abi_var(name, def, node::ObjectType::NativeField, witness);
+        let mut witnesses = Vec::new();
+        self.abi_value(name, Some(def), param_type, &mut witnesses, igen)?;
+        self.param_witnesses.insert(name.to_owned(), witnesses);
+        Ok(())
+    }
+
+    /// Adds the witnesses of a parameter of main, in the order described by `AbiType`,
+    /// and returns the SSA value which holds them.
+    fn abi_value(
+        &mut self,
+        name: &str,
+        def: Option<DefinitionId>,
+        param_type: &AbiType,
+        witnesses: &mut Vec<Witness>,
+        igen: &mut IRGenerator,
+    ) -> Result<Value, RuntimeErrorKind> {
+        let value = match param_type {
+            AbiType::Field(_) | AbiType::Integer { .. } | AbiType::Boolean(_) => {
+                let witness = self.add_abi_witness(param_type)?;
+                witnesses.push(witness);
+                igen.abi_var(name, def, param_type, witness).map_err(RuntimeError::remove_span)?
             }
-            AbiType::Array { visibility, length, typ } => {
-                let mut witnesses = Vec::new();
-                let mut element_width = None;
-                if let AbiType::Integer { width, .. } = typ.as_ref() {
-                    element_width = Some(*width);
+            AbiType::Array { length, typ, .. }
+                if typ.is_scalar() && abi_long_integer_bits(typ).is_none() =>
+            {
+                let mut elements = Vec::new();
+                for _ in 0..*length {
+                    elements.push(self.add_abi_witness(typ)?);
                 }
+                witnesses.extend(&elements);
+                igen.abi_array(name, def, typ, *length, elements)
+            }
+            AbiType::String { visibility, length } => {
+                // Strings are laid out as an array of their bytes
+                let typ =
+                    AbiType::Integer { visibility: *visibility, sign: Sign::Unsigned, width: 8 };
+                let mut elements = Vec::new();
                 for _ in 0..*length {
-                    let witness = self.add_witness_to_cs();
-                    witnesses.push(witness);
-                    if let Some(ww) = element_width {
-                        ssa::acir_gen::range_constraint(witness, ww, self)?;
-                    }
-                    if *visibility == AbiFEType::Public {
-                        self.public_inputs.push(witness);
-                    }
+                    elements.push(self.add_abi_witness(&typ)?);
                 }
-                igen.abi_array(name, def, typ.as_ref(), *length, witnesses);
+                witnesses.extend(&elements);
+                igen.abi_array(name, def, &typ, *length, elements)
             }
-            AbiType::Integer { visibility, sign, width } => {
-                let witness = self.add_witness_to_cs();
-                ssa::acir_gen::range_constraint(witness, *width, self)?;
-                if *visibility == AbiFEType::Public {
-                    self.public_inputs.push(witness);
+            AbiType::Array { length, typ, .. } => {
+                let mut elements = Vec::new();
+                for i in 0..*length {
+                    let element_name = format!("{}[{}]", name, i);
+                    elements.push(self.abi_value(&element_name, None, typ, witnesses, igen)?);
                 }
-                match sign {
-                    noirc_abi::Sign::Unsigned => {
-                        igen.abi_var(name, def, node::ObjectType::Unsigned(*width), witness)
-                    }
-                    noirc_abi::Sign::Signed => {
-                        igen.abi_var(name, def, node::ObjectType::Signed(*width), witness)
-                    }
+                igen.abi_struct(def, elements)
+            }
+            AbiType::Struct { fields, .. } => {
+                let mut values = Vec::new();
+                for (field_name, typ) in fields {
+                    let field_name = format!("{}.{}", name, field_name);
+                    values.push(self.abi_value(&field_name, None, typ, witnesses, igen)?);
+                }
+                igen.abi_struct(def, values)
+            }
+            AbiType::Tuple { fields, .. } => {
+                let mut values = Vec::new();
+                for (i, typ) in fields.iter().enumerate() {
+                    let field_name = format!("{}.{}", name, i);
+                    values.push(self.abi_value(&field_name, None, typ, witnesses, igen)?);
                 }
+                igen.abi_struct(def, values)
+            }
+        };
+        Ok(value)
+    }
+
+    /// Adds a witness for a scalar parameter of main, constraining it to the range of its type
+    fn add_abi_witness(&mut self, typ: &AbiType) -> Result<Witness, RuntimeErrorKind> {
+        let witness = self.add_witness_to_cs();
+        match typ {
+            // Long integers are range checked by their decomposition into limbs
+            AbiType::Integer { .. } if abi_long_integer_bits(typ).is_some() => (),
+            AbiType::Integer { width, .. } => {
+                ssa::acir_gen::range_constraint(witness, *width, self)?
             }
+            AbiType::Boolean(_) => ssa::acir_gen::range_constraint(witness, 1, self)?,
+            _ => (),
         }
-        Ok(())
+        if typ.is_public() {
+            self.public_inputs.push(witness);
+        }
+        Ok(witness)
     }
 
     /// The ABI is the intermediate representation between Noir and types like Toml
@@ -201,8 +318,8 @@ impl Evaluator {
         // u8 and arrays are assumed to be private
         // This is not a short-coming of the ABI, but of the grammar
         // The new grammar has been conceived, and will be implemented.
-        let main = igen.program.main();
-        let main_params = std::mem::take(&mut main.parameters);
+        // The parameters of main are kept, as a recursive main needs them when it calls itself
+        let main_params = igen.program.main().parameters.clone();
         let abi_params = std::mem::take(&mut igen.program.abi.parameters);
         assert_eq!(main_params.len(), abi_params.len());
 
@@ -20,6 +20,9 @@ use acvm::acir::circuit::gate::{Directive, GadgetCall, GadgetInput};
 use acvm::acir::native_types::{Expression, Linear, Witness};
 use num_bigint::BigUint;
 
+//Bit size of the low limb when decomposing a field element into two limbs
+const FIELD_LIMB_BITS: u32 = 128;
+
 #[derive(Default)]
 pub struct Acir {
     pub arith_cache: HashMap<NodeId, InternalVar>,
@@ -126,10 +129,15 @@ impl Acir {
 
         let mut output = match &ins.operation {
             Operation::Binary(binary) => self.evaluate_binary(binary, ins.res_type, evaluator, ctx),
-            Operation::Constrain(value, ..) => {
+            Operation::Constrain(value, location, message) => {
+                let first_gate = evaluator.gates.len();
                 let value = self.substitute(*value, evaluator, ctx);
                 let subtract = subtract(&Expression::one(), FieldElement::one(), &value.expression);
                 evaluator.gates.push(Gate::Arithmetic(subtract));
+                evaluator.set_gate_locations(first_gate, *location);
+                if let Some(message) = message {
+                    evaluator.set_assert_message(first_gate, message);
+                }
                 value
             }
             Operation::Not(value) => {
@@ -163,7 +171,9 @@ impl Acir {
                 InternalVar::from(v)
             }
             Operation::Call { .. } => unreachable!("call instruction should have been inlined"),
-            Operation::Return(_) => todo!(), //return from main
+            Operation::Return(_) => {
+                unreachable!("main does not return, and other functions have been inlined")
+            }
             Operation::Cond { condition, val_true: lhs, val_false: rhs } => {
                 let cond = self.substitute(*condition, evaluator, ctx);
                 let l_c = self.substitute(*lhs, evaluator, ctx);
@@ -197,7 +207,8 @@ impl Acir {
                         }
                     }
                 } else {
-                    unimplemented!("dynamic arrays are not implemented yet");
+                    let mem_array = &ctx.mem[*array_id];
+                    self.evaluate_dynamic_load(mem_array, &index, evaluator)
                 }
             }
 
@@ -213,7 +224,9 @@ impl Acir {
                     //we do not generate constraint, so no output.
                     InternalVar::default()
                 } else {
-                    todo!("dynamic arrays are not implemented yet");
+                    let mem_array = &ctx.mem[*array_id];
+                    self.evaluate_dynamic_store(mem_array, &index, &value, evaluator);
+                    InternalVar::default()
                 }
             }
         };
@@ -232,10 +245,13 @@ impl Acir {
         let r_c = self.substitute(binary.rhs, evaluator, ctx);
 
         match &binary.operator {
-            BinaryOp::Add | BinaryOp::SafeAdd => {
+            BinaryOp::Add => {
                 InternalVar::from(add(&l_c.expression, FieldElement::one(), &r_c.expression))
             }
-            BinaryOp::Sub { max_rhs_value } | BinaryOp::SafeSub { max_rhs_value } => {
+            BinaryOp::SafeAdd | BinaryOp::SafeSub { .. } | BinaryOp::SafeMul => InternalVar::from(
+                evaluate_checked(&binary.operator, &l_c, &r_c, res_type, evaluator),
+            ),
+            BinaryOp::Sub { max_rhs_value } => {
                 if res_type == node::ObjectType::NativeField {
                     InternalVar::from(subtract(
                         &l_c.expression,
@@ -270,19 +286,21 @@ impl Acir {
                     sub_var
                 }
             }
-            BinaryOp::Mul | BinaryOp::SafeMul => {
-                InternalVar::from(evaluate_mul(&l_c, &r_c, evaluator))
-            }
+            BinaryOp::Mul => InternalVar::from(evaluate_mul(&l_c, &r_c, evaluator)),
             BinaryOp::Udiv => {
-                let (q_wit, _) = evaluate_udiv(&l_c, &r_c, evaluator);
+                let (q_wit, _) = evaluate_udiv(&l_c, &r_c, res_type.bits(), evaluator);
                 InternalVar::from(q_wit)
             }
-            BinaryOp::Sdiv => InternalVar::from(evaluate_sdiv(&l_c, &r_c, evaluator).0),
+            BinaryOp::Sdiv => {
+                InternalVar::from(evaluate_sdiv(&l_c, &r_c, res_type.bits(), evaluator).0)
+            }
             BinaryOp::Urem => {
-                let (_, r_wit) = evaluate_udiv(&l_c, &r_c, evaluator);
+                let (_, r_wit) = evaluate_udiv(&l_c, &r_c, res_type.bits(), evaluator);
                 InternalVar::from(r_wit)
             }
-            BinaryOp::Srem => InternalVar::from(evaluate_sdiv(&l_c, &r_c, evaluator).1),
+            BinaryOp::Srem => {
+                InternalVar::from(evaluate_sdiv(&l_c, &r_c, res_type.bits(), evaluator).1)
+            }
             BinaryOp::Div => InternalVar::from(mul(
                 &l_c.expression,
                 &from_witness(evaluate_inverse(r_c, evaluator)),
@@ -321,16 +339,33 @@ impl Acir {
                 }
                 .into()
             }
-            BinaryOp::Lt => unimplemented!(
-                "Field comparison is not implemented yet, try to cast arguments to integer type"
-            ),
-            BinaryOp::Lte => unimplemented!(
-                "Field comparison is not implemented yet, try to cast arguments to integer type"
-            ),
+            BinaryOp::Lt => evaluate_field_cmp(&l_c, &r_c, evaluator).into(),
+            BinaryOp::Lte => {
+                let gt = evaluate_field_cmp(&r_c, &l_c, evaluator);
+                subtract(&Expression::one(), FieldElement::one(), &gt).into()
+            }
             BinaryOp::And => InternalVar::from(evaluate_and(l_c, r_c, res_type.bits(), evaluator)),
             BinaryOp::Or => InternalVar::from(evaluate_or(l_c, r_c, res_type.bits(), evaluator)),
             BinaryOp::Xor => InternalVar::from(evaluate_xor(l_c, r_c, res_type.bits(), evaluator)),
-            BinaryOp::Shl | BinaryOp::Shr => unreachable!(),
+            BinaryOp::Shl => {
+                let bit_size = res_type.bits();
+                let shift_size = ctx[binary.rhs].size_in_bits();
+                let pow = evaluate_pow2(&r_c, bit_size, shift_size, evaluator);
+                InternalVar::from(evaluate_mul(&l_c, &pow, evaluator))
+            }
+            BinaryOp::Shr => {
+                let bit_size = res_type.bits();
+                let shift_size = ctx[binary.rhs].size_in_bits();
+                if let ObjectType::Signed(_) = res_type {
+                    //shifting a signed integer by bit_size-1 or more results in its sign bit
+                    let pow = evaluate_pow2(&r_c, bit_size - 1, shift_size, evaluator);
+                    InternalVar::from(evaluate_arithmetic_shr(&l_c, &pow, bit_size, evaluator))
+                } else {
+                    let pow = evaluate_pow2(&r_c, bit_size, shift_size, evaluator);
+                    let (q_wit, _) = evaluate_udiv(&l_c, &pow, bit_size, evaluator);
+                    InternalVar::from(q_wit)
+                }
+            }
             i @ BinaryOp::Assign => unreachable!("Invalid Instruction: {:?}", i),
         }
     }
@@ -366,6 +401,43 @@ impl Acir {
             .collect()
     }
 
+    //Load the element array[index] when the index is not known at compile time
+    //The result is \sum_i s_i*array[i], where s_i are the selectors of the index
+    fn evaluate_dynamic_load(
+        &mut self,
+        array: &MemArray,
+        index: &InternalVar,
+        evaluator: &mut Evaluator,
+    ) -> InternalVar {
+        let values = self.load_array(array, false, evaluator);
+        let selectors = index_selectors(index, array.len, evaluator);
+        let mut result = Expression::default();
+        for (selector, value) in selectors.iter().zip(values) {
+            let selected = mul_with_witness(evaluator, selector, &value.expression);
+            result = add(&result, FieldElement::one(), &selected);
+        }
+        InternalVar::from(result)
+    }
+
+    //Store value into array[index] when the index is not known at compile time
+    //Every element of the array is updated to array[i] + s_i*(value - array[i]), where s_i are the selectors of the index
+    fn evaluate_dynamic_store(
+        &mut self,
+        array: &MemArray,
+        index: &InternalVar,
+        value: &InternalVar,
+        evaluator: &mut Evaluator,
+    ) {
+        let values = self.load_array(array, false, evaluator);
+        let selectors = index_selectors(index, array.len, evaluator);
+        for (i, (selector, old_value)) in selectors.iter().zip(values).enumerate() {
+            let diff = subtract(&value.expression, FieldElement::one(), &old_value.expression);
+            let update = mul_with_witness(evaluator, selector, &diff);
+            let new_value = add(&old_value.expression, FieldElement::one(), &update);
+            self.memory_map.insert(array.absolute_adr(i as u32), InternalVar::from(new_value));
+        }
+    }
+
     //Map the outputs into the array
     fn map_array(&mut self, a: ArrayId, outputs: &[Witness], ctx: &SsaContext) {
         let adr = ctx.mem[a].adr;
@@ -589,12 +661,209 @@ impl Acir {
     }
 }
 
+//Generates the selectors of a witness index into an array of size len
+//s_i is 1 if index == i and 0 otherwise. We constrain the sum of the selectors to be 1,
+//which ensures that the index is within the bounds of the array
+fn index_selectors(index: &InternalVar, len: u32, evaluator: &mut Evaluator) -> Vec<Expression> {
+    let mut selectors = Vec::with_capacity(len as usize);
+    let mut sum = Expression::default();
+    for i in 0..len {
+        let mut diff = index.expression.clone();
+        diff.q_c -= FieldElement::from(i as i128);
+        let mut diff_var = InternalVar::from(diff);
+        diff_var.witness = Some(generate_witness(&diff_var, evaluator));
+        let is_different = evaluate_zero_equality(&diff_var, evaluator);
+        let selector =
+            subtract(&Expression::one(), FieldElement::one(), &from_witness(is_different));
+        sum = add(&sum, FieldElement::one(), &selector);
+        selectors.push(selector);
+    }
+    evaluator.gates.push(Gate::Arithmetic(subtract(&sum, FieldElement::one(), &Expression::one())));
+    selectors
+}
+
+//Checked addition, subtraction or multiplication of lhs and rhs, integers of the type res_type
+//The exact result is computed on the values of lhs and rhs and constrained to fit the type, so that it cannot overflow
+pub fn evaluate_checked(
+    operator: &BinaryOp,
+    lhs: &InternalVar,
+    rhs: &InternalVar,
+    res_type: ObjectType,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    let bit_size = res_type.bits();
+    let signed = matches!(res_type, ObjectType::Signed(_));
+    let (lhs, rhs) = if signed {
+        (signed_value(lhs, bit_size, evaluator), signed_value(rhs, bit_size, evaluator))
+    } else {
+        (lhs.clone(), rhs.clone())
+    };
+    let exact = match operator {
+        BinaryOp::SafeAdd => add(&lhs.expression, FieldElement::one(), &rhs.expression),
+        BinaryOp::SafeSub { .. } => subtract(&lhs.expression, FieldElement::one(), &rhs.expression),
+        BinaryOp::SafeMul => evaluate_mul(&lhs, &rhs, evaluator),
+        _ => unreachable!("{:?} is not a checked operation", operator),
+    };
+
+    //a constant result is also bound to a witness, so that the circuit cannot be satisfied when it overflows
+    let exact_witness = if is_const(&exact) {
+        evaluator.create_intermediate_variable(exact).1
+    } else {
+        generate_witness(&InternalVar::from(exact), evaluator)
+    };
+    if !signed {
+        range_constraint(exact_witness, bit_size, evaluator).unwrap_or_else(|err| {
+            dbg!(err);
+        });
+        return from_witness(exact_witness);
+    }
+
+    //v fits in bit_size bits iff v+2^(bit_size-1) is in [0, 2^bit_size), and then its two's complement
+    //is v+2^(bit_size-1) with its top bit flipped
+    let half = FieldElement::from(2_i128).pow(&FieldElement::from((bit_size - 1) as i128));
+    let mut biased = from_witness(exact_witness);
+    biased.q_c += half;
+    let bits = split(&InternalVar::from(biased.clone()), bit_size, evaluator);
+    let mut result = add(&biased, -(half + half), &from_witness(bits[(bit_size - 1) as usize]));
+    result.q_c += half;
+    result
+}
+
+//Returns the value of x, a two's complement integer of bit_size bits, which is x-2^bit_size when x is negative
+fn signed_value(x: &InternalVar, bit_size: u32, evaluator: &mut Evaluator) -> InternalVar {
+    let sign = sign_bit(x, bit_size, evaluator);
+    let modulus = FieldElement::from(2_i128).pow(&FieldElement::from(bit_size as i128));
+    InternalVar::from(add(&x.expression, -modulus, &sign))
+}
+
+//Signed division of lhs by rhs, two's complement integers of bit_size bits.
+//Returns the quotient, rounded towards zero, and the remainder, which has the sign of lhs.
+//They are computed from the euclidean division of the absolute values of lhs and rhs.
+//n.b. the results may be 2^bit_size instead of 0 and must be truncated
 pub fn evaluate_sdiv(
-    _lhs: &InternalVar,
-    _rhs: &InternalVar,
-    _evaluator: &mut Evaluator,
+    lhs: &InternalVar,
+    rhs: &InternalVar,
+    bit_size: u32,
+    evaluator: &mut Evaluator,
 ) -> (Expression, Expression) {
-    todo!();
+    let l_sign = sign_bit(lhs, bit_size, evaluator);
+    let r_sign = sign_bit(rhs, bit_size, evaluator);
+    let l_abs = conditional_negate(&lhs.expression, &l_sign, bit_size, evaluator);
+    let r_abs = conditional_negate(&rhs.expression, &r_sign, bit_size, evaluator);
+    let l_abs = into_linear(InternalVar::from(l_abs), evaluator);
+    let r_abs = into_linear(InternalVar::from(r_abs), evaluator);
+    let (q_witness, r_witness) = evaluate_udiv(&l_abs, &r_abs, bit_size, evaluator);
+
+    //the quotient is negative when exactly one of lhs and rhs is negative
+    let signs_product = evaluate_mul(
+        &InternalVar::from(l_sign.clone()),
+        &InternalVar::from(r_sign.clone()),
+        evaluator,
+    );
+    let mut q_sign = add(&l_sign, FieldElement::one(), &r_sign);
+    q_sign = add(&q_sign, -FieldElement::from(2_i128), &signs_product);
+
+    let quotient = conditional_negate(&from_witness(q_witness), &q_sign, bit_size, evaluator);
+    let remainder = conditional_negate(&from_witness(r_witness), &l_sign, bit_size, evaluator);
+    (quotient, remainder)
+}
+
+//Arithmetic right shift of lhs, a two's complement integer of bit_size bits, by shift < bit_size, given pow_shift = 2^shift
+//The logical shift is the quotient of lhs by 2^shift, whose vacated high bits are then set to the sign bit of lhs
+pub fn evaluate_arithmetic_shr(
+    lhs: &InternalVar,
+    pow_shift: &InternalVar,
+    bit_size: u32,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    let two = FieldElement::from(2_i128);
+    let half = two.pow(&FieldElement::from((bit_size - 1) as i128));
+    let (q_witness, _) = evaluate_udiv(lhs, pow_shift, bit_size, evaluator);
+    let sign = sign_bit(lhs, bit_size, evaluator);
+    //the high bits are 2^bit_size - 2^(bit_size-shift), where 2^(bit_size-shift) = 2*(2^(bit_size-1)/2^shift)
+    let shifted_half = match pow_shift.to_const() {
+        Some(pow) => Expression::from_field(half / pow),
+        None => {
+            let half = InternalVar::from(half);
+            from_witness(evaluate_udiv(&half, pow_shift, bit_size, evaluator).0)
+        }
+    };
+    let mut high_bits = &shifted_half * &-two;
+    high_bits.q_c += half + half;
+    let fill = evaluate_mul(&InternalVar::from(sign), &InternalVar::from(high_bits), evaluator);
+    add(&from_witness(q_witness), FieldElement::one(), &fill)
+}
+
+//Returns 2^min(shift, limit), where shift is an unsigned integer of bit_size bits and limit <= bit_size
+//When shift is not constant, it is decomposed into bits and 2^shift is the product of 2^(2^i) for each bit i which is set
+fn evaluate_pow2(
+    shift: &InternalVar,
+    limit: u32,
+    bit_size: u32,
+    evaluator: &mut Evaluator,
+) -> InternalVar {
+    let two = FieldElement::from(2_i128);
+    if let Some(shift) = shift.to_const() {
+        let shift = shift.to_u128().min(limit as u128);
+        return InternalVar::from(two.pow(&FieldElement::from(shift as i128)));
+    }
+
+    //clamped = limit + (shift < limit)*(shift - limit)
+    let limit_var = InternalVar::from(FieldElement::from(limit as i128));
+    let is_lower = evaluate_cmp(shift, &limit_var, bit_size, false, evaluator);
+    let diff = subtract(&shift.expression, FieldElement::one(), &limit_var.expression);
+    let mut clamped =
+        evaluate_mul(&InternalVar::from(is_lower), &InternalVar::from(diff), evaluator);
+    clamped.q_c += limit_var.expression.q_c;
+
+    let num_bits = 32 - limit.leading_zeros();
+    let bits = split(&InternalVar::from(clamped), num_bits, evaluator);
+    let mut pow = InternalVar::from(FieldElement::one());
+    for (i, bit) in bits.into_iter().enumerate() {
+        //1 + bit*(2^(2^i) - 1)
+        let factor_minus_one = two.pow(&FieldElement::from(1_i128 << i)) - FieldElement::one();
+        let factor = add(&Expression::one(), factor_minus_one, &from_witness(bit));
+        pow = InternalVar::from(evaluate_mul(&pow, &InternalVar::from(factor), evaluator));
+    }
+    into_linear(pow, evaluator)
+}
+
+//Returns the sign bit of x, a two's complement integer of bit_size bits
+fn sign_bit(x: &InternalVar, bit_size: u32, evaluator: &mut Evaluator) -> Expression {
+    if let Some(x_const) = x.to_const() {
+        let x_big = BigUint::from_bytes_be(&x_const.to_bytes());
+        return if x_big >> (bit_size - 1) == BigUint::one() {
+            Expression::one()
+        } else {
+            Expression::default()
+        };
+    }
+    let bits = split(x, bit_size, evaluator);
+    from_witness(bits[(bit_size - 1) as usize])
+}
+
+//Returns x if condition is 0, or its opposite 2^bit_size-x if condition is 1,
+//x being a two's complement integer of bit_size bits and condition a boolean
+fn conditional_negate(
+    x: &Expression,
+    condition: &Expression,
+    bit_size: u32,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    //x + condition*(2^bit_size - 2x)
+    let mut diff = x * &-FieldElement::from(2_i128);
+    diff.q_c += FieldElement::from(2_i128).pow(&FieldElement::from(bit_size as i128));
+    let negation = evaluate_mul(&InternalVar::from(condition.clone()), &diff.into(), evaluator);
+    add(x, FieldElement::one(), &negation)
+}
+
+//Returns a variable equal to var which can be used in a product, by creating a witness for it if it is not linear
+fn into_linear(var: InternalVar, evaluator: &mut Evaluator) -> InternalVar {
+    if var.expression.is_linear() {
+        var
+    } else {
+        InternalVar::from(generate_witness(&var, evaluator))
+    }
 }
 
 //Returns 1 if lhs < rhs
@@ -617,6 +886,90 @@ pub fn evaluate_cmp(
     }
 }
 
+//Returns 1 if lhs < rhs, where lhs and rhs are compared as integers in [0, p)
+//Both arguments are decomposed into two limbs which are compared lexicographically
+pub fn evaluate_field_cmp(
+    lhs: &InternalVar,
+    rhs: &InternalVar,
+    evaluator: &mut Evaluator,
+) -> Expression {
+    let (lhs_lo, lhs_hi) = split_field(lhs, evaluator);
+    let (rhs_lo, rhs_hi) = split_field(rhs, evaluator);
+    limbs_cmp((&lhs_lo, &lhs_hi), (&rhs_lo, &rhs_hi), evaluator)
+}
+
+//Returns 1 if (a_hi, a_lo) < (b_hi, b_lo) in lexicographic order, i.e a_hi < b_hi or (a_hi == b_hi and a_lo < b_lo)
+//Limbs are assumed to be range constrained to FIELD_LIMB_BITS for the low limb and max_num_bits-FIELD_LIMB_BITS for the high limb
+fn limbs_cmp(
+    a: (&InternalVar, &InternalVar),
+    b: (&InternalVar, &InternalVar),
+    evaluator: &mut Evaluator,
+) -> Expression {
+    let hi_bits = FieldElement::max_num_bits() - FIELD_LIMB_BITS;
+    let hi_lt = evaluate_cmp(a.1, b.1, hi_bits, false, evaluator);
+    let mut hi_diff =
+        InternalVar::from(subtract(&a.1.expression, FieldElement::one(), &b.1.expression));
+    hi_diff.witness = Some(generate_witness(&hi_diff, evaluator));
+    let hi_ne = evaluate_zero_equality(&hi_diff, evaluator);
+    let lo_lt = evaluate_cmp(a.0, b.0, FIELD_LIMB_BITS, false, evaluator);
+    let hi_eq = subtract(&Expression::one(), FieldElement::one(), &from_witness(hi_ne));
+    //hi_lt and hi_eq cannot be both 1, so we can add them
+    add(&from_witness(hi_lt), FieldElement::one(), &mul(&hi_eq, &from_witness(lo_lt)))
+}
+
+//Decomposes x into two limbs (lo, hi) such that x = lo + 2^FIELD_LIMB_BITS*hi
+//We also constrain the limbs to represent an integer less than the field modulus, so that the decomposition is unique
+fn split_field(x: &InternalVar, evaluator: &mut Evaluator) -> (InternalVar, InternalVar) {
+    if let Some(x_const) = x.to_const() {
+        let (lo, hi) = field_limbs(x_const);
+        return (lo.into(), hi.into());
+    }
+    let hi_bits = FieldElement::max_num_bits() - FIELD_LIMB_BITS;
+    let x_witness = generate_witness(x, evaluator);
+    let lo_witness = evaluator.add_witness_to_cs();
+    let hi_witness = evaluator.add_witness_to_cs();
+    evaluator.gates.push(Gate::Directive(Directive::Truncate {
+        a: x_witness,
+        b: lo_witness,
+        c: hi_witness,
+        bit_size: FIELD_LIMB_BITS,
+    }));
+    range_constraint(lo_witness, FIELD_LIMB_BITS, evaluator).unwrap_or_else(|err| {
+        dbg!(err);
+    });
+    range_constraint(hi_witness, hi_bits, evaluator).unwrap_or_else(|err| {
+        dbg!(err);
+    });
+    //x = lo + 2^FIELD_LIMB_BITS*hi
+    let two_pow = FieldElement::from(2_i128).pow(&FieldElement::from(FIELD_LIMB_BITS as i128));
+    let limbs = add(&from_witness(lo_witness), two_pow, &from_witness(hi_witness));
+    evaluator.gates.push(Gate::Arithmetic(subtract(
+        &limbs,
+        FieldElement::one(),
+        &from_witness(x_witness),
+    )));
+
+    //(hi, lo) < (p_hi, p_lo), where p = p_lo + 2^FIELD_LIMB_BITS*p_hi, i.e (hi, lo) <= (p-1)
+    let lo = InternalVar::from(lo_witness);
+    let hi = InternalVar::from(hi_witness);
+    let (max_lo, max_hi) = field_limbs(-FieldElement::one());
+    let (max_lo, max_hi) = (InternalVar::from(max_lo), InternalVar::from(max_hi));
+    let gt_max = limbs_cmp((&max_lo, &max_hi), (&lo, &hi), evaluator);
+    evaluator.gates.push(Gate::Arithmetic(gt_max));
+    (lo, hi)
+}
+
+//Returns the limbs (lo, hi) of a constant field element, such that x = lo + 2^FIELD_LIMB_BITS*hi
+fn field_limbs(x: FieldElement) -> (FieldElement, FieldElement) {
+    let x_big = BigUint::from_bytes_be(&x.to_bytes());
+    let lo = &x_big % (BigUint::one() << FIELD_LIMB_BITS);
+    let hi = x_big >> FIELD_LIMB_BITS;
+    (
+        FieldElement::from_be_bytes_reduce(&lo.to_bytes_be()),
+        FieldElement::from_be_bytes_reduce(&hi.to_bytes_be()),
+    )
+}
+
 //Performs bit decomposition
 pub fn split(lhs: &InternalVar, bit_size: u32, evaluator: &mut Evaluator) -> Vec<Witness> {
     assert!(bit_size < FieldElement::max_num_bits());
@@ -898,6 +1251,7 @@ pub fn evaluate_mul(lhs: &InternalVar, rhs: &InternalVar, evaluator: &mut Evalua
 pub fn evaluate_udiv(
     lhs: &InternalVar,
     rhs: &InternalVar,
+    bit_size: u32,
     evaluator: &mut Evaluator,
 ) -> (Witness, Witness) {
     let q_witness = evaluator.add_witness_to_cs();
@@ -912,12 +1266,11 @@ pub fn evaluate_udiv(
     //r<b
     let r_expr = Expression::from(Linear::from_witness(r_witness));
     let r_var = InternalVar { expression: r_expr, witness: Some(r_witness), id: None };
-    bound_check(&r_var, rhs, true, 32, evaluator); //TODO bit size! should be max(a.bit, b.bit)
-                                                   //range check q<=a
-    range_constraint(q_witness, 32, evaluator).unwrap_or_else(|err| {
+    bound_check(&r_var, rhs, true, bit_size, evaluator);
+    //range check q<=a
+    range_constraint(q_witness, bit_size, evaluator).unwrap_or_else(|err| {
         dbg!(err);
     });
-    //todo bit size should be a.bits
     // a-b*q-r = 0
     let mut d = mul(&rhs.expression, &Expression::from(&q_witness));
     d = add(&d, FieldElement::one(), &Expression::from(&r_witness));
@@ -1,13 +1,14 @@
 use super::context::SsaContext;
 use super::function::FuncIndex;
+use super::long_integer::{self, long_integer_bits};
 use super::mem::ArrayId;
 use super::node::{Binary, BinaryOp, NodeId, ObjectType, Operation, Variable};
-use super::{block, node, ssa_form};
-use std::collections::HashMap;
+use super::{block, integer, node, ssa_form};
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 
 use super::super::environment::Environment;
-use super::super::errors::RuntimeError;
+use super::super::errors::{RuntimeError, RuntimeErrorKind};
 
 use crate::ssa::block::BlockType;
 use crate::ssa::function;
@@ -27,9 +28,48 @@ pub struct IRGenerator {
     /// into multiple variables/values
     variable_values: HashMap<DefinitionId, Value>,
 
+    /// Functions which are part of, or can call into, a cycle of the call graph.
+    /// Calls to these functions are expanded in place instead of being inlined afterwards.
+    recursive_functions: HashSet<FuncId>,
+    /// The number of nested calls to recursive functions which are currently being expanded
+    recursion_depth: u32,
+    max_recursion_depth: u32,
+
+    /// Whether integer arithmetic is checked in the functions which do not select it
+    checked_arithmetic: bool,
+    /// Whether integer arithmetic is checked in the function being generated
+    checked: bool,
+
+    /// The flags of the loops enclosing the code being generated, the innermost loop being last
+    loops: Vec<LoopFlags>,
+    /// The flags of the function being generated, when it returns early
+    returns: Option<ReturnFlags>,
+
     pub program: Program,
 }
 
+/// Boolean variables recording whether a loop has been exited by a break,
+/// and whether its current iteration has been exited by a continue
+#[derive(Debug, Clone, Copy)]
+struct LoopFlags {
+    broken: NodeId,
+    continued: NodeId,
+}
+
+/// A boolean variable recording whether a function has returned,
+/// and the variables holding the value which it returns
+#[derive(Debug, Clone)]
+struct ReturnFlags {
+    returned: NodeId,
+    value: Value,
+}
+
+/// A step from a variable to the part of it which an lvalue refers to
+enum LValueStep {
+    Field(usize),
+    Index(NodeId, noirc_errors::Location),
+}
+
 #[derive(Debug, Clone)]
 pub enum Value {
     Single(NodeId),
@@ -99,18 +139,53 @@ impl Value {
 }
 
 impl IRGenerator {
-    pub fn new(program: Program) -> IRGenerator {
+    pub fn new(
+        program: Program,
+        max_recursion_depth: u32,
+        checked_arithmetic: bool,
+    ) -> IRGenerator {
         IRGenerator {
             context: SsaContext::new(),
             variable_values: HashMap::new(),
             function_context: None,
+            recursive_functions: function::recursive_functions(&program),
+            recursion_depth: 0,
+            max_recursion_depth,
+            checked_arithmetic,
+            checked: checked_arithmetic,
+            loops: Vec::new(),
+            returns: None,
             program,
         }
     }
 
     pub fn codegen_main(&mut self, env: &mut Environment) -> Result<(), RuntimeError> {
-        let main_body = self.program.take_main_body();
-        self.codegen_expression(env, &main_body)?;
+        let checked_arithmetic = self.program.main().checked_arithmetic;
+        // The body of a recursive main is needed again when main calls itself
+        let main_body = if self.recursive_functions.contains(&FuncId(0)) {
+            self.program[FuncId(0)].body.clone()
+        } else {
+            self.program.take_main_body()
+        };
+        match &main_body {
+            // When main returns a value, its body is constrained to be equal to the `return`
+            // parameter. The value is only known at the end of a body which returns early.
+            Expression::Constrain(constraint, location, _) if return_type(&main_body).is_some() => {
+                let constraint = match constraint.as_ref() {
+                    Expression::Binary(constraint) => constraint,
+                    _ => unreachable!("the return value of main is constrained by an equality"),
+                };
+                let expected = self.codegen_expression(env, &constraint.lhs)?;
+                let value = self.codegen_function_body(env, &constraint.rhs, checked_arithmetic)?;
+                let (operator, eq_location) = (constraint.operator, constraint.location);
+                let cond = self.codegen_binary(expected, value, operator, eq_location)?.unwrap_id();
+                let operation = Operation::Constrain(cond, *location, None);
+                self.context.new_instruction(operation, ObjectType::NotAnObject)?;
+            }
+            _ => {
+                self.codegen_function_body(env, &main_body, checked_arithmetic)?;
+            }
+        }
         Ok(())
     }
 
@@ -130,46 +205,80 @@ impl IRGenerator {
     pub fn abi_array(
         &mut self,
         name: &str,
-        ident_def: DefinitionId,
+        ident_def: Option<DefinitionId>,
         el_type: &noirc_abi::AbiType,
         len: u128,
         witness: Vec<acvm::acir::native_types::Witness>,
-    ) {
-        let element_type = match el_type {
-            noirc_abi::AbiType::Field(_) => ObjectType::NativeField,
-            noirc_abi::AbiType::Integer { sign, width, .. } => match sign {
-                noirc_abi::Sign::Unsigned => ObjectType::Unsigned(*width),
-                noirc_abi::Sign::Signed => ObjectType::Signed(*width),
-            },
-            noirc_abi::AbiType::Array { .. } => unreachable!(),
-        };
-        let (v_id, array_idx) = self.new_array(name, element_type, len as u32, Some(ident_def));
+    ) -> Value {
+        let element_type = Self::abi_object_type(el_type);
+        let (v_id, array_idx) = self.new_array(name, element_type, len as u32, ident_def);
         self.context.mem[array_idx].values = vecmap(witness, |w| w.into());
         self.context.get_current_block_mut().update_variable(v_id, v_id);
+        Value::Single(v_id)
     }
 
     pub fn abi_var(
         &mut self,
         name: &str,
-        ident_def: DefinitionId,
-        obj_type: node::ObjectType,
+        ident_def: Option<DefinitionId>,
+        abi_type: &noirc_abi::AbiType,
         witness: acvm::acir::native_types::Witness,
-    ) {
+    ) -> Result<Value, RuntimeError> {
+        // The witness of a long integer holds its value as a field element
+        let long_bits = long_integer::abi_long_integer_bits(abi_type);
+        let obj_type = match long_bits {
+            Some(_) => ObjectType::NativeField,
+            None => Self::abi_object_type(abi_type),
+        };
         //new variable - should be in a let statement? The let statement should set the type
         let var = node::Variable {
             id: NodeId::dummy(),
             name: name.to_string(),
             obj_type,
             root: None,
-            def: Some(ident_def),
+            def: ident_def,
             witness: Some(witness),
             parent_block: self.context.current_block,
         };
         let v_id = self.context.add_variable(var, None);
 
         self.context.get_current_block_mut().update_variable(v_id, v_id);
-        let v_value = Value::Single(v_id);
-        self.variable_values.insert(ident_def, v_value); //TODO ident_def or ident_id??
+        let v_value = match long_bits {
+            Some(bit_size) => {
+                let types = long_integer::limb_types(bit_size);
+                let location = self.program.main().location;
+                let limbs = self.long_integer_from_field(v_id, &types, true, location)?;
+                Value::Tuple(vecmap(limbs, Value::Single))
+            }
+            None => Value::Single(v_id),
+        };
+        if let Some(ident_def) = ident_def {
+            self.variable_values.insert(ident_def, v_value.clone()); //TODO ident_def or ident_id??
+        }
+        Ok(v_value)
+    }
+
+    /// Groups the values of the fields of a struct or tuple parameter of main,
+    /// or of the elements of an array parameter whose elements are not scalars.
+    pub fn abi_struct(&mut self, ident_def: Option<DefinitionId>, values: Vec<Value>) -> Value {
+        self.insert_new_struct(ident_def, values)
+    }
+
+    fn abi_object_type(abi_type: &noirc_abi::AbiType) -> ObjectType {
+        match abi_type {
+            noirc_abi::AbiType::Field(_) => ObjectType::NativeField,
+            noirc_abi::AbiType::Integer { sign, width, .. } => match sign {
+                noirc_abi::Sign::Unsigned => ObjectType::Unsigned(*width),
+                noirc_abi::Sign::Signed => ObjectType::Signed(*width),
+            },
+            noirc_abi::AbiType::Boolean(_) => ObjectType::Boolean,
+            noirc_abi::AbiType::Array { .. }
+            | noirc_abi::AbiType::String { .. }
+            | noirc_abi::AbiType::Struct { .. }
+            | noirc_abi::AbiType::Tuple { .. } => {
+                unreachable!("{:?} is not a scalar type", abi_type)
+            }
+        }
     }
 
     fn codegen_identifier(&mut self, ident: &Ident) -> Value {
@@ -187,6 +296,7 @@ impl IRGenerator {
             UnaryOp::Minus => {
                 let lhs = self.context.zero_with_type(rtype);
                 let operator = BinaryOp::Sub { max_rhs_value: BigUint::zero() };
+                let operator = self.checked_operator(operator, rtype);
                 let op = Operation::Binary(node::Binary { operator, lhs, rhs });
                 self.context.new_instruction(op, rtype)
             }
@@ -194,6 +304,20 @@ impl IRGenerator {
         }
     }
 
+    /// Returns the checked version of an integer addition, subtraction or multiplication
+    /// when the arithmetic of the function being generated is checked
+    fn checked_operator(&self, operator: BinaryOp, op_type: ObjectType) -> BinaryOp {
+        if !self.checked || !matches!(op_type, ObjectType::Unsigned(_) | ObjectType::Signed(_)) {
+            return operator;
+        }
+        match operator {
+            BinaryOp::Add => BinaryOp::SafeAdd,
+            BinaryOp::Sub { max_rhs_value } => BinaryOp::SafeSub { max_rhs_value },
+            BinaryOp::Mul => BinaryOp::SafeMul,
+            operator => operator,
+        }
+    }
+
     fn codegen_infix_expression(
         &mut self,
         lhs: NodeId,
@@ -202,144 +326,155 @@ impl IRGenerator {
     ) -> Result<NodeId, RuntimeError> {
         let ltype = self.context.get_object_type(lhs);
         // Get the opcode from the infix operator
-        let opcode = Operation::Binary(Binary::from_ast(op, ltype, lhs, rhs));
+        let mut binary = Binary::from_ast(op, ltype, lhs, rhs);
+        binary.operator = self.checked_operator(binary.operator, ltype);
+        let opcode = Operation::Binary(binary);
         let optype = self.context.get_result_type(&opcode, ltype);
         self.context.new_instruction(opcode, optype)
     }
 
-    fn codegen_indexed_value(
+    /// Arrays whose elements are arrays or structs are kept as a tuple of their elements.
+    /// The element is chosen directly when the index is known at compile time,
+    /// otherwise each element is conditionally selected.
+    fn codegen_tuple_index(
         &mut self,
-        array: &LValue,
-        index: &Expression,
-        env: &mut Environment,
-    ) -> Result<(ArrayId, NodeId), RuntimeError> {
-        let ident_def = Self::lvalue_ident_def(array);
-        let val = self.find_variable(ident_def).unwrap();
-        let lhs = val.unwrap_id();
-
-        let a_id = self.context.get_object_type(lhs).type_to_pointer();
-        let index = self.codegen_expression(env, index)?.unwrap_id();
-        Ok((a_id, index))
-    }
+        mut elements: Vec<Value>,
+        index: NodeId,
+        location: noirc_errors::Location,
+    ) -> Result<Value, RuntimeError> {
+        if let Some(index) = self.context.get_as_constant(index) {
+            let index = index.to_u128();
+            let bound = elements.len() as u128;
+            if index >= bound {
+                let kind = RuntimeErrorKind::ArrayOutOfBounds { index, bound };
+                return Err(kind.add_location(location));
+            }
+            return Ok(elements.swap_remove(index as usize));
+        }
 
-    fn lvalue_ident_def(lvalue: &LValue) -> DefinitionId {
-        match lvalue {
-            LValue::Ident(ident) => ident.id,
-            LValue::Index { array, .. } => Self::lvalue_ident_def(array.as_ref()),
-            LValue::MemberAccess { object, .. } => Self::lvalue_ident_def(object.as_ref()),
+        let selectors = self.codegen_index_selectors(index, elements.len(), location)?;
+        let mut result = elements.pop().expect("cannot index an empty array");
+        for (selector, element) in selectors.into_iter().zip(elements).rev() {
+            result = self.select_value(selector, element, result)?;
         }
+        Ok(result)
     }
 
-    pub fn create_new_variable(
+    /// Returns one boolean selector per element of an array of size len, which is true
+    /// if the index is the position of that element. The selectors are constrained
+    /// to sum to 1, which ensures that the index is within the bounds of the array.
+    fn codegen_index_selectors(
         &mut self,
-        var_name: String,
-        def: Option<DefinitionId>,
-        obj_type: node::ObjectType,
-        witness: Option<acvm::acir::native_types::Witness>,
-    ) -> NodeId {
-        let new_var = node::Variable {
-            id: NodeId::dummy(),
-            obj_type,
-            name: var_name,
-            root: None,
-            def,
-            witness,
-            parent_block: self.context.current_block,
-        };
-        let v_id = self.context.add_variable(new_var, None);
-        let v_value = Value::Single(v_id);
-        if let Some(def) = def {
-            self.variable_values.insert(def, v_value);
+        index: NodeId,
+        len: usize,
+        location: noirc_errors::Location,
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let index_type = self.context.get_object_type(index);
+        let mut selectors = Vec::with_capacity(len);
+        let mut sum = self.context.zero_with_type(ObjectType::NativeField);
+        for i in 0..len {
+            let i = self.context.get_or_create_const(FieldElement::from(i as u128), index_type);
+            let eq = Operation::binary(BinaryOp::Eq, index, i);
+            let selector = self.context.new_instruction(eq, ObjectType::Boolean)?;
+            let cast = Operation::Cast(selector);
+            let selector_field = self.context.new_instruction(cast, ObjectType::NativeField)?;
+            let add = Operation::binary(BinaryOp::Add, sum, selector_field);
+            sum = self.context.new_instruction(add, ObjectType::NativeField)?;
+            selectors.push(selector);
         }
-        v_id
-    }
 
-    //Helper function for create_new_value()
-    fn insert_new_struct(&mut self, def: Option<DefinitionId>, values: Vec<Value>) -> Value {
-        let result = Value::Tuple(values);
-        if let Some(def_id) = def {
-            self.variable_values.insert(def_id, result.clone());
-        }
-        result
+        let one = self.context.one_with_type(ObjectType::NativeField);
+        let eq = Operation::binary(BinaryOp::Eq, sum, one);
+        let in_bounds = self.context.new_instruction(eq, ObjectType::Boolean)?;
+        let constrain = Operation::Constrain(in_bounds, location, None);
+        self.context.new_instruction(constrain, ObjectType::NotAnObject)?;
+        Ok(selectors)
     }
 
-    pub fn create_new_value(
+    fn select_value(
         &mut self,
-        typ: &Type,
-        base_name: &str,
-        def: Option<DefinitionId>,
-    ) -> Value {
-        match typ {
-            Type::Tuple(fields) => {
-                let values = vecmap(fields.iter().enumerate(), |(i, field)| {
-                    let name = format!("{}.{}", base_name, i);
-                    self.create_new_value(field, &name, None)
-                });
-                self.insert_new_struct(def, values)
-            }
-            Type::Array(len, _) => {
-                //TODO support array of structs
-                let obj_type = node::ObjectType::from(typ);
-                let len = *len;
-                let (v_id, _) = self.new_array(base_name, obj_type, len.try_into().unwrap(), def);
-                Value::Single(v_id)
+        condition: NodeId,
+        val_true: Value,
+        val_false: Value,
+    ) -> Result<Value, RuntimeError> {
+        match (val_true, val_false) {
+            (Value::Single(val_true), Value::Single(val_false)) => {
+                let res_type = self.context.get_object_type(val_true);
+                let id = match res_type {
+                    ObjectType::Pointer(true_array) => {
+                        let false_array = self.context.get_object_type(val_false).type_to_pointer();
+                        self.select_array(condition, true_array, false_array)?
+                    }
+                    _ => {
+                        let cond = Operation::Cond { condition, val_true, val_false };
+                        self.context.new_instruction(cond, res_type)?
+                    }
+                };
+                Ok(Value::Single(id))
             }
-            _ => {
-                let obj_type = node::ObjectType::from(typ);
-                let v_id = self.create_new_variable(base_name.to_string(), def, obj_type, None);
-                self.context.get_current_block_mut().update_variable(v_id, v_id);
-                Value::Single(v_id)
+            (Value::Tuple(true_fields), Value::Tuple(false_fields)) => {
+                let mut fields = Vec::with_capacity(true_fields.len());
+                for (val_true, val_false) in true_fields.into_iter().zip(false_fields) {
+                    fields.push(self.select_value(condition, val_true, val_false)?);
+                }
+                Ok(Value::Tuple(fields))
             }
+            _ => unreachable!(
+                "Runtime type error, cannot select between a struct and a single value"
+            ),
         }
     }
 
-    pub fn new_array(
+    /// Creates a new array whose elements are conditionally selected from the given arrays
+    fn select_array(
         &mut self,
-        name: &str,
-        element_type: ObjectType,
-        len: u32,
-        def_id: Option<DefinitionId>,
-    ) -> (NodeId, ArrayId) {
-        let (id, array_id) = self.context.new_array(name, element_type, len, def_id);
-        if let Some(def) = def_id {
-            self.variable_values.insert(def, super::code_gen::Value::Single(id));
+        condition: NodeId,
+        true_array: ArrayId,
+        false_array: ArrayId,
+    ) -> Result<NodeId, RuntimeError> {
+        let element_type = self.context.mem[true_array].element_type;
+        let len = self.context.mem[true_array].len;
+        let (new_var, array_id) = self.context.new_array("", element_type, len, None);
+        for pos in 0..len {
+            let index = self
+                .context
+                .get_or_create_const(FieldElement::from(pos as u128), ObjectType::NativeField);
+            let load = Operation::Load { array_id: true_array, index };
+            let val_true = self.context.new_instruction(load, element_type)?;
+            let load = Operation::Load { array_id: false_array, index };
+            let val_false = self.context.new_instruction(load, element_type)?;
+            let cond = Operation::Cond { condition, val_true, val_false };
+            let value = self.context.new_instruction(cond, element_type)?;
+            let store = Operation::Store { array_id, index, value };
+            self.context.new_instruction(store, element_type)?;
         }
-        (id, array_id)
+        Ok(new_var)
     }
 
-    // Add a constraint to constrain two expression together
-    fn codegen_constrain(
+    /// Returns the variable an lvalue assigns to, along with the steps to the part of it
+    /// which is assigned. The indices of the lvalue are generated from left to right.
+    fn codegen_lvalue_steps(
         &mut self,
+        lvalue: &LValue,
         env: &mut Environment,
-        expr: &Expression,
-        location: noirc_errors::Location,
-    ) -> Result<Value, RuntimeError> {
-        let cond = self.codegen_expression(env, expr)?.unwrap_id();
-        let operation = Operation::Constrain(cond, location);
-        self.context.new_instruction(operation, ObjectType::NotAnObject)?;
-        Ok(Value::dummy())
-    }
-
-    /// Bind the given DefinitionId to the given Value. This will flatten the Value as needed,
-    /// expanding each field of the value to a new variable.
-    fn bind_id(&mut self, id: DefinitionId, value: Value, name: &str) -> Result<(), RuntimeError> {
-        match value {
-            Value::Single(node_id) => {
-                let otype = self.context.get_object_type(node_id);
-                let value = self.bind_variable(name.to_owned(), Some(id), otype, node_id)?;
-                self.variable_values.insert(id, value);
+        steps: &mut Vec<LValueStep>,
+    ) -> Result<DefinitionId, RuntimeError> {
+        match lvalue {
+            LValue::Ident(ident) => Ok(ident.id),
+            LValue::MemberAccess { object, field_index } => {
+                let ident_def = self.codegen_lvalue_steps(object, env, steps)?;
+                steps.push(LValueStep::Field(*field_index));
+                Ok(ident_def)
             }
-            value @ Value::Tuple(_) => {
-                let value = self.bind_fresh_pattern(name, value)?;
-                self.variable_values.insert(id, value);
+            LValue::Index { array, index, location } => {
+                let ident_def = self.codegen_lvalue_steps(array, env, steps)?;
+                let index = self.codegen_expression(env, index)?.unwrap_id();
+                steps.push(LValueStep::Index(index, *location));
+                Ok(ident_def)
             }
         }
-        Ok(())
     }
 
-    /// This function is a recursive helper for bind_pattern which takes care
-    /// of creating fresh variables to expand `ident = (a, b, ...)` to `(i_a, i_b, ...) = (a, b, ...)`
-    ///
     /// This function could use a clearer name
     fn bind_fresh_pattern(&mut self, basename: &str, value: Value) -> Result<Value, RuntimeError> {
         match value {
@@ -393,33 +528,89 @@ impl IRGenerator {
         expression: &Expression,
         env: &mut Environment,
     ) -> Result<Value, RuntimeError> {
-        let ident_def = Self::lvalue_ident_def(lvalue);
         let rhs = self.codegen_expression(env, expression)?;
+        let mut steps = Vec::new();
+        let ident_def = self.codegen_lvalue_steps(lvalue, env, &mut steps)?;
+
+        // We may be able to avoid cloning here if we change find_variable
+        // and assign_pattern to use only fields of self instead of `self` itself.
+        let lhs = self.find_variable(ident_def).unwrap().clone();
+        let result = self.assign_lvalue(&lhs, &steps, rhs, None)?;
+        if steps.is_empty() {
+            self.variable_values.insert(ident_def, result);
+        }
+        Ok(Value::dummy())
+    }
 
-        match lvalue {
-            LValue::Ident(_) => {
-                let lhs = self.find_variable(ident_def).unwrap();
-                // We may be able to avoid cloning here if we change find_variable
-                // and assign_pattern to use only fields of self instead of `self` itself.
-                let lhs = lhs.clone();
-                let result = self.assign_pattern(&lhs, rhs)?;
-                self.variable_values.insert(ident_def, result);
-            }
-            LValue::Index { array, index } => {
-                let (_, array_idx) = self.codegen_indexed_value(array.as_ref(), index, env)?;
-                let val = self.find_variable(ident_def).unwrap();
-                let rhs_id = rhs.unwrap_id();
-                let lhs_id = val.unwrap_id();
-                self.context.handle_assign(lhs_id, Some(array_idx), rhs_id)?;
-            }
-            LValue::MemberAccess { object: _, field_index } => {
-                // TODO: This is incorrect for nested structs
-                let val = self.find_variable(ident_def).unwrap();
-                let value = val.get_field_member(*field_index).clone();
-                self.assign_pattern(&value, rhs)?;
+    /// Assigns rhs to the part of lhs which the steps lead to. Arrays of arrays or structs are
+    /// kept as a tuple of their elements, so an element of one chosen by a witness index is
+    /// assigned by conditionally assigning each element, under the given condition if any.
+    fn assign_lvalue(
+        &mut self,
+        lhs: &Value,
+        steps: &[LValueStep],
+        rhs: Value,
+        condition: Option<NodeId>,
+    ) -> Result<Value, RuntimeError> {
+        let (step, rest) = match steps.split_first() {
+            Some(split) => split,
+            None => {
+                let rhs = match condition {
+                    Some(condition) => {
+                        let current = self.get_current_value(lhs);
+                        self.select_value(condition, rhs, current)?
+                    }
+                    None => rhs,
+                };
+                return self.assign_pattern(lhs, rhs);
+            }
+        };
+
+        match (step, lhs) {
+            (LValueStep::Field(field_index), Value::Tuple(fields)) => {
+                self.assign_lvalue(&fields[*field_index], rest, rhs, condition)
+            }
+            (LValueStep::Index(index, location), Value::Tuple(elements)) => {
+                if let Some(index) = self.context.get_as_constant(*index) {
+                    let index = index.to_u128();
+                    let bound = elements.len() as u128;
+                    if index >= bound {
+                        let kind = RuntimeErrorKind::ArrayOutOfBounds { index, bound };
+                        return Err(kind.add_location(*location));
+                    }
+                    return self.assign_lvalue(&elements[index as usize], rest, rhs, condition);
+                }
+
+                let selectors = self.codegen_index_selectors(*index, elements.len(), *location)?;
+                for (selector, element) in selectors.into_iter().zip(elements) {
+                    let selector = match condition {
+                        Some(condition) => {
+                            let zero = self.context.zero();
+                            self.select_bool(condition, selector, zero)?
+                        }
+                        None => selector,
+                    };
+                    self.assign_lvalue(element, rest, rhs.clone(), Some(selector))?;
+                }
+                Ok(Value::dummy())
+            }
+            (LValueStep::Index(index, _), Value::Single(array)) => {
+                assert!(rest.is_empty(), "the elements of an array in memory cannot be indexed");
+                let mut rhs = rhs.unwrap_id();
+                if let Some(condition) = condition {
+                    let array_id = self.context.get_object_type(*array).type_to_pointer();
+                    let element_type = self.context.mem[array_id].element_type;
+                    let load = Operation::Load { array_id, index: *index };
+                    let current = self.context.new_instruction(load, element_type)?;
+                    let cond = Operation::Cond { condition, val_true: rhs, val_false: current };
+                    rhs = self.context.new_instruction(cond, element_type)?;
+                }
+                Ok(Value::Single(self.context.handle_assign(*array, Some(*index), rhs)?))
+            }
+            (LValueStep::Field(_), Value::Single(_)) => {
+                unreachable!("Uncaught type error, tried to access a field of a single value")
             }
         }
-        Ok(Value::dummy())
     }
 
     /// Similar to bind_pattern but recursively creates Assignment instructions for
@@ -459,8 +650,21 @@ impl IRGenerator {
         expr: &Expression,
     ) -> Result<Value, RuntimeError> {
         match expr {
-            Expression::Literal(Literal::Integer(x, typ)) => {
-                Ok(Value::Single(self.context.get_or_create_const(*x, typ.into())))
+            Expression::Literal(Literal::Integer(x, typ)) => match long_integer_bits(typ) {
+                Some(bit_size) => Ok(self.long_integer_const(*x, bit_size)),
+                None => Ok(Value::Single(self.context.get_or_create_const(*x, typ.into()))),
+            },
+            Expression::Literal(Literal::Array(arr_lit))
+                if long_integer_bits(&arr_lit.element_type).is_some()
+                    || matches!(arr_lit.element_type, Type::Array(..) | Type::Tuple(_)) =>
+            {
+                // Arrays of long integers, arrays or structs are kept as a tuple of their elements
+                let elements = arr_lit
+                    .contents
+                    .iter()
+                    .map(|element| self.codegen_expression(env, element))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Tuple(elements))
             }
             Expression::Literal(Literal::Array(arr_lit)) => {
                 let element_type = ObjectType::from(&arr_lit.element_type);
@@ -487,19 +691,29 @@ impl IRGenerator {
                 // Note: using .into_id() here disallows structs/tuples in infix expressions.
                 // The type checker currently disallows this as well but we may want to allow
                 // for e.g. struct == struct in the future
-                let lhs = self.codegen_expression(env, &binary.lhs)?.unwrap_id();
-                let rhs = self.codegen_expression(env, &binary.rhs)?.unwrap_id();
-                Ok(Value::Single(self.codegen_infix_expression(lhs, rhs, binary.operator)?))
+                let lhs = self.codegen_expression(env, &binary.lhs)?;
+                let rhs = self.codegen_expression(env, &binary.rhs)?;
+                self.codegen_binary(lhs, rhs, binary.operator, binary.location)
             }
             Expression::Cast(cast_expr) => {
-                let lhs = self.codegen_expression(env, &cast_expr.lhs)?.unwrap_id();
+                let lhs = self.codegen_expression(env, &cast_expr.lhs)?;
+                if matches!(lhs, Value::Tuple(_)) || long_integer_bits(&cast_expr.r#type).is_some()
+                {
+                    return self.codegen_long_cast(lhs, &cast_expr.r#type, cast_expr.location);
+                }
+                let lhs = lhs.unwrap_id();
                 let rtype = ObjectType::from(&cast_expr.r#type);
 
                 Ok(Value::Single(self.context.new_instruction(Operation::Cast(lhs), rtype)?))
             }
             Expression::Index(indexed_expr) => {
                 // Evaluate the 'array' expression
-                let expr_node = self.codegen_expression(env, &indexed_expr.collection)?.unwrap_id();
+                let collection = self.codegen_expression(env, &indexed_expr.collection)?;
+                if let Value::Tuple(elements) = collection {
+                    let index = self.codegen_expression(env, &indexed_expr.index)?.unwrap_id();
+                    return self.codegen_tuple_index(elements, index, indexed_expr.location);
+                }
+                let expr_node = collection.unwrap_id();
                 let array = match self.context.get_object_type(expr_node) {
                     ObjectType::Pointer(array_id) => &self.context.mem[array_id],
                     other => unreachable!("Expected Pointer type, found {:?}", other),
@@ -512,6 +726,10 @@ impl IRGenerator {
                 Ok(Value::Single(self.context.new_instruction(load, e_type)?))
             }
             Expression::Call(call_expr) => {
+                if self.recursive_functions.contains(&call_expr.func_id) {
+                    return self.codegen_recursive_call(env, call_expr);
+                }
+
                 if self.context.get_ssafunc(call_expr.func_id).is_none() {
                     let index = self.context.get_function_index();
                     self.create_function(call_expr.func_id, env, index)?;
@@ -526,13 +744,7 @@ impl IRGenerator {
                 let results = self.call(call_expr, env)?;
 
                 let function = &self.program[call_expr.func_id];
-                Ok(match &function.return_type {
-                    Type::Tuple(_) => Value::Tuple(vecmap(results, Value::Single)),
-                    _ => {
-                        assert_eq!(results.len(), 1);
-                        Value::Single(results[0])
-                    }
-                })
+                Ok(Self::result_value(&function.return_type, &mut results.into_iter()))
             }
             Expression::CallLowLevel(call) => Ok(Value::Single(self.codegen_lowlevel(env, call)?)),
             Expression::CallBuiltin(_call) => {
@@ -542,12 +754,18 @@ impl IRGenerator {
                 // builtin::call_builtin(self, env, builtin_name, (call_expr,span))
             }
             Expression::For(for_expr) => self.codegen_for(env, for_expr),
+            Expression::While(while_expr) => self.codegen_while(env, while_expr),
+            Expression::Break => self.codegen_loop_exit(true),
+            Expression::Continue => self.codegen_loop_exit(false),
+            Expression::Return(value, _) => self.codegen_return(env, value),
             Expression::Tuple(fields) => self.codegen_tuple(env, fields),
             Expression::If(if_expr) => self.handle_if_expr(env, if_expr),
-            Expression::Unary(prefix) => {
-                let rhs = self.codegen_expression(env, &prefix.rhs)?.unwrap_id();
-                self.codegen_prefix_expression(rhs, prefix.operator).map(Value::Single)
-            }
+            Expression::Unary(prefix) => match self.codegen_expression(env, &prefix.rhs)? {
+                rhs @ Value::Tuple(_) => self.codegen_long_prefix(rhs, prefix.operator),
+                rhs => self
+                    .codegen_prefix_expression(rhs.unwrap_id(), prefix.operator)
+                    .map(Value::Single),
+            },
             Expression::Literal(l) => Ok(Value::Single(self.codegen_literal(l))),
             Expression::Block(block) => self.codegen_block(block, env),
             Expression::ExtractTupleField(expr, field) => {
@@ -555,8 +773,8 @@ impl IRGenerator {
                 Ok(tuple.into_field_member(*field))
             }
             Expression::Let(let_expr) => self.codegen_let(env, let_expr),
-            Expression::Constrain(expr, location) => {
-                self.codegen_constrain(env, expr.as_ref(), *location)
+            Expression::Constrain(expr, location, message) => {
+                self.codegen_constrain(env, expr.as_ref(), *location, message.clone())
             }
             Expression::Assign(assign) => {
                 self.codegen_assign(&assign.lvalue, assign.expression.as_ref(), env)
@@ -568,6 +786,104 @@ impl IRGenerator {
         }
     }
 
+    fn codegen_binary(
+        &mut self,
+        lhs: Value,
+        rhs: Value,
+        operator: BinaryOpKind,
+        location: noirc_errors::Location,
+    ) -> Result<Value, RuntimeError> {
+        // The exact result of a checked operation on wider integers may not fit in a field element
+        let max_bit_size = integer::short_integer_max_bit_size();
+        let is_wide = match &lhs {
+            Value::Tuple(_) => true,
+            Value::Single(id) => matches!(
+                self.context.get_object_type(*id),
+                ObjectType::Signed(bit_size) if bit_size >= max_bit_size
+            ),
+        };
+        let is_checked = self.checked
+            && matches!(
+                operator,
+                BinaryOpKind::Add | BinaryOpKind::Subtract | BinaryOpKind::Multiply
+            );
+        if is_wide && is_checked {
+            let message = format!(
+                "checked {} is not supported for integers of {} bits or more",
+                operator.as_string(),
+                max_bit_size
+            );
+            return Err(RuntimeErrorKind::UnstructuredError { message }.add_location(location));
+        }
+        if let Value::Tuple(_) = lhs {
+            return self.codegen_long_infix(lhs, rhs, operator, location);
+        }
+        let (lhs, rhs) = (lhs.unwrap_id(), rhs.unwrap_id());
+        Ok(Value::Single(self.codegen_infix_expression(lhs, rhs, operator)?))
+    }
+
+    /// Rebuilds a value of the given type from the flattened results of a function call
+    fn result_value(typ: &Type, results: &mut impl Iterator<Item = NodeId>) -> Value {
+        match typ {
+            Type::Tuple(fields) => {
+                Value::Tuple(vecmap(fields, |field| Self::result_value(field, results)))
+            }
+            Type::Array(len, element) if long_integer_bits(element).is_some() => {
+                Value::Tuple(vecmap(0..*len, |_| Self::result_value(element, results)))
+            }
+            _ => match long_integer_bits(typ) {
+                Some(bit_size) => Value::Tuple(vecmap(long_integer::limb_types(bit_size), |_| {
+                    Value::Single(results.next().unwrap())
+                })),
+                None => Value::Single(results.next().unwrap()),
+            },
+        }
+    }
+
+    /// Expands a call to a recursive function in place, by generating the body of the function
+    /// with its parameters bound to the arguments. The expansion terminates once the base case
+    /// is selected by a condition which is known at compile time.
+    fn codegen_recursive_call(
+        &mut self,
+        env: &mut Environment,
+        call: &Call,
+    ) -> Result<Value, RuntimeError> {
+        let function = &self.program[call.func_id];
+        if self.recursion_depth >= self.max_recursion_depth {
+            let message = format!(
+                "cannot determine the recursion depth of `{}` at compile time, or it exceeds the limit of {}",
+                function.name, self.max_recursion_depth
+            );
+            return Err(RuntimeErrorKind::UnstructuredError { message }.add_location(call.location));
+        }
+        let parameters = function.parameters.clone();
+        let body = function.body.clone();
+        let checked_arithmetic = function.checked_arithmetic;
+
+        let arguments = call
+            .arguments
+            .iter()
+            .map(|argument| self.codegen_expression(env, argument))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let caller_values = self.variable_values.clone();
+        for ((param_id, mutable, name, _), argument) in parameters.iter().zip(arguments) {
+            if *mutable {
+                self.bind_id(*param_id, argument, name)?;
+            } else {
+                // Binding the value directly keeps constant arguments constant,
+                // so that the conditions on the recursion depth can be evaluated
+                self.variable_values.insert(*param_id, argument);
+            }
+        }
+
+        self.recursion_depth += 1;
+        let result = self.codegen_function_body(env, &body, checked_arithmetic);
+        self.recursion_depth -= 1;
+        self.variable_values = caller_values;
+        result
+    }
+
     fn codegen_lowlevel(
         &mut self,
         env: &mut Environment,
@@ -594,7 +910,7 @@ impl IRGenerator {
                 }
             }
             Literal::Integer(f, typ) => self.context.get_or_create_const(*f, typ.into()),
-            _ => todo!(), //todo: add support for Array(ArrayLiteral), Str(String)
+            Literal::Array(_) => unreachable!("array literals are handled in codegen_expression"),
         }
     }
 
@@ -630,6 +946,10 @@ impl IRGenerator {
         env: &mut Environment,
         for_expr: &For,
     ) -> Result<Value, RuntimeError> {
+        if let Some(max_iterations) = &for_expr.max_iterations {
+            return self.codegen_bounded_for(env, for_expr, max_iterations);
+        }
+
         //we add the 'i = start' instruction (in the block before the join)
         let start_idx = self.codegen_expression(env, &for_expr.start_range).unwrap().unwrap_id();
         let end_idx = self.codegen_expression(env, &for_expr.end_range).unwrap().unwrap_id();
@@ -644,6 +964,118 @@ impl IRGenerator {
         let iter_var = self.context.get_mut_variable(iter_id).unwrap();
         iter_var.obj_type = iter_type;
 
+        let flags = if exits_loop(&for_expr.block) { Some(self.new_loop_flags()?) } else { None };
+        self.codegen_loop(env, iter_id, start_idx, end_idx, |this, env| {
+            this.codegen_loop_body(env, flags, None, &for_expr.block)
+        })
+    }
+
+    /// A for loop whose range is not known at compile time is unrolled over its maximum number
+    /// of iterations, each iteration being conditioned on the index being lower than the end.
+    /// The iterations are counted separately from the index, whose start may not be known either.
+    fn codegen_bounded_for(
+        &mut self,
+        env: &mut Environment,
+        for_expr: &For,
+        max_iterations: &Expression,
+    ) -> Result<Value, RuntimeError> {
+        let start = self.codegen_expression(env, &for_expr.start_range)?.unwrap_id();
+        let end = self.codegen_expression(env, &for_expr.end_range)?.unwrap_id();
+        let max_iterations = self.codegen_expression(env, max_iterations)?.unwrap_id();
+        let max_iterations = match self.context.get_as_constant(max_iterations) {
+            Some(max_iterations) => max_iterations,
+            None => {
+                let message =
+                    "the maximum number of iterations of a loop must be known at compile-time";
+                let kind = RuntimeErrorKind::UnstructuredError { message: message.to_string() };
+                return Err(kind.add_location(for_expr.location));
+            }
+        };
+
+        let iter_type = ObjectType::from(&for_expr.index_type);
+        let index_name = for_expr.index_name.clone();
+        let iter_id =
+            self.create_new_variable(index_name, Some(for_expr.index_variable), iter_type, None);
+        self.context.handle_assign(iter_id, None, start)?;
+
+        let zero = self.context.get_or_create_const(FieldElement::zero(), iter_type);
+        let max_iterations = self.context.get_or_create_const(max_iterations, iter_type);
+        let counter = self.create_new_variable("for".to_string(), None, iter_type, None);
+
+        let flags = self.new_loop_flags()?;
+        self.codegen_loop(env, counter, zero, max_iterations, |this, env| {
+            let counter = ssa_form::get_current_value(&mut this.context, counter);
+            let index = this.codegen_range_index(start, counter, iter_type)?;
+            this.context.handle_assign(iter_id, None, index)?;
+            let condition = this.codegen_infix_expression(index, end, BinaryOpKind::Less)?;
+            this.codegen_loop_body(env, Some(flags), Some(condition), &for_expr.block)
+        })?;
+
+        //the end of the range must have been reached within the maximum number of iterations
+        let broken = ssa_form::get_current_value(&mut self.context, flags.broken);
+        let last_index = self.codegen_range_index(start, max_iterations, iter_type)?;
+        let condition = self.codegen_infix_expression(last_index, end, BinaryOpKind::Less)?;
+        let not_condition =
+            self.context.new_instruction(Operation::Not(condition), ObjectType::Boolean)?;
+        let one = self.context.one();
+        let terminated = self.select_bool(broken, one, not_condition)?;
+        let constrain = Operation::Constrain(terminated, for_expr.location, None);
+        self.context.new_instruction(constrain, ObjectType::NotAnObject)?;
+        Ok(Value::dummy())
+    }
+
+    /// Returns the index of a loop after the given number of iterations from start
+    fn codegen_range_index(
+        &mut self,
+        start: NodeId,
+        iterations: NodeId,
+        iter_type: ObjectType,
+    ) -> Result<NodeId, RuntimeError> {
+        let index = Operation::binary(BinaryOp::Add, start, iterations);
+        self.context.new_instruction(index, iter_type)
+    }
+
+    /// A while loop is unrolled like a for loop over its maximum number of iterations,
+    /// each iteration being conditioned on the loop not having been exited yet.
+    fn codegen_while(
+        &mut self,
+        env: &mut Environment,
+        while_expr: &While,
+    ) -> Result<Value, RuntimeError> {
+        let max_iterations = self.codegen_expression(env, &while_expr.max_iterations)?.unwrap_id();
+        let counter_type = self.context.get_object_type(max_iterations);
+        let start = self.context.get_or_create_const(FieldElement::zero(), counter_type);
+        let counter = self.create_new_variable("while".to_string(), None, counter_type, None);
+
+        let flags = self.new_loop_flags()?;
+        self.codegen_loop(env, counter, start, max_iterations, |this, env| {
+            let condition = this.codegen_expression(env, &while_expr.condition)?.unwrap_id();
+            this.codegen_loop_body(env, Some(flags), Some(condition), &while_expr.block)
+        })?;
+
+        //the loop must have been exited within its maximum number of iterations
+        let broken = ssa_form::get_current_value(&mut self.context, flags.broken);
+        let condition = self.codegen_expression(env, &while_expr.condition)?.unwrap_id();
+        let not_condition =
+            self.context.new_instruction(Operation::Not(condition), ObjectType::Boolean)?;
+        let one = self.context.one();
+        let terminated = self.select_bool(broken, one, not_condition)?;
+        let constrain = Operation::Constrain(terminated, while_expr.location, None);
+        self.context.new_instruction(constrain, ObjectType::NotAnObject)?;
+        Ok(Value::dummy())
+    }
+
+    /// Generates the control flow of a loop running the iterator from start to end,
+    /// the body of the loop being generated by the given function
+    fn codegen_loop(
+        &mut self,
+        env: &mut Environment,
+        iter_id: NodeId,
+        start_idx: NodeId,
+        end_idx: NodeId,
+        codegen_body: impl FnOnce(&mut Self, &mut Environment) -> Result<(), RuntimeError>,
+    ) -> Result<Value, RuntimeError> {
+        let iter_type = self.context.get_object_type(iter_id);
         let assign = Operation::binary(BinaryOp::Assign, iter_id, start_idx);
         let iter_ass = self.context.new_instruction(assign, iter_type)?;
 
@@ -675,7 +1107,7 @@ impl IRGenerator {
         let body_block1 = &mut self.context[body_id];
         body_block1.update_variable(iter_id, phi); //TODO try with just a get_current_value(iter)
 
-        self.codegen_expression(env, for_expr.block.as_ref())?;
+        codegen_body(self, env)?;
 
         //increment iter
         let one = self.context.get_or_create_const(FieldElement::one(), iter_type);
@@ -706,15 +1138,284 @@ impl IRGenerator {
         Ok(Value::Single(exit_first)) //TODO what should we return???
     }
 
+    /// Generates one iteration of the body of a loop. When the loop can be exited, the body
+    /// is conditioned on the loop not having been exited by a break, its condition or a return.
+    fn codegen_loop_body(
+        &mut self,
+        env: &mut Environment,
+        flags: Option<LoopFlags>,
+        condition: Option<NodeId>,
+        body: &Expression,
+    ) -> Result<(), RuntimeError> {
+        let returns = self.returns.is_some() && return_type(body).is_some();
+        if flags.is_none() && !returns {
+            self.codegen_expression(env, body)?;
+            return Ok(());
+        }
+
+        let entry_block = self.new_entry_block();
+        let zero = self.context.zero();
+        let mut active = self.context.one();
+        if let Some(flags) = flags {
+            self.context.handle_assign(flags.continued, None, zero)?;
+            let broken = ssa_form::get_current_value(&mut self.context, flags.broken);
+            active = self.context.new_instruction(Operation::Not(broken), ObjectType::Boolean)?;
+            if let Some(condition) = condition {
+                active = self.select_bool(broken, zero, condition)?;
+            }
+        }
+        if returns {
+            let returned = self.returned()?;
+            active = self.select_bool(returned, zero, active)?;
+        }
+        if let (Some(flags), Some(_)) = (flags, condition) {
+            // A loop with a condition is exited once an iteration is not run
+            let exited =
+                self.context.new_instruction(Operation::Not(active), ObjectType::Boolean)?;
+            self.context.handle_assign(flags.broken, None, exited)?;
+        }
+
+        self.loops.extend(flags);
+        let result = self.codegen_branches(
+            env,
+            entry_block,
+            active,
+            |this, env| this.codegen_expression(env, body),
+            |_, _| Ok(Value::dummy()),
+        );
+        if flags.is_some() {
+            self.loops.pop();
+        }
+        result.map(|_| ())
+    }
+
+    /// Creates a boolean variable set to false
+    fn new_flag(&mut self, name: &str) -> Result<NodeId, RuntimeError> {
+        let flag = self.create_new_variable(name.to_string(), None, ObjectType::Boolean, None);
+        let zero = self.context.zero();
+        self.context.handle_assign(flag, None, zero)?;
+        Ok(flag)
+    }
+
+    /// Creates the flags of a loop which can be exited, before entering the loop
+    fn new_loop_flags(&mut self) -> Result<LoopFlags, RuntimeError> {
+        let broken = self.new_flag("break")?;
+        let continued = self.new_flag("continue")?;
+        Ok(LoopFlags { broken, continued })
+    }
+
+    /// Sets the flag of the innermost loop for a break or a continue
+    fn codegen_loop_exit(&mut self, is_break: bool) -> Result<Value, RuntimeError> {
+        let flags = *self.loops.last().expect("break and continue can only be used inside a loop");
+        let flag = if is_break { flags.broken } else { flags.continued };
+        let one = self.context.one();
+        self.context.handle_assign(flag, None, one)?;
+        Ok(Value::dummy())
+    }
+
+    /// Generates the body of a function. When the function returns early, its body
+    /// assigns the value which it returns instead of producing it.
+    /// Its integer arithmetic is checked as selected by the function, or else by the package.
+    pub(super) fn codegen_function_body(
+        &mut self,
+        env: &mut Environment,
+        body: &Expression,
+        checked_arithmetic: Option<bool>,
+    ) -> Result<Value, RuntimeError> {
+        // The loops of the caller do not enclose the body of the callee
+        let caller_loops = std::mem::take(&mut self.loops);
+        let caller_returns = self.returns.take();
+        let caller_checked = self.checked;
+        self.checked = checked_arithmetic.unwrap_or(self.checked_arithmetic);
+        let result = match return_type(body) {
+            Some(typ) => self.codegen_returning_body(env, body, typ),
+            None => self.codegen_expression(env, body),
+        };
+        self.loops = caller_loops;
+        self.returns = caller_returns;
+        self.checked = caller_checked;
+        result
+    }
+
+    fn codegen_returning_body(
+        &mut self,
+        env: &mut Environment,
+        body: &Expression,
+        typ: &Type,
+    ) -> Result<Value, RuntimeError> {
+        let returned = self.new_flag("return")?;
+        let value = if *typ == Type::Unit {
+            Value::dummy()
+        } else {
+            let value = self.create_new_value(typ, "return", None);
+            for id in value.to_node_ids() {
+                let obj_type = self.context.get_object_type(id);
+                if !matches!(obj_type, ObjectType::Pointer(_)) {
+                    let zero = self.context.get_or_create_const(FieldElement::zero(), obj_type);
+                    self.context.handle_assign(id, None, zero)?;
+                }
+            }
+            value
+        };
+        self.returns = Some(ReturnFlags { returned, value: value.clone() });
+
+        self.codegen_tail(env, body)?;
+        if value.is_dummy() {
+            Ok(value)
+        } else {
+            Ok(self.get_current_value(&value))
+        }
+    }
+
+    /// Generates an expression whose value is returned by the function, in a function
+    /// which returns early
+    fn codegen_tail(
+        &mut self,
+        env: &mut Environment,
+        expr: &Expression,
+    ) -> Result<(), RuntimeError> {
+        match expr {
+            Expression::Block(block) => {
+                self.codegen_statements(env, block, true)?;
+            }
+            Expression::If(if_expr) => {
+                let entry_block = self.new_entry_block();
+                let condition = self.codegen_expression(env, &if_expr.condition)?.unwrap_id();
+                self.codegen_branches(
+                    env,
+                    entry_block,
+                    condition,
+                    |this, env| {
+                        this.codegen_tail(env, &if_expr.consequence).map(|_| Value::dummy())
+                    },
+                    |this, env| match &if_expr.alternative {
+                        Some(alt) => this.codegen_tail(env, alt).map(|_| Value::dummy()),
+                        None => Ok(Value::dummy()),
+                    },
+                )?;
+            }
+            Expression::Return(..) => {
+                self.codegen_expression(env, expr)?;
+            }
+            Expression::Semi(statement) if matches!(statement.as_ref(), Expression::Return(..)) => {
+                self.codegen_expression(env, statement)?;
+            }
+            _ if return_type(expr).is_some() => {
+                // The value is only returned if the expression did not return already
+                let value = self.codegen_expression(env, expr)?;
+                let entry_block = self.new_entry_block();
+                let returned = self.returned()?;
+                let active =
+                    self.context.new_instruction(Operation::Not(returned), ObjectType::Boolean)?;
+                self.codegen_branches(
+                    env,
+                    entry_block,
+                    active,
+                    |this, _| this.assign_return(value).map(|_| Value::dummy()),
+                    |_, _| Ok(Value::dummy()),
+                )?;
+            }
+            _ => {
+                let value = self.codegen_expression(env, expr)?;
+                self.assign_return(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn codegen_return(
+        &mut self,
+        env: &mut Environment,
+        value: &Expression,
+    ) -> Result<Value, RuntimeError> {
+        let value = self.codegen_expression(env, value)?;
+        self.assign_return(value.clone())?;
+        let returned =
+            self.returns.as_ref().expect("return can only be used in a function").returned;
+        let one = self.context.one();
+        self.context.handle_assign(returned, None, one)?;
+        Ok(value)
+    }
+
+    /// Assigns the value returned by the function being generated
+    fn assign_return(&mut self, value: Value) -> Result<(), RuntimeError> {
+        let returned_value = self.returns.as_ref().unwrap().value.clone();
+        if !returned_value.is_dummy() && !value.is_dummy() {
+            self.assign_pattern(&returned_value, value)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current value of the flag recording whether the function has returned
+    fn returned(&mut self) -> Result<NodeId, RuntimeError> {
+        let returned = self.returns.as_ref().unwrap().returned;
+        Ok(ssa_form::get_current_value(&mut self.context, returned))
+    }
+
+    /// Returns val_true if the boolean condition holds, and val_false otherwise
+    fn select_bool(
+        &mut self,
+        condition: NodeId,
+        val_true: NodeId,
+        val_false: NodeId,
+    ) -> Result<NodeId, RuntimeError> {
+        let select = Operation::Cond { condition, val_true, val_false };
+        self.context.new_instruction(select, ObjectType::Boolean)
+    }
+
     //Parse a block of AST statements into ssa form
     pub fn codegen_block(
         &mut self,
         block: &[Expression],
         env: &mut Environment,
+    ) -> Result<Value, RuntimeError> {
+        self.codegen_statements(env, block, false)
+    }
+
+    /// Generates a block of statements, the last one being in tail position of a function
+    /// which returns early if `tail` is set. The statements which follow a break, continue or
+    /// return are conditioned on the loop iteration or the function not having been exited.
+    fn codegen_statements(
+        &mut self,
+        env: &mut Environment,
+        block: &[Expression],
+        tail: bool,
     ) -> Result<Value, RuntimeError> {
         let mut last_value = Value::dummy();
-        for expr in block {
+        for (i, expr) in block.iter().enumerate() {
+            if tail && i + 1 == block.len() {
+                self.codegen_tail(env, expr)?;
+                return Ok(Value::dummy());
+            }
             last_value = self.codegen_expression(env, expr)?;
+
+            let loop_flags = self.loops.last().copied().filter(|_| exits_loop(expr));
+            let returns = self.returns.is_some() && return_type(expr).is_some();
+            if i + 1 < block.len() && (loop_flags.is_some() || returns) {
+                let entry_block = self.new_entry_block();
+                let zero = self.context.zero();
+                let mut active = self.context.one();
+                if let Some(flags) = loop_flags {
+                    let broken = ssa_form::get_current_value(&mut self.context, flags.broken);
+                    let continued = ssa_form::get_current_value(&mut self.context, flags.continued);
+                    let not_continued = self
+                        .context
+                        .new_instruction(Operation::Not(continued), ObjectType::Boolean)?;
+                    active = self.select_bool(broken, zero, not_continued)?;
+                }
+                if returns {
+                    let returned = self.returned()?;
+                    active = self.select_bool(returned, zero, active)?;
+                }
+                let rest = &block[i + 1..];
+                return self.codegen_branches(
+                    env,
+                    entry_block,
+                    active,
+                    |this, env| this.codegen_statements(env, rest, tail),
+                    |_, _| Ok(Value::dummy()),
+                );
+            }
         }
         Ok(last_value)
     }
@@ -724,34 +1425,55 @@ impl IRGenerator {
         env: &mut Environment,
         if_expr: &If,
     ) -> Result<Value, RuntimeError> {
-        //jump instruction
-        let mut entry_block = self.context.current_block;
+        let entry_block = self.new_entry_block();
+        let condition = self.codegen_expression(env, if_expr.condition.as_ref())?.unwrap_id();
+        self.codegen_branches(
+            env,
+            entry_block,
+            condition,
+            |this, env| this.codegen_expression(env, &if_expr.consequence),
+            |this, env| match &if_expr.alternative {
+                Some(alt) => this.codegen_expression(env, alt),
+                None => Ok(Value::dummy()),
+            },
+        )
+    }
+
+    /// Returns the block from which conditional branches can be generated
+    fn new_entry_block(&mut self) -> block::BlockId {
+        let entry_block = self.context.current_block;
         if self.context[entry_block].kind != BlockType::Normal {
-            entry_block =
-                block::new_sealed_block(&mut self.context, block::BlockType::Normal, true);
+            block::new_sealed_block(&mut self.context, block::BlockType::Normal, true)
+        } else {
+            entry_block
         }
+    }
 
-        let condition = self.codegen_expression(env, if_expr.condition.as_ref())?.unwrap_id();
-
+    /// Generates the blocks of the consequence and the alternative, depending on the condition
+    fn codegen_branches(
+        &mut self,
+        env: &mut Environment,
+        entry_block: block::BlockId,
+        condition: NodeId,
+        consequence: impl FnOnce(&mut Self, &mut Environment) -> Result<Value, RuntimeError>,
+        alternative: impl FnOnce(&mut Self, &mut Environment) -> Result<Value, RuntimeError>,
+    ) -> Result<Value, RuntimeError> {
         if let Some(cond) = node::NodeEval::from_id(&self.context, condition).into_const_value() {
             if cond.is_zero() {
-                if let Some(alt) = &if_expr.alternative {
-                    return self.codegen_expression(env, alt);
-                } else {
-                    return Ok(Value::dummy());
-                }
+                return alternative(self, env);
             } else {
-                return self.codegen_expression(env, if_expr.consequence.as_ref());
+                return consequence(self, env);
             }
         }
 
+        //jump instruction
         let jump_op = Operation::Jeq(condition, block::BlockId::dummy());
         let jump_ins = self.context.new_instruction(jump_op, ObjectType::NotAnObject).unwrap();
 
         //Then block
         block::new_sealed_block(&mut self.context, block::BlockType::Normal, true);
 
-        let v1 = self.codegen_expression(env, if_expr.consequence.as_ref())?;
+        let v1 = consequence(self, env)?;
 
         //Exit block
         let exit_block =
@@ -771,10 +1493,7 @@ impl IRGenerator {
             *target = block2;
         }
 
-        let mut v2 = Value::dummy();
-        if let Some(alt) = if_expr.alternative.as_ref() {
-            v2 = self.codegen_expression(env, alt)?;
-        }
+        let v2 = alternative(self, env)?;
 
         //Connect with the exit block
         self.context.get_current_block_mut().left = Some(exit_block);
@@ -790,3 +1509,58 @@ impl IRGenerator {
         Ok(v1.zip(&v2, &mut phi))
     }
 }
+
+/// Returns the expressions directly contained in the given expression
+fn sub_expressions(expr: &Expression) -> Vec<&Expression> {
+    match expr {
+        Expression::Ident(_) | Expression::Break | Expression::Continue => vec![],
+        Expression::Literal(Literal::Array(array)) => array.contents.iter().collect(),
+        Expression::Literal(_) => vec![],
+        Expression::Block(exprs) | Expression::Tuple(exprs) => exprs.iter().collect(),
+        Expression::Unary(unary) => vec![&unary.rhs],
+        Expression::Binary(binary) => vec![&binary.lhs, &binary.rhs],
+        Expression::Index(index) => vec![&index.collection, &index.index],
+        Expression::Cast(cast) => vec![&cast.lhs],
+        Expression::For(for_expr) => {
+            let mut exprs = vec![for_expr.start_range.as_ref(), for_expr.end_range.as_ref()];
+            exprs.extend(for_expr.max_iterations.as_deref());
+            exprs.push(&for_expr.block);
+            exprs
+        }
+        Expression::While(while_expr) => {
+            vec![&while_expr.condition, &while_expr.max_iterations, &while_expr.block]
+        }
+        Expression::If(if_expr) => {
+            let mut exprs = vec![if_expr.condition.as_ref(), if_expr.consequence.as_ref()];
+            exprs.extend(if_expr.alternative.as_deref());
+            exprs
+        }
+        Expression::Call(call) => call.arguments.iter().collect(),
+        Expression::CallBuiltin(call) => call.arguments.iter().collect(),
+        Expression::CallLowLevel(call) => call.arguments.iter().collect(),
+        Expression::Let(let_expr) => vec![&let_expr.expression],
+        Expression::Assign(assign) => vec![&assign.expression],
+        Expression::Return(expr, _)
+        | Expression::ExtractTupleField(expr, _)
+        | Expression::Constrain(expr, ..)
+        | Expression::Semi(expr) => vec![expr],
+    }
+}
+
+/// Returns true if the expression contains a break or a continue of the loop enclosing it
+fn exits_loop(expr: &Expression) -> bool {
+    match expr {
+        Expression::Break | Expression::Continue => true,
+        // A break or continue inside a nested loop only exits the nested loop
+        Expression::For(_) | Expression::While(_) => false,
+        _ => sub_expressions(expr).into_iter().any(exits_loop),
+    }
+}
+
+/// Returns the type of the value returned by the first `return` within the expression, if any
+fn return_type(expr: &Expression) -> Option<&Type> {
+    match expr {
+        Expression::Return(_, typ) => Some(typ),
+        _ => sub_expressions(expr).into_iter().find_map(return_type),
+    }
+}
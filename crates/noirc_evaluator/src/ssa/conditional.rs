@@ -502,7 +502,7 @@ impl DecisionTree {
                 }
                 stack.push(ins_id);
             }
-            Operation::Constrain(expr, loc) => {
+            Operation::Constrain(expr, loc, message) => {
                 if ctx.under_assumption(ass_value) {
                     let operation = Operation::Cond {
                         condition: ass_value,
@@ -516,7 +516,7 @@ impl DecisionTree {
                     ));
                     stack.push(cond);
                     let ins2 = ctx.get_mut_instruction(ins_id);
-                    ins2.operation = Operation::Constrain(cond, *loc);
+                    ins2.operation = Operation::Constrain(cond, *loc, message.clone());
                 }
                 stack.push(ins_id);
             }
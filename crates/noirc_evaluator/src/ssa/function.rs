@@ -1,10 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::environment::Environment;
 use crate::errors::RuntimeError;
 use acvm::acir::OPCODE;
 use acvm::FieldElement;
-use noirc_frontend::monomorphisation::ast::{self, Call, DefinitionId, FuncId, Type};
+use noirc_frontend::monomorphisation::ast::{
+    self, Call, DefinitionId, Expression, FuncId, LValue, Literal, Program, Type,
+};
+use noirc_frontend::util::vecmap;
 
 use super::conditional::{AssumptionId, DecisionTree};
 use super::node::Node;
@@ -12,6 +15,7 @@ use super::{
     block::BlockId,
     code_gen::IRGenerator,
     context::SsaContext,
+    long_integer,
     node::{self, NodeId, ObjectType},
     ssa_form,
 };
@@ -146,6 +150,10 @@ impl IRGenerator {
         let function = &mut self.program[func_id];
         let return_types = function.return_type.flatten();
         for typ in return_types {
+            if let Some(bit_size) = long_integer::long_integer_bits(&typ) {
+                func.result_types.extend(long_integer::limb_types(bit_size));
+                continue;
+            }
             func.result_types.push(match typ {
                 Type::Unit => ObjectType::NotAnObject,
                 Type::Array(_, _) => ObjectType::Pointer(crate::ssa::mem::ArrayId::dummy()),
@@ -156,8 +164,9 @@ impl IRGenerator {
         self.function_context = Some(index);
         self.context.functions.insert(func_id, func.clone());
 
+        let checked_arithmetic = self.program[func_id].checked_arithmetic;
         let function_body = self.program.take_function_body(func_id);
-        let last_value = self.codegen_expression(env, &function_body)?;
+        let last_value = self.codegen_function_body(env, &function_body, checked_arithmetic)?;
         let returned_values = last_value.to_node_ids();
 
         func.result_types.clear();
@@ -288,13 +297,110 @@ fn is_leaf(call_graph: &[Vec<u8>], i: FuncIndex) -> bool {
     true
 }
 
+/// Returns the functions of the program which are part of a cycle of the call graph,
+/// together with the functions which can call into such a cycle.
+pub fn recursive_functions(program: &Program) -> HashSet<FuncId> {
+    let callees = vecmap(&program.functions, |function| {
+        let mut callees = HashSet::new();
+        called_functions(&function.body, &mut callees);
+        callees
+    });
+
+    // The functions reachable from each function, through one or more calls
+    let reachable = vecmap(0..callees.len(), |i| {
+        let mut reachable = HashSet::new();
+        let mut queue = vec![FuncId(i as u32)];
+        while let Some(caller) = queue.pop() {
+            for callee in &callees[caller.0 as usize] {
+                if reachable.insert(*callee) {
+                    queue.push(*callee);
+                }
+            }
+        }
+        reachable
+    });
+
+    let in_cycle = |f: &FuncId| reachable[f.0 as usize].contains(f);
+    (0..callees.len())
+        .map(|i| FuncId(i as u32))
+        .filter(|f| in_cycle(f) || reachable[f.0 as usize].iter().any(in_cycle))
+        .collect()
+}
+
+/// Collects the functions called by the expression
+fn called_functions(expr: &Expression, callees: &mut HashSet<FuncId>) {
+    let mut visit = |expr: &Expression| called_functions(expr, callees);
+    match expr {
+        Expression::Ident(_) | Expression::Break | Expression::Continue => (),
+        Expression::Literal(Literal::Array(array)) => array.contents.iter().for_each(visit),
+        Expression::Literal(_) => (),
+        Expression::Block(exprs) | Expression::Tuple(exprs) => exprs.iter().for_each(visit),
+        Expression::Unary(unary) => visit(&unary.rhs),
+        Expression::Binary(binary) => {
+            visit(&binary.lhs);
+            visit(&binary.rhs);
+        }
+        Expression::Index(index) => {
+            visit(&index.collection);
+            visit(&index.index);
+        }
+        Expression::Cast(cast) => visit(&cast.lhs),
+        Expression::For(for_expr) => {
+            visit(&for_expr.start_range);
+            visit(&for_expr.end_range);
+            if let Some(max_iterations) = &for_expr.max_iterations {
+                visit(max_iterations);
+            }
+            visit(&for_expr.block);
+        }
+        Expression::While(while_expr) => {
+            visit(&while_expr.condition);
+            visit(&while_expr.max_iterations);
+            visit(&while_expr.block);
+        }
+        Expression::If(if_expr) => {
+            visit(&if_expr.condition);
+            visit(&if_expr.consequence);
+            if let Some(alternative) = &if_expr.alternative {
+                visit(alternative);
+            }
+        }
+        Expression::ExtractTupleField(expr, _) => visit(expr),
+        Expression::Call(call) => {
+            call.arguments.iter().for_each(visit);
+            callees.insert(call.func_id);
+        }
+        Expression::CallBuiltin(call) => call.arguments.iter().for_each(visit),
+        Expression::CallLowLevel(call) => call.arguments.iter().for_each(visit),
+        Expression::Let(let_expr) => visit(&let_expr.expression),
+        Expression::Constrain(expr, ..) | Expression::Semi(expr) | Expression::Return(expr, _) => {
+            visit(expr)
+        }
+        Expression::Assign(assign) => {
+            called_functions_in_lvalue(&assign.lvalue, callees);
+            visit(&assign.expression);
+        }
+    }
+}
+
+fn called_functions_in_lvalue(lvalue: &LValue, callees: &mut HashSet<FuncId>) {
+    match lvalue {
+        LValue::Ident(_) => (),
+        LValue::Index { array, index, .. } => {
+            called_functions_in_lvalue(array, callees);
+            called_functions(index, callees);
+        }
+        LValue::MemberAccess { object, .. } => called_functions_in_lvalue(object, callees),
+    }
+}
+
 fn get_new_leaf(ctx: &SsaContext, processed: &[FuncIndex]) -> (FuncIndex, FuncId) {
     for f in ctx.functions.values() {
         if !processed.contains(&(f.idx)) && is_leaf(&ctx.call_graph, f.idx) {
             return (f.idx, f.id);
         }
     }
-    unimplemented!("Recursive function call is not supported");
+    unreachable!("ice: calls to recursive functions are expanded during code generation");
 }
 
 //inline all functions of the call graph such that every inlining operates with a fully flattened function
@@ -11,7 +11,7 @@ use super::{
 use acvm::{acir::OPCODE, FieldElement};
 use noirc_frontend::util::vecmap;
 use num_bigint::BigUint;
-use num_traits::{One, Zero};
+use num_traits::{One, ToPrimitive, Zero};
 use std::collections::BTreeMap;
 use std::{collections::HashMap, ops::Neg};
 
@@ -305,10 +305,11 @@ fn block_overflow(
                         Operation::Binary(node::Binary { lhs, rhs, operator: BinaryOp::Mul });
                 }
             }
-            Operation::Binary(node::Binary { operator: BinaryOp::Shr, lhs, rhs }) => {
-                if !matches!(ins.res_type, node::ObjectType::Unsigned(_)) {
-                    todo!("Right shift is only implemented for unsigned integers");
-                }
+            //signed right shifts are arithmetic shifts, which are not a division when lhs is
+            //negative, so they are kept and handled by the ACIR generation
+            Operation::Binary(node::Binary { operator: BinaryOp::Shr, lhs, rhs })
+                if matches!(ins.res_type, node::ObjectType::Unsigned(_)) =>
+            {
                 if let Some(r_const) = ctx.get_as_constant(rhs) {
                     let r_type = ctx[rhs].get_type();
                     let rhs =
@@ -449,9 +450,9 @@ fn get_max_value(ins: &Instruction, max_map: &mut HashMap<NodeId, BigUint>) -> B
             let type_max = ins.res_type.max_size();
             BigUint::min(max_map[value_id].clone(), type_max)
         }
-        Operation::Truncate { value, max_bit_size, .. } => BigUint::min(
+        Operation::Truncate { value, bit_size, .. } => BigUint::min(
             max_map[value].clone(),
-            BigUint::from(2_u32).pow(*max_bit_size) - BigUint::from(1_u32),
+            BigUint::from(2_u32).pow(*bit_size) - BigUint::from(1_u32),
         ),
         Operation::Nop | Operation::Jne(..) | Operation::Jeq(..) | Operation::Jmp(_) => todo!(),
         Operation::Phi { root, block_args } => {
@@ -469,8 +470,7 @@ fn get_max_value(ins: &Instruction, max_map: &mut HashMap<NodeId, BigUint>) -> B
         Operation::Load { .. } => unreachable!(),
         Operation::Store { .. } => BigUint::zero(),
         Operation::Call { .. } => ins.res_type.max_size(), //n.b. functions should have been inlined
-        Operation::Return(_) => todo!(),
-        Operation::Result { .. } => {
+        Operation::Return(_) | Operation::Result { .. } => {
             unreachable!("Functions must have been inlined before checking for overflows")
         }
         Operation::Intrinsic(opcode, _) => {
@@ -509,7 +509,8 @@ fn get_binary_max_value(
 
     match &binary.operator {
         BinaryOp::Add => lhs_max + rhs_max,
-        BinaryOp::SafeAdd => todo!(),
+        //checked operations constrain their result to fit the type
+        BinaryOp::SafeAdd | BinaryOp::SafeSub { .. } | BinaryOp::SafeMul => res_type.max_size(),
         BinaryOp::Sub { .. } => {
             let r_mod = BigUint::one() << res_type.bits();
             let mut k = rhs_max / &r_mod;
@@ -519,13 +520,12 @@ fn get_binary_max_value(
             assert!(&k * &r_mod >= *rhs_max);
             lhs_max + k * r_mod
         }
-        BinaryOp::SafeSub { .. } => todo!(),
         BinaryOp::Mul => lhs_max * rhs_max,
-        BinaryOp::SafeMul => todo!(),
         BinaryOp::Udiv => lhs_max.clone(),
-        BinaryOp::Sdiv => todo!(),
+        //negating a zero quotient or remainder results in 2^bit_size
+        BinaryOp::Sdiv => BigUint::one() << res_type.bits(),
         BinaryOp::Urem => rhs_max - BigUint::one(),
-        BinaryOp::Srem => todo!(),
+        BinaryOp::Srem => BigUint::one() << res_type.bits(),
         BinaryOp::Div => todo!(),
         BinaryOp::Eq => BigUint::one(),
         BinaryOp::Ne => BigUint::one(),
@@ -544,16 +544,12 @@ fn get_binary_max_value(
                 - BigUint::one()
         }
         BinaryOp::Assign => rhs_max.clone(),
-        BinaryOp::Shl => BigUint::min(
-            BigUint::from(2_u32).pow((lhs_max.bits() + 1) as u32) - BigUint::one(),
-            res_type.max_size(),
-        ),
-        BinaryOp::Shr => {
-            if lhs_max.bits() >= 1 {
-                BigUint::from(2_u32).pow((lhs_max.bits() - 1) as u32) - BigUint::one()
-            } else {
-                BigUint::zero()
-            }
+        //the shift amount may not be known, and shifting by bit_size or more is the same as shifting by bit_size
+        BinaryOp::Shl => {
+            let max_shift = BigUint::min(rhs_max.clone(), BigUint::from(res_type.bits()));
+            lhs_max * BigUint::from(2_u32).pow(max_shift.to_u32().unwrap())
         }
+        BinaryOp::Shr if matches!(res_type, ObjectType::Signed(_)) => res_type.max_size(),
+        BinaryOp::Shr => lhs_max.clone(),
     }
 }
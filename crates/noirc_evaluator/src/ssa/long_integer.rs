@@ -0,0 +1,635 @@
+//! Unsigned integers which are too large to be handled as a single value by the SSA are
+//! represented by a `Value::Tuple` of limbs, from the least significant one to the most
+//! significant one. Each operation on long integers is lowered into operations on the limbs,
+//! where carries are computed over the field and extracted with truncate instructions.
+use super::code_gen::{IRGenerator, Value};
+use super::integer::short_integer_max_bit_size;
+use super::node::{BinaryOp, NodeId, ObjectType, Operation};
+use crate::errors::{RuntimeError, RuntimeErrorKind};
+use acvm::FieldElement;
+use noirc_errors::Location;
+use noirc_frontend::monomorphisation::ast::Type;
+use noirc_frontend::util::vecmap;
+use noirc_frontend::{BinaryOpKind, Signedness, UnaryOp};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::convert::TryFrom;
+
+/// The bit size of the limbs of long integers
+pub const LIMB_BITS: u32 = 32;
+
+/// Returns the bit size of the type if it is a long integer
+pub fn long_integer_bits(typ: &Type) -> Option<u32> {
+    match typ {
+        Type::Integer(Signedness::Unsigned, bit_size)
+            if *bit_size >= short_integer_max_bit_size() =>
+        {
+            Some(*bit_size)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the bit size of a parameter of main if it is a long integer
+pub fn abi_long_integer_bits(abi_type: &noirc_abi::AbiType) -> Option<u32> {
+    match abi_type {
+        noirc_abi::AbiType::Integer { sign: noirc_abi::Sign::Unsigned, width, .. }
+            if *width >= short_integer_max_bit_size() =>
+        {
+            Some(*width)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the types of the limbs of a long integer. Every limb has `LIMB_BITS` bits,
+/// except for the most significant one which has the remaining bits.
+pub fn limb_types(bit_size: u32) -> Vec<ObjectType> {
+    let count = (bit_size + LIMB_BITS - 1) / LIMB_BITS;
+    vecmap(0..count, |i| ObjectType::Unsigned(u32::min(LIMB_BITS, bit_size - i * LIMB_BITS)))
+}
+
+fn limbs(value: &Value) -> Vec<NodeId> {
+    match value {
+        Value::Tuple(limbs) => vecmap(limbs, Value::unwrap_id),
+        Value::Single(_) => unreachable!("Expected the limbs of a long integer"),
+    }
+}
+
+fn to_biguint(value: FieldElement) -> BigUint {
+    BigUint::from_bytes_be(&value.to_bytes())
+}
+
+fn to_field(value: &BigUint) -> FieldElement {
+    FieldElement::from_be_bytes_reduce(&value.to_bytes_be())
+}
+
+fn pow2(exponent: u32) -> FieldElement {
+    FieldElement::from(2_i128).pow(&FieldElement::from(exponent as i128))
+}
+
+/// Splits a constant into limbs of the given types, discarding the bits which do not fit
+fn const_limbs(value: &BigUint, types: &[ObjectType]) -> Vec<FieldElement> {
+    vecmap(types.iter().enumerate(), |(i, typ)| {
+        let mask = (BigUint::one() << typ.bits()) - BigUint::one();
+        to_field(&((value >> (i as u32 * LIMB_BITS)) & mask))
+    })
+}
+
+impl IRGenerator {
+    pub(super) fn long_integer_const(&mut self, value: FieldElement, bit_size: u32) -> Value {
+        let types = limb_types(bit_size);
+        let limbs = self.const_limbs(&to_biguint(value), &types);
+        Value::Tuple(vecmap(limbs, Value::Single))
+    }
+
+    pub(super) fn codegen_long_infix(
+        &mut self,
+        lhs: Value,
+        rhs: Value,
+        op: BinaryOpKind,
+        location: Location,
+    ) -> Result<Value, RuntimeError> {
+        let lhs = limbs(&lhs);
+        let types = vecmap(&lhs, |limb| self.context.get_object_type(*limb));
+
+        if let BinaryOpKind::ShiftLeft | BinaryOpKind::ShiftRight = op {
+            let shift = self.shift_amount(&rhs, location)?;
+            let bit_size = types.iter().map(ObjectType::bits).sum::<u32>();
+            let result = if shift >= bit_size as u128 {
+                self.const_limbs(&BigUint::zero(), &types)
+            } else if op == BinaryOpKind::ShiftLeft {
+                self.long_shl(&lhs, shift as u32, &types)?
+            } else {
+                self.long_shr(&lhs, shift as u32, &types)?
+            };
+            return Ok(Value::Tuple(vecmap(result, Value::Single)));
+        }
+
+        let rhs = limbs(&rhs);
+        let result = match op {
+            BinaryOpKind::Add => self.long_add(&lhs, &rhs, false, &types)?,
+            BinaryOpKind::Subtract => {
+                // a - b = a + !b + 1 (mod 2^bit_size)
+                let not_rhs = self.long_not(&rhs)?;
+                self.long_add(&lhs, &not_rhs, true, &types)?
+            }
+            BinaryOpKind::Multiply => self.long_mul(&lhs, &rhs, &types)?,
+            BinaryOpKind::And => self.long_bitwise(BinaryOp::And, &lhs, &rhs)?,
+            BinaryOpKind::Or => self.long_bitwise(BinaryOp::Or, &lhs, &rhs)?,
+            BinaryOpKind::Xor => self.long_bitwise(BinaryOp::Xor, &lhs, &rhs)?,
+            BinaryOpKind::Equal => return self.long_eq(&lhs, &rhs).map(Value::Single),
+            BinaryOpKind::NotEqual => {
+                let eq = self.long_eq(&lhs, &rhs)?;
+                return self.not_bool(eq).map(Value::Single);
+            }
+            BinaryOpKind::Less => return self.long_lt(&lhs, &rhs).map(Value::Single),
+            BinaryOpKind::Greater => return self.long_lt(&rhs, &lhs).map(Value::Single),
+            BinaryOpKind::LessEqual => {
+                let gt = self.long_lt(&rhs, &lhs)?;
+                return self.not_bool(gt).map(Value::Single);
+            }
+            BinaryOpKind::GreaterEqual => {
+                let lt = self.long_lt(&lhs, &rhs)?;
+                return self.not_bool(lt).map(Value::Single);
+            }
+            BinaryOpKind::Divide
+            | BinaryOpKind::Modulo
+            | BinaryOpKind::ShiftLeft
+            | BinaryOpKind::ShiftRight => {
+                let message = format!(
+                    "{} is not supported for integers of {} bits or more",
+                    op.as_string(),
+                    short_integer_max_bit_size()
+                );
+                return Err(RuntimeErrorKind::UnstructuredError { message }.add_location(location));
+            }
+        };
+        Ok(Value::Tuple(vecmap(result, Value::Single)))
+    }
+
+    pub(super) fn codegen_long_prefix(
+        &mut self,
+        rhs: Value,
+        op: UnaryOp,
+    ) -> Result<Value, RuntimeError> {
+        let rhs = limbs(&rhs);
+        let result = match op {
+            UnaryOp::Not => self.long_not(&rhs)?,
+            UnaryOp::Minus => {
+                // -a = !a + 1 (mod 2^bit_size)
+                let types = vecmap(&rhs, |limb| self.context.get_object_type(*limb));
+                let zero = self.const_limbs(&BigUint::zero(), &types);
+                let not_rhs = self.long_not(&rhs)?;
+                self.long_add(&zero, &not_rhs, true, &types)?
+            }
+        };
+        Ok(Value::Tuple(vecmap(result, Value::Single)))
+    }
+
+    /// Casts from or into a long integer
+    pub(super) fn codegen_long_cast(
+        &mut self,
+        value: Value,
+        typ: &Type,
+        location: Location,
+    ) -> Result<Value, RuntimeError> {
+        let result = match (value, long_integer_bits(typ)) {
+            (Value::Tuple(limbs), Some(bit_size)) => {
+                let types = limb_types(bit_size);
+                let limbs = vecmap(limbs, |limb| limb.unwrap_id());
+                Value::Tuple(vecmap(self.resize_limbs(&limbs, &types)?, Value::Single))
+            }
+            (Value::Tuple(limbs), None) => {
+                // Only the limbs which overlap with the target type are needed
+                let target = ObjectType::from(typ);
+                let mut terms = Vec::new();
+                for (i, limb) in limbs.iter().enumerate() {
+                    let shift = i as u32 * LIMB_BITS;
+                    if shift >= target.bits() {
+                        break;
+                    }
+                    let limb = self.to_field(limb.unwrap_id())?;
+                    let factor = self.field_const(pow2(shift));
+                    terms.push(self.field_binary(BinaryOp::Mul, limb, factor)?);
+                }
+                let sum = self.field_sum(&terms)?;
+                if target == ObjectType::NativeField {
+                    Value::Single(sum)
+                } else {
+                    Value::Single(self.context.new_instruction(Operation::Cast(sum), target)?)
+                }
+            }
+            (Value::Single(value), Some(bit_size)) => {
+                let types = limb_types(bit_size);
+                let limbs = match self.context.get_object_type(value) {
+                    ObjectType::NativeField => {
+                        self.long_integer_from_field(value, &types, false, location)?
+                    }
+                    ObjectType::Signed(bits) => self.sign_extend(value, bits, &types)?,
+                    source => self.decompose(value, source.bits(), &types)?,
+                };
+                Value::Tuple(vecmap(limbs, Value::Single))
+            }
+            (Value::Single(_), None) => unreachable!("Expected a cast from or into a long integer"),
+        };
+        Ok(result)
+    }
+
+    /// Decomposes a field element into the limbs of the given types.
+    /// If `range_check` is set, the field element is constrained to fit into the limbs,
+    /// otherwise it is truncated.
+    pub(super) fn long_integer_from_field(
+        &mut self,
+        value: NodeId,
+        types: &[ObjectType],
+        range_check: bool,
+        location: Location,
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let field_types = limb_types(FieldElement::max_num_bits());
+        let mut field_limbs = Vec::with_capacity(field_types.len());
+        let mut rest = value;
+        let mut rest_bits = FieldElement::max_num_bits();
+        for typ in &field_types {
+            let max_bit_size = u32::max(rest_bits, typ.bits() + 1);
+            let (limb, hi) = self.split(rest, typ.bits(), max_bit_size)?;
+            field_limbs.push(limb);
+            rest = hi;
+            rest_bits -= typ.bits();
+        }
+        let zero = self.field_const(FieldElement::zero());
+        self.constrain_eq(rest, zero, location)?;
+
+        // The limbs must be the canonical decomposition, i.e. less than the field modulus
+        let modulus = to_biguint(-FieldElement::one()) + BigUint::one();
+        let modulus = self.const_limbs(&modulus, &field_types);
+        let canonical = self.long_lt(&field_limbs, &modulus)?;
+        self.constrain(canonical, location)?;
+
+        let mut limbs = Vec::with_capacity(types.len());
+        for (i, typ) in types.iter().enumerate() {
+            let limb = match (field_limbs.get(i), field_types.get(i)) {
+                (Some(limb), Some(field_type)) if field_type.bits() > typ.bits() => {
+                    let limb = self.to_field(*limb)?;
+                    let (lo, hi) = self.split(limb, typ.bits(), field_type.bits())?;
+                    if range_check {
+                        let zero = self.field_const(FieldElement::zero());
+                        self.constrain_eq(hi, zero, location)?;
+                    }
+                    lo
+                }
+                (Some(limb), Some(_)) => self.cast(*limb, *typ)?,
+                _ => self.context.get_or_create_const(FieldElement::zero(), *typ),
+            };
+            limbs.push(limb);
+        }
+        if range_check {
+            for (limb, typ) in field_limbs.iter().zip(&field_types).skip(types.len()) {
+                let zero = self.context.get_or_create_const(FieldElement::zero(), *typ);
+                self.constrain_eq(*limb, zero, location)?;
+            }
+        }
+        Ok(limbs)
+    }
+
+    fn shift_amount(&mut self, rhs: &Value, location: Location) -> Result<u128, RuntimeError> {
+        let limbs = match rhs {
+            Value::Single(id) => vec![*id],
+            Value::Tuple(_) => limbs(rhs),
+        };
+        let mut amount = BigUint::zero();
+        for (i, limb) in limbs.iter().enumerate() {
+            match self.context.get_as_constant(*limb) {
+                Some(limb) => amount += to_biguint(limb) << (i as u32 * LIMB_BITS),
+                None => {
+                    let message = format!(
+                        "the shift amount of an integer of {} bits or more must be known at compile time",
+                        short_integer_max_bit_size()
+                    );
+                    return Err(
+                        RuntimeErrorKind::UnstructuredError { message }.add_location(location)
+                    );
+                }
+            }
+        }
+        Ok(u128::try_from(amount).unwrap_or(u128::MAX))
+    }
+
+    fn const_limbs(&mut self, value: &BigUint, types: &[ObjectType]) -> Vec<NodeId> {
+        let limbs = const_limbs(value, types);
+        vecmap(limbs.into_iter().zip(types), |(limb, typ)| {
+            self.context.get_or_create_const(limb, *typ)
+        })
+    }
+
+    fn long_add(
+        &mut self,
+        lhs: &[NodeId],
+        rhs: &[NodeId],
+        carry_in: bool,
+        types: &[ObjectType],
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let mut columns = Vec::with_capacity(types.len());
+        for (lhs, rhs) in lhs.iter().zip(rhs) {
+            columns.push(vec![self.to_field(*lhs)?, self.to_field(*rhs)?]);
+        }
+        if carry_in {
+            let one = self.field_const(FieldElement::one());
+            columns[0].push(one);
+        }
+        self.normalize(columns, types, LIMB_BITS + 2)
+    }
+
+    /// Schoolbook multiplication, where the partial products which overflow
+    /// the most significant limb are not computed.
+    fn long_mul(
+        &mut self,
+        lhs: &[NodeId],
+        rhs: &[NodeId],
+        types: &[ObjectType],
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let count = types.len();
+        let mut columns = vec![Vec::new(); count];
+        for (i, lhs) in lhs.iter().enumerate() {
+            for (j, rhs) in rhs.iter().enumerate().take(count - i) {
+                let lhs = self.to_field(*lhs)?;
+                let rhs = self.to_field(*rhs)?;
+                let product = self.field_binary(BinaryOp::Mul, lhs, rhs)?;
+                if i + j + 1 < count {
+                    let (lo, hi) = self.split(product, LIMB_BITS, 2 * LIMB_BITS)?;
+                    columns[i + j].push(self.to_field(lo)?);
+                    columns[i + j + 1].push(hi);
+                } else {
+                    columns[i + j].push(product);
+                }
+            }
+        }
+        // A column holds at most 2*count terms of LIMB_BITS bits, or count products of
+        // 2*LIMB_BITS bits for the most significant limb
+        self.normalize(columns, types, 2 * LIMB_BITS + 8)
+    }
+
+    fn long_shl(
+        &mut self,
+        lhs: &[NodeId],
+        shift: u32,
+        types: &[ObjectType],
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let (offset, shift) = ((shift / LIMB_BITS) as usize, shift % LIMB_BITS);
+        let mut columns = vec![Vec::new(); types.len()];
+        for (j, limb) in lhs.iter().enumerate() {
+            let target = j + offset;
+            if target >= types.len() {
+                break;
+            }
+            let limb = self.to_field(*limb)?;
+            if shift == 0 {
+                columns[target].push(limb);
+                continue;
+            }
+            let factor = self.field_const(pow2(shift));
+            let shifted = self.field_binary(BinaryOp::Mul, limb, factor)?;
+            if target + 1 < types.len() {
+                let (lo, hi) = self.split(shifted, LIMB_BITS, LIMB_BITS + shift)?;
+                columns[target].push(self.to_field(lo)?);
+                columns[target + 1].push(hi);
+            } else {
+                columns[target].push(shifted);
+            }
+        }
+        self.normalize(columns, types, 2 * LIMB_BITS)
+    }
+
+    fn long_shr(
+        &mut self,
+        lhs: &[NodeId],
+        shift: u32,
+        types: &[ObjectType],
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let (offset, shift) = ((shift / LIMB_BITS) as usize, shift % LIMB_BITS);
+        let mut columns = vec![Vec::new(); types.len()];
+        for (j, limb) in lhs.iter().enumerate().skip(offset) {
+            let target = j - offset;
+            let limb = self.to_field(*limb)?;
+            if shift == 0 {
+                columns[target].push(limb);
+                continue;
+            }
+            // The low bits of the limb are moved to the top of the previous limb
+            let factor = self.field_const(pow2(LIMB_BITS - shift));
+            let width = types[j].bits();
+            let lo = if shift < width {
+                let (lo, hi) = self.split(limb, shift, width)?;
+                columns[target].push(hi);
+                self.to_field(lo)?
+            } else {
+                limb
+            };
+            if target > 0 {
+                let lo = self.field_binary(BinaryOp::Mul, lo, factor)?;
+                columns[target - 1].push(lo);
+            }
+        }
+        self.normalize(columns, types, 2 * LIMB_BITS)
+    }
+
+    fn long_not(&mut self, value: &[NodeId]) -> Result<Vec<NodeId>, RuntimeError> {
+        let mut result = Vec::with_capacity(value.len());
+        for limb in value {
+            let typ = self.context.get_object_type(*limb);
+            result.push(self.context.new_instruction(Operation::Not(*limb), typ)?);
+        }
+        Ok(result)
+    }
+
+    fn long_bitwise(
+        &mut self,
+        op: BinaryOp,
+        lhs: &[NodeId],
+        rhs: &[NodeId],
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let mut result = Vec::with_capacity(lhs.len());
+        for (lhs, rhs) in lhs.iter().zip(rhs) {
+            let typ = self.context.get_object_type(*lhs);
+            let operation = Operation::binary(op.clone(), *lhs, *rhs);
+            result.push(self.context.new_instruction(operation, typ)?);
+        }
+        Ok(result)
+    }
+
+    fn long_eq(&mut self, lhs: &[NodeId], rhs: &[NodeId]) -> Result<NodeId, RuntimeError> {
+        let mut result = self.context.one();
+        for (lhs, rhs) in lhs.iter().zip(rhs) {
+            let eq = Operation::binary(BinaryOp::Eq, *lhs, *rhs);
+            let eq = self.context.new_instruction(eq, ObjectType::Boolean)?;
+            let val_false = self.context.zero();
+            let cond = Operation::Cond { condition: eq, val_true: result, val_false };
+            result = self.context.new_instruction(cond, ObjectType::Boolean)?;
+        }
+        Ok(result)
+    }
+
+    /// The comparison is decided by the most significant limbs which differ
+    fn long_lt(&mut self, lhs: &[NodeId], rhs: &[NodeId]) -> Result<NodeId, RuntimeError> {
+        let mut result = self.context.zero();
+        for (lhs, rhs) in lhs.iter().zip(rhs) {
+            let lt = Operation::binary(BinaryOp::Ult, *lhs, *rhs);
+            let lt = self.context.new_instruction(lt, ObjectType::Boolean)?;
+            let eq = Operation::binary(BinaryOp::Eq, *lhs, *rhs);
+            let eq = self.context.new_instruction(eq, ObjectType::Boolean)?;
+            let cond = Operation::Cond { condition: eq, val_true: result, val_false: lt };
+            result = self.context.new_instruction(cond, ObjectType::Boolean)?;
+        }
+        Ok(result)
+    }
+
+    /// Converts limbs into limbs of other types, truncating the value if needed
+    fn resize_limbs(
+        &mut self,
+        limbs: &[NodeId],
+        types: &[ObjectType],
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let mut result = Vec::with_capacity(types.len());
+        for (i, typ) in types.iter().enumerate() {
+            let limb = match limbs.get(i) {
+                Some(limb) => {
+                    let limb_type = self.context.get_object_type(*limb);
+                    if limb_type.bits() > typ.bits() {
+                        let limb = self.to_field(*limb)?;
+                        self.split(limb, typ.bits(), limb_type.bits())?.0
+                    } else {
+                        self.cast(*limb, *typ)?
+                    }
+                }
+                None => self.context.get_or_create_const(FieldElement::zero(), *typ),
+            };
+            result.push(limb);
+        }
+        Ok(result)
+    }
+
+    /// Decomposes an unsigned value of `bit_size` bits into limbs
+    fn decompose(
+        &mut self,
+        value: NodeId,
+        bit_size: u32,
+        types: &[ObjectType],
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let mut limbs = Vec::with_capacity(types.len());
+        let mut rest = self.to_field(value)?;
+        let mut rest_bits = bit_size;
+        for (i, typ) in types.iter().enumerate() {
+            let limb = if rest_bits == 0 {
+                self.context.get_or_create_const(FieldElement::zero(), *typ)
+            } else if i == 0 && rest_bits <= typ.bits() {
+                self.cast(value, *typ)?
+            } else {
+                let max_bit_size = u32::max(rest_bits, typ.bits() + 1);
+                let (lo, hi) = self.split(rest, typ.bits(), max_bit_size)?;
+                rest = hi;
+                lo
+            };
+            rest_bits = rest_bits.saturating_sub(typ.bits());
+            limbs.push(limb);
+        }
+        Ok(limbs)
+    }
+
+    /// Decomposes a signed value of `bit_size` bits into limbs, extending its sign bit
+    fn sign_extend(
+        &mut self,
+        value: NodeId,
+        bit_size: u32,
+        types: &[ObjectType],
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let value = self.to_field(value)?;
+        let (low, sign) = self.split(value, bit_size - 1, bit_size)?;
+        let low = self.decompose(low, bit_size - 1, types)?;
+
+        // A negative value has all of its bits above the sign bit set
+        let total_bits = types.iter().map(ObjectType::bits).sum::<u32>();
+        let extension = (BigUint::one() << total_bits) - (BigUint::one() << (bit_size - 1));
+        let extension = const_limbs(&extension, types);
+
+        let mut columns = Vec::with_capacity(types.len());
+        for (low, extension) in low.into_iter().zip(extension) {
+            let low = self.to_field(low)?;
+            let extension = self.field_const(extension);
+            let extension = self.field_binary(BinaryOp::Mul, sign, extension)?;
+            columns.push(vec![low, extension]);
+        }
+        self.normalize(columns, types, LIMB_BITS + 1)
+    }
+
+    /// Sums each column of field elements, together with the carry of the previous column,
+    /// and splits the sum into a limb of the given type and the carry of the next column.
+    /// The sums must be less than 2^max_bit_size. The carry of the last column is discarded.
+    fn normalize(
+        &mut self,
+        columns: Vec<Vec<NodeId>>,
+        types: &[ObjectType],
+        max_bit_size: u32,
+    ) -> Result<Vec<NodeId>, RuntimeError> {
+        let mut carry = self.field_const(FieldElement::zero());
+        let mut limbs = Vec::with_capacity(types.len());
+        for (mut column, typ) in columns.into_iter().zip(types) {
+            column.push(carry);
+            let sum = self.field_sum(&column)?;
+            let (limb, hi) = self.split(sum, typ.bits(), max_bit_size)?;
+            limbs.push(limb);
+            carry = hi;
+        }
+        Ok(limbs)
+    }
+
+    /// Splits a field element which is less than 2^max_bit_size into an unsigned integer
+    /// holding its `bit_size` least significant bits, and a field element holding the others.
+    fn split(
+        &mut self,
+        value: NodeId,
+        bit_size: u32,
+        max_bit_size: u32,
+    ) -> Result<(NodeId, NodeId), RuntimeError> {
+        let truncate = Operation::Truncate { value, bit_size, max_bit_size };
+        let lo = self.context.new_instruction(truncate, ObjectType::Unsigned(bit_size))?;
+        let lo_field = self.to_field(lo)?;
+        let sub = BinaryOp::Sub { max_rhs_value: BigUint::zero() };
+        let diff = self.field_binary(sub, value, lo_field)?;
+        let inverse = self.field_const(pow2(bit_size).inverse());
+        let hi = self.field_binary(BinaryOp::Mul, diff, inverse)?;
+        Ok((lo, hi))
+    }
+
+    fn cast(&mut self, value: NodeId, typ: ObjectType) -> Result<NodeId, RuntimeError> {
+        if self.context.get_object_type(value) == typ {
+            return Ok(value);
+        }
+        self.context.new_instruction(Operation::Cast(value), typ)
+    }
+
+    fn to_field(&mut self, value: NodeId) -> Result<NodeId, RuntimeError> {
+        self.cast(value, ObjectType::NativeField)
+    }
+
+    fn field_const(&mut self, value: FieldElement) -> NodeId {
+        self.context.get_or_create_const(value, ObjectType::NativeField)
+    }
+
+    fn field_binary(
+        &mut self,
+        op: BinaryOp,
+        lhs: NodeId,
+        rhs: NodeId,
+    ) -> Result<NodeId, RuntimeError> {
+        self.context.new_instruction(Operation::binary(op, lhs, rhs), ObjectType::NativeField)
+    }
+
+    fn field_sum(&mut self, terms: &[NodeId]) -> Result<NodeId, RuntimeError> {
+        let mut sum = self.field_const(FieldElement::zero());
+        for term in terms {
+            sum = self.field_binary(BinaryOp::Add, sum, *term)?;
+        }
+        Ok(sum)
+    }
+
+    fn not_bool(&mut self, value: NodeId) -> Result<NodeId, RuntimeError> {
+        self.context.new_instruction(Operation::Not(value), ObjectType::Boolean)
+    }
+
+    fn constrain(&mut self, condition: NodeId, location: Location) -> Result<(), RuntimeError> {
+        let operation = Operation::Constrain(condition, location, None);
+        self.context.new_instruction(operation, ObjectType::NotAnObject)?;
+        Ok(())
+    }
+
+    fn constrain_eq(
+        &mut self,
+        lhs: NodeId,
+        rhs: NodeId,
+        location: Location,
+    ) -> Result<(), RuntimeError> {
+        let eq = Operation::binary(BinaryOp::Eq, lhs, rhs);
+        let eq = self.context.new_instruction(eq, ObjectType::Boolean)?;
+        self.constrain(eq, location)
+    }
+}
@@ -8,6 +8,7 @@ pub mod flatten;
 pub mod function;
 pub mod inline;
 pub mod integer;
+pub mod long_integer;
 pub mod mem;
 pub mod node;
 pub mod optim;
@@ -5,7 +5,8 @@ use acvm::acir::native_types::Witness;
 use acvm::acir::OPCODE;
 use acvm::FieldElement;
 use arena;
-use noirc_errors::Location;
+use fm::FileId;
+use noirc_errors::{Location, Span};
 use noirc_frontend::monomorphisation::ast::{DefinitionId, FuncId, Type};
 use noirc_frontend::util::vecmap;
 use noirc_frontend::{BinaryOpKind, Signedness};
@@ -210,7 +211,7 @@ impl From<&Type> for ObjectType {
             Type::Integer(sign, bit_size) => {
                 assert!(
                     *bit_size < super::integer::short_integer_max_bit_size(),
-                    "long integers are not yet supported"
+                    "long integers are represented by their limbs, and long signed integers are not yet supported"
                 );
                 match sign {
                     Signedness::Signed => ObjectType::Signed(*bit_size),
@@ -268,7 +269,7 @@ impl ObjectType {
                 unreachable!()
             }
             ObjectType::NativeField => f,
-            ObjectType::Signed(_) => todo!(),
+            //signed integers are represented by their two's complement
             _ => {
                 assert!(self.bits() < 128);
                 FieldElement::from(f.to_u128() % (1_u128 << self.bits()))
@@ -423,6 +424,16 @@ impl Instruction {
                     }
                 }
             }
+            Operation::Truncate { value, bit_size, .. } => {
+                if let Some(l_const) = eval_fn(ctx, *value)?.into_const_value() {
+                    let l = BigUint::from_bytes_be(&l_const.to_bytes());
+                    let l = l % (BigUint::one() << *bit_size);
+                    return Ok(NodeEval::Const(
+                        FieldElement::from_be_bytes_reduce(&l.to_bytes_be()),
+                        self.res_type,
+                    ));
+                }
+            }
             Operation::Not(value) => {
                 if let Some(l_const) = eval_fn(ctx, *value)?.into_const_value() {
                     let l = self.res_type.field_to_type(l_const).to_u128();
@@ -430,16 +441,19 @@ impl Instruction {
                     return Ok(NodeEval::Const(FieldElement::from((!l) & max), self.res_type));
                 }
             }
-            Operation::Constrain(value, location) => {
+            Operation::Constrain(value, location, message) => {
                 if let Some(obj) = eval_fn(ctx, *value)?.into_const_value() {
                     if obj.is_one() {
                         // Delete the constrain, it is always true
                         return Ok(NodeEval::VarOrInstruction(NodeId::dummy()));
                     } else if obj.is_zero() {
-                        return Err(RuntimeErrorKind::UnstructuredError {
-                            message: "Constraint is always false".into(),
-                        }
-                        .add_location(*location));
+                        let message = match message {
+                            Some(message) => format!("Constraint is always false: {}", message),
+                            None => "Constraint is always false".into(),
+                        };
+                        return Err(
+                            RuntimeErrorKind::UnstructuredError { message }.add_location(*location)
+                        );
                     }
                 }
             }
@@ -508,7 +522,7 @@ pub enum Operation {
     }, //truncate
 
     Not(NodeId), //(!) Bitwise Not
-    Constrain(NodeId, Location),
+    Constrain(NodeId, Location, /*message:*/ Option<String>),
 
     //control flow
     Jne(NodeId, BlockId), //jump on not equal
@@ -610,39 +624,30 @@ pub struct Binary {
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum BinaryOp {
-    Add, //(+)
-    #[allow(dead_code)]
-    SafeAdd, //(+) safe addition
-    Sub {
-        max_rhs_value: BigUint,
-    }, //(-)
-    #[allow(dead_code)]
-    SafeSub {
-        max_rhs_value: BigUint,
-    }, //(-) safe subtraction
-    Mul, //(*)
-    #[allow(dead_code)]
-    SafeMul, //(*) safe multiplication
-    Udiv, //(/) unsigned division
-    Sdiv, //(/) signed division
-    #[allow(dead_code)]
-    Urem, //(%) modulo; remainder of unsigned division
-    #[allow(dead_code)]
-    Srem, //(%) remainder of signed division
-    Div, //(/) field division
-    Eq,  //(==) equal
-    Ne,  //(!=) not equal
-    Ult, //(<) unsigned less than
-    Ule, //(<=) unsigned less or equal
-    Slt, //(<) signed less than
-    Sle, //(<=) signed less or equal
-    Lt,  //(<) field less
-    Lte, //(<=) field less or equal
-    And, //(&) Bitwise And
-    Or,  //(|) Bitwise Or
-    Xor, //(^) Bitwise Xor
-    Shl, //(<<) Shift left
-    Shr, //(>>) Shift right
+    Add,                                //(+)
+    SafeAdd,                            //(+) safe addition
+    Sub { max_rhs_value: BigUint },     //(-)
+    SafeSub { max_rhs_value: BigUint }, //(-) safe subtraction
+    Mul,                                //(*)
+    SafeMul,                            //(*) safe multiplication
+    Udiv,                               //(/) unsigned division
+    Sdiv,                               //(/) signed division
+    Urem,                               //(%) modulo; remainder of unsigned division
+    Srem,                               //(%) remainder of signed division
+    Div,                                //(/) field division
+    Eq,                                 //(==) equal
+    Ne,                                 //(!=) not equal
+    Ult,                                //(<) unsigned less than
+    Ule,                                //(<=) unsigned less or equal
+    Slt,                                //(<) signed less than
+    Sle,                                //(<=) signed less or equal
+    Lt,                                 //(<) field less
+    Lte,                                //(<=) field less or equal
+    And,                                //(&) Bitwise And
+    Or,                                 //(|) Bitwise Or
+    Xor,                                //(^) Bitwise Xor
+    Shl,                                //(<<) Shift left
+    Shr,                                //(>>) Shift right
 
     Assign,
 }
@@ -675,6 +680,18 @@ impl Binary {
                     NumericType::NativeField => BinaryOp::Div,
                 }
             }
+            BinaryOpKind::Modulo => {
+                let num_type: NumericType = op_type.into();
+                match num_type {
+                    NumericType::Signed(_) => BinaryOp::Srem,
+                    NumericType::Unsigned(_) => BinaryOp::Urem,
+                    NumericType::NativeField => {
+                        unreachable!(
+                            "The remainder of Field elements is rejected during type checking"
+                        )
+                    }
+                }
+            }
             BinaryOpKind::Less => {
                 let num_type: NumericType = op_type.into();
                 match num_type {
@@ -744,7 +761,11 @@ impl Binary {
                 }
                 assert_eq!(l_type, r_type);
                 if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
-                    return Ok(wrapping(lhs, rhs, l_type, u128::add, Add::add));
+                    if matches!(self.operator, BinaryOp::Add) {
+                        return Ok(wrapping(lhs, rhs, l_type, u128::add, Add::add));
+                    } else if let Some(result) = checked(lhs, rhs, l_type, i128::checked_add) {
+                        return Ok(result);
+                    }
                 }
                 //if only one is const, we could try to do constant propagation but this will be handled by the arithmetization step anyways
                 //so it is probably not worth it.
@@ -759,7 +780,11 @@ impl Binary {
                 }
                 //constant folding
                 if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
-                    return Ok(wrapping(lhs, rhs, res_type, u128::wrapping_sub, Sub::sub));
+                    if matches!(self.operator, BinaryOp::Sub { .. }) {
+                        return Ok(wrapping(lhs, rhs, res_type, u128::wrapping_sub, Sub::sub));
+                    } else if let Some(result) = checked(lhs, rhs, res_type, i128::checked_sub) {
+                        return Ok(result);
+                    }
                 }
             }
             BinaryOp::Mul | BinaryOp::SafeMul => {
@@ -771,7 +796,11 @@ impl Binary {
                 } else if r_is_zero || l_is_one {
                     return Ok(r_eval);
                 } else if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
-                    return Ok(wrapping(lhs, rhs, res_type, u128::mul, Mul::mul));
+                    if matches!(self.operator, BinaryOp::Mul) {
+                        return Ok(wrapping(lhs, rhs, res_type, u128::mul, Mul::mul));
+                    } else if let Some(result) = checked(lhs, rhs, res_type, i128::checked_mul) {
+                        return Ok(result);
+                    }
                 }
                 //if only one is const, we could try to do constant propagation but this will be handled by the arithmetization step anyways
                 //so it is probably not worth it.
@@ -803,24 +832,43 @@ impl Binary {
             }
             BinaryOp::Sdiv => {
                 if r_is_zero {
-                    todo!("Panic - division by zero");
+                    return Err(division_by_zero());
                 } else if l_is_zero {
                     return Ok(l_eval); //TODO should we ensure rhs != 0 ???
                 }
-                //constant folding...TODO
-                else if lhs.is_some() && rhs.is_some() {
-                    todo!("Constant folding for division");
+                //constant folding, the quotient is rounded towards zero
+                else if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    let bit_size = res_type.bits();
+                    let lhs = to_signed(res_type.field_to_type(lhs).to_u128(), bit_size);
+                    let rhs = to_signed(res_type.field_to_type(rhs).to_u128(), bit_size);
+                    return Ok(NodeEval::from_u128(from_signed(lhs / rhs, bit_size), res_type));
                 }
             }
-            BinaryOp::Urem | BinaryOp::Srem => {
+            BinaryOp::Urem => {
                 if r_is_zero {
                     todo!("Panic - division by zero");
                 } else if l_is_zero {
-                    return Ok(l_eval); //TODO what is the correct result?
+                    return Ok(l_eval);
                 }
-                //constant folding - TODO
-                else if lhs.is_some() && rhs.is_some() {
-                    todo!("divide lhs/rhs but take sign into account");
+                //constant folding
+                else if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    let lhs = res_type.field_to_type(lhs).to_u128();
+                    let rhs = res_type.field_to_type(rhs).to_u128();
+                    return Ok(NodeEval::from_u128(lhs % rhs, res_type));
+                }
+            }
+            BinaryOp::Srem => {
+                if r_is_zero {
+                    return Err(division_by_zero());
+                } else if l_is_zero {
+                    return Ok(l_eval);
+                }
+                //constant folding, the remainder has the sign of lhs
+                else if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    let bit_size = res_type.bits();
+                    let lhs = to_signed(res_type.field_to_type(lhs).to_u128(), bit_size);
+                    let rhs = to_signed(res_type.field_to_type(rhs).to_u128(), bit_size);
+                    return Ok(NodeEval::from_u128(from_signed(lhs % rhs, bit_size), res_type));
                 }
             }
             BinaryOp::Ult => {
@@ -938,6 +986,15 @@ impl Binary {
                     return Ok(l_eval);
                 }
                 if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    if let ObjectType::Signed(bit_size) = res_type {
+                        //arithmetic shift, the vacated bits are filled with the sign bit
+                        let lhs = to_signed(res_type.field_to_type(lhs).to_u128(), bit_size);
+                        let shift = rhs.to_u128().min((bit_size - 1) as u128);
+                        return Ok(NodeEval::from_u128(
+                            from_signed(lhs >> shift, bit_size),
+                            res_type,
+                        ));
+                    }
                     return Ok(wrapping(lhs, rhs, res_type, u128::shr, field_op_not_allowed));
                 }
             }
@@ -949,11 +1006,11 @@ impl Binary {
     fn truncate_required(&self) -> bool {
         match &self.operator {
             BinaryOp::Add => false,
-            BinaryOp::SafeAdd => false,
+            BinaryOp::SafeAdd => true,
             BinaryOp::Sub { .. } => false,
-            BinaryOp::SafeSub { .. } => false,
+            BinaryOp::SafeSub { .. } => true,
             BinaryOp::Mul => false,
-            BinaryOp::SafeMul => false,
+            BinaryOp::SafeMul => true,
             BinaryOp::Udiv => true,
             BinaryOp::Sdiv => true,
             BinaryOp::Urem => true,
@@ -1028,6 +1085,54 @@ fn wrapping(
     }
 }
 
+//Evaluates a checked operation on constants, when its result fits in res_type
+//An overflowing operation is not evaluated, so that it is left to the circuit, which cannot be satisfied
+fn checked(
+    lhs: FieldElement,
+    rhs: FieldElement,
+    res_type: ObjectType,
+    i128_op: impl FnOnce(i128, i128) -> Option<i128>,
+) -> Option<NodeEval> {
+    let bit_size = res_type.bits();
+    if bit_size >= 127 {
+        return None;
+    }
+    let lhs = lhs.to_u128();
+    let rhs = rhs.to_u128();
+    let (lhs, rhs, min, max) = match res_type {
+        ObjectType::Signed(_) => {
+            let half = 1_i128 << (bit_size - 1);
+            (to_signed(lhs, bit_size), to_signed(rhs, bit_size), -half, half - 1)
+        }
+        _ => (lhs as i128, rhs as i128, 0, (1_i128 << bit_size) - 1),
+    };
+    let x = i128_op(lhs, rhs).filter(|x| (min..=max).contains(x))?;
+    Some(NodeEval::from_u128(from_signed(x, bit_size), res_type))
+}
+
+//Binary operations do not keep the location of the expression they come from,
+//so the error is reported without a span
+fn division_by_zero() -> RuntimeError {
+    let location = Location::new(Span::default(), FileId::default());
+    RuntimeErrorKind::Spanless("division by zero".to_owned()).add_location(location)
+}
+
+//Interprets x, the two's complement representation of an integer of bit_size bits, as a signed value
+fn to_signed(x: u128, bit_size: u32) -> i128 {
+    assert!(bit_size < 127);
+    if x < 1_u128 << (bit_size - 1) {
+        x as i128
+    } else {
+        x as i128 - (1_i128 << bit_size)
+    }
+}
+
+//Returns the two's complement representation of x as an integer of bit_size bits
+fn from_signed(x: i128, bit_size: u32) -> u128 {
+    assert!(bit_size < 127);
+    x.rem_euclid(1_i128 << bit_size) as u128
+}
+
 fn field_op_not_allowed(_lhs: FieldElement, _rhs: FieldElement) -> FieldElement {
     unreachable!("operation not allowed for FieldElement");
 }
@@ -1057,7 +1162,7 @@ impl Operation {
                 Truncate { value: f(*value), bit_size: *bit_size, max_bit_size: *max_bit_size }
             }
             Not(id) => Not(f(*id)),
-            Constrain(id, loc) => Constrain(f(*id), *loc),
+            Constrain(id, loc, message) => Constrain(f(*id), *loc, message.clone()),
             Jne(id, block) => Jne(f(*id), *block),
             Jeq(id, block) => Jeq(f(*id), *block),
             Jmp(block) => Jmp(*block),
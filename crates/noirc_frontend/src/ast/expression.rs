@@ -20,9 +20,15 @@ pub enum ExpressionKind {
     Cast(Box<CastExpression>),
     Infix(Box<InfixExpression>),
     For(Box<ForExpression>),
+    While(Box<WhileExpression>),
+    Break,
+    Continue,
+    Return(Box<Expression>),
     If(Box<IfExpression>),
+    Match(Box<MatchExpression>),
     Path(Path),
     Tuple(Vec<Expression>),
+    Lambda(Box<Lambda>),
     Error,
 }
 
@@ -176,14 +182,41 @@ impl Expression {
     }
 }
 
+/// A `for` loop over a range. A range which is not known at compile-time
+/// requires the loop to be unrolled up to `max_iterations` times.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ForExpression {
     pub identifier: Ident,
     pub start_range: Expression,
     pub end_range: Expression,
+    pub max_iterations: Option<Expression>,
     pub block: Expression,
 }
 
+/// A `while` loop, which is unrolled up to `max_iterations` times
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WhileExpression {
+    pub condition: Expression,
+    pub max_iterations: Expression,
+    pub block: Expression,
+}
+
+/// A `match` over a value, choosing the first arm whose pattern matches it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MatchExpression {
+    pub expression: Expression,
+    pub arms: Vec<(Pattern, Expression)>,
+}
+
+/// A closure such as `|x: Field, y| x + y`. Any parameter or return type which was
+/// not given is `UnresolvedType::Unspecified` and is inferred during type checking.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Lambda {
+    pub parameters: Vec<(Pattern, UnresolvedType)>,
+    pub return_type: UnresolvedType,
+    pub body: Expression,
+}
+
 pub type BinaryOp = Spanned<BinaryOpKind>;
 
 #[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Copy, Clone)]
@@ -192,6 +225,7 @@ pub enum BinaryOpKind {
     Subtract,
     Multiply,
     Divide,
+    Modulo,
     Equal,
     NotEqual,
     Less,
@@ -227,6 +261,7 @@ impl BinaryOpKind {
             BinaryOpKind::Subtract => "-",
             BinaryOpKind::Multiply => "*",
             BinaryOpKind::Divide => "/",
+            BinaryOpKind::Modulo => "%",
             BinaryOpKind::Equal => "==",
             BinaryOpKind::NotEqual => "!=",
             BinaryOpKind::Less => "<",
@@ -247,6 +282,7 @@ impl BinaryOpKind {
             BinaryOpKind::Subtract => Token::Minus,
             BinaryOpKind::Multiply => Token::Star,
             BinaryOpKind::Divide => Token::Slash,
+            BinaryOpKind::Modulo => Token::Percent,
             BinaryOpKind::Equal => Token::Equal,
             BinaryOpKind::NotEqual => Token::NotEqual,
             BinaryOpKind::Less => Token::Less,
@@ -274,6 +310,7 @@ impl From<&Token> for Option<BinaryOpKind> {
             Token::Minus => BinaryOpKind::Subtract,
             Token::Star => BinaryOpKind::Multiply,
             Token::Slash => BinaryOpKind::Divide,
+            Token::Percent => BinaryOpKind::Modulo,
             Token::Equal => BinaryOpKind::Equal,
             Token::NotEqual => BinaryOpKind::NotEqual,
             Token::Less => BinaryOpKind::Less,
@@ -343,6 +380,8 @@ pub struct FunctionDefinition {
     pub name: Ident,
     pub attribute: Option<Attribute>, // XXX: Currently we only have one attribute defined. If more attributes are needed per function, we can make this a vector and make attribute definition more expressive
     pub generics: Vec<Ident>,
+    /// Each generic with a bound, paired with the trait it must implement
+    pub trait_bounds: Vec<(Ident, Path)>,
     pub parameters: Vec<(Pattern, UnresolvedType, noirc_abi::AbiFEType)>,
     pub body: BlockExpression,
     pub span: Span,
@@ -424,7 +463,12 @@ impl Display for ExpressionKind {
             Cast(cast) => cast.fmt(f),
             Infix(infix) => infix.fmt(f),
             For(for_loop) => for_loop.fmt(f),
+            While(while_loop) => while_loop.fmt(f),
+            Break => write!(f, "break"),
+            Continue => write!(f, "continue"),
+            Return(value) => write!(f, "return {}", value),
             If(if_expr) => if_expr.fmt(f),
+            Match(match_expr) => match_expr.fmt(f),
             Path(path) => path.fmt(f),
             Constructor(constructor) => constructor.fmt(f),
             MemberAccess(access) => access.fmt(f),
@@ -432,6 +476,7 @@ impl Display for ExpressionKind {
                 let elements = vecmap(elements, ToString::to_string);
                 write!(f, "({})", elements.join(", "))
             }
+            Lambda(lambda) => lambda.fmt(f),
             Error => write!(f, "Error"),
         }
     }
@@ -536,6 +581,7 @@ impl Display for BinaryOpKind {
             BinaryOpKind::Subtract => write!(f, "-"),
             BinaryOpKind::Multiply => write!(f, "*"),
             BinaryOpKind::Divide => write!(f, "/"),
+            BinaryOpKind::Modulo => write!(f, "%"),
             BinaryOpKind::Equal => write!(f, "=="),
             BinaryOpKind::NotEqual => write!(f, "!="),
             BinaryOpKind::Less => write!(f, "<"),
@@ -553,11 +599,17 @@ impl Display for BinaryOpKind {
 
 impl Display for ForExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "for {} in {} .. {} {}",
-            self.identifier, self.start_range, self.end_range, self.block
-        )
+        write!(f, "for {} in {} .. {}", self.identifier, self.start_range, self.end_range)?;
+        if let Some(max_iterations) = &self.max_iterations {
+            write!(f, " max {}", max_iterations)?;
+        }
+        write!(f, " {}", self.block)
+    }
+}
+
+impl Display for WhileExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "while {} max {} {}", self.condition, self.max_iterations, self.block)
     }
 }
 
@@ -571,6 +623,30 @@ impl Display for IfExpression {
     }
 }
 
+impl Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "match {} {{", self.expression)?;
+        for (pattern, branch) in &self.arms {
+            writeln!(f, "    {} => {},", pattern, branch)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Display for Lambda {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parameters = vecmap(&self.parameters, |(pattern, typ)| match typ {
+            UnresolvedType::Unspecified => pattern.to_string(),
+            typ => format!("{}: {}", pattern, typ),
+        });
+
+        match &self.return_type {
+            UnresolvedType::Unspecified => write!(f, "|{}| {}", parameters.join(", "), self.body),
+            ret => write!(f, "|{}| -> {} {}", parameters.join(", "), ret, self.body),
+        }
+    }
+}
+
 impl Display for FunctionDefinition {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(attribute) = &self.attribute {
@@ -76,7 +76,10 @@ impl From<FunctionDefinition> for NoirFunction {
         let kind = match fd.attribute {
             Some(Attribute::Builtin(_)) => FunctionKind::Builtin,
             Some(Attribute::Foreign(_)) => FunctionKind::LowLevel,
-            None => FunctionKind::Normal,
+            Some(Attribute::Test)
+            | Some(Attribute::Checked)
+            | Some(Attribute::Unchecked)
+            | None => FunctionKind::Normal,
         };
 
         NoirFunction { def: fd, kind }
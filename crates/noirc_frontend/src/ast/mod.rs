@@ -12,21 +12,26 @@ use noirc_errors::Span;
 pub use statement::*;
 pub use structure::*;
 
-use crate::{token::IntType, util::vecmap, IsConst};
+use crate::{token::IntType, util::vecmap, ArrayLengthOperator, IsConst};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum UnresolvedArraySize {
     Variable,
     Fixed(u64),
     FixedVariable(Ident),
+    /// Arithmetic over array sizes, such as the `N + 1` in `[Field; N + 1]`
+    Binary(Box<UnresolvedArraySize>, ArrayLengthOperator, Box<UnresolvedArraySize>, Span),
 }
 
 impl std::fmt::Display for UnresolvedArraySize {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             UnresolvedArraySize::Variable => write!(f, "[]"),
-            UnresolvedArraySize::Fixed(size) => write!(f, "[{}]", size),
-            UnresolvedArraySize::FixedVariable(ident) => write!(f, "[{}]", ident),
+            UnresolvedArraySize::Fixed(size) => write!(f, "{}", size),
+            UnresolvedArraySize::FixedVariable(ident) => write!(f, "{}", ident),
+            UnresolvedArraySize::Binary(lhs, operator, rhs, _) => {
+                write!(f, "({} {} {})", lhs, operator, rhs)
+            }
         }
     }
 }
@@ -40,6 +45,7 @@ pub enum UnresolvedType {
     Array(UnresolvedArraySize, Box<UnresolvedType>), // [4]Witness = Array(4, Witness)
     Integer(IsConst, Signedness, u32),               // u32 = Integer(unsigned, 32)
     Bool(IsConst),
+    String(UnresolvedArraySize), // str<4> = String(4)
     Unit,
 
     /// A Named UnresolvedType can be a struct type or a type variable
@@ -48,6 +54,9 @@ pub enum UnresolvedType {
     // Note: Tuples have no FieldElementType, instead each of their elements may have one.
     Tuple(Vec<UnresolvedType>),
 
+    /// fn(Field, u8) -> bool = Function([Field, u8], bool)
+    Function(Vec<UnresolvedType>, Box<UnresolvedType>),
+
     Unspecified, // This is for when the user declares a variable without specifying it's type
     Error,
 }
@@ -83,7 +92,15 @@ impl std::fmt::Display for UnresolvedType {
                 let elements = vecmap(elements, ToString::to_string);
                 write!(f, "({})", elements.join(", "))
             }
+            Function(args, ret) => {
+                let args = vecmap(args, ToString::to_string);
+                write!(f, "fn({}) -> {}", args.join(", "), ret)
+            }
             Bool(is_const) => write!(f, "{}bool", is_const),
+            String(len) => match len {
+                UnresolvedArraySize::Variable => write!(f, "str"),
+                len => write!(f, "str<{}>", len),
+            },
             Unit => write!(f, "()"),
             Error => write!(f, "error"),
             Unspecified => write!(f, "unspecified"),
@@ -4,7 +4,7 @@ use crate::lexer::token::SpannedToken;
 use crate::parser::ParserError;
 use crate::token::Token;
 use crate::util::vecmap;
-use crate::{Expression, ExpressionKind, UnresolvedType};
+use crate::{Expression, ExpressionKind, MemberAccessExpression, UnresolvedType};
 use noirc_errors::{Span, Spanned};
 
 /// This is used when an identifier fails to parse in the parser.
@@ -173,7 +173,9 @@ impl Statement {
                     // Semicolons are optional for these expressions
                     (ExpressionKind::Block(_), semi, _)
                     | (ExpressionKind::For(_), semi, _)
-                    | (ExpressionKind::If(_), semi, _) => {
+                    | (ExpressionKind::While(_), semi, _)
+                    | (ExpressionKind::If(_), semi, _)
+                    | (ExpressionKind::Match(_), semi, _) => {
                         if semi.is_some() {
                             Statement::Semi(expr)
                         } else {
@@ -203,6 +205,8 @@ impl Statement {
 pub struct ImportStatement {
     pub path: Path,
     pub alias: Option<Ident>,
+    /// True for `use path::*`, which imports every item within the module `path` refers to
+    pub is_glob: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
@@ -311,8 +315,61 @@ pub enum LValue {
     Index { array: Box<LValue>, index: Expression },
 }
 
+impl LValue {
+    /// Binds each index of this lvalue to a fresh variable declared in `statements`,
+    /// unless it is already a variable or a literal. This is used so that the indices
+    /// of a compound assignment are only evaluated once.
+    pub fn bind_indices(self, statements: &mut Vec<Statement>) -> LValue {
+        match self {
+            LValue::Ident(_) => self,
+            LValue::MemberAccess { object, field_name } => {
+                let object = Box::new(object.bind_indices(statements));
+                LValue::MemberAccess { object, field_name }
+            }
+            LValue::Index { array, index } => {
+                let array = Box::new(array.bind_indices(statements));
+                let index = match index.kind {
+                    ExpressionKind::Ident(_) | ExpressionKind::Literal(_) => index,
+                    _ => {
+                        let name = format!("$index{}", statements.len());
+                        let pattern =
+                            (Pattern::Identifier(name.clone().into()), UnresolvedType::Unspecified);
+                        let span = index.span;
+                        statements.push(Statement::new_let((pattern, index)));
+                        Expression::new(ExpressionKind::Ident(name), span)
+                    }
+                };
+                LValue::Index { array, index }
+            }
+        }
+    }
+
+    /// Returns the expression reading the current value of this lvalue.
+    /// This is used to desugar compound assignments such as `a.b[i] += 1`
+    /// into `a.b[i] = a.b[i] + 1`.
+    pub fn as_expression(&self) -> Expression {
+        match self {
+            LValue::Ident(ident) => Expression::from(ident.clone()),
+            LValue::MemberAccess { object, field_name } => {
+                let lhs = object.as_expression();
+                let span = lhs.span.merge(field_name.span());
+                let kind = ExpressionKind::MemberAccess(Box::new(MemberAccessExpression {
+                    lhs,
+                    rhs: field_name.clone(),
+                }));
+                Expression::new(kind, span)
+            }
+            LValue::Index { array, index } => {
+                let collection = array.as_expression();
+                let span = collection.span.merge(index.span);
+                Expression::index(collection, index.clone(), span)
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct ConstrainStatement(pub Expression);
+pub struct ConstrainStatement(pub Expression, pub Option<String>);
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Pattern {
@@ -320,6 +377,9 @@ pub enum Pattern {
     Mutable(Box<Pattern>, Span),
     Tuple(Vec<Pattern>, Span),
     Struct(Path, Vec<(Ident, Pattern)>, Span),
+    /// An enum variant along with patterns for each of its payload fields.
+    /// These are refutable and so may only appear as the pattern of a `match` arm.
+    Variant(Path, Vec<Pattern>, Span),
 }
 
 impl Pattern {
@@ -358,7 +418,11 @@ impl Display for LetStatement {
 
 impl Display for ConstrainStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "constrain {}", self.0)
+        write!(f, "constrain {}", self.0)?;
+        if let Some(message) = &self.1 {
+            write!(f, ", {:?}", message)?;
+        }
+        Ok(())
     }
 }
 
@@ -398,6 +462,9 @@ impl Display for PathKind {
 impl Display for ImportStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "use {}", self.path)?;
+        if self.is_glob {
+            write!(f, "::*")?;
+        }
         if let Some(alias) = &self.alias {
             write!(f, " as {}", alias)?;
         }
@@ -418,6 +485,11 @@ impl Display for Pattern {
                 let fields = vecmap(fields, |(name, pattern)| format!("{}: {}", name, pattern));
                 write!(f, "{} {{ {} }}", typename, fields.join(", "))
             }
+            Pattern::Variant(variant, fields, _) if fields.is_empty() => variant.fmt(f),
+            Pattern::Variant(variant, fields, _) => {
+                let fields = vecmap(fields, ToString::to_string);
+                write!(f, "{}({})", variant, fields.join(", "))
+            }
         }
     }
 }
@@ -1,6 +1,10 @@
 use std::fmt::Display;
 
-use crate::{Ident, NoirFunction, Path, UnresolvedType};
+use crate::{
+    util::vecmap, BlockExpression, FunctionDefinition, Ident, NoirFunction, Path, Pattern,
+    UnresolvedType,
+};
+use noirc_abi::AbiFEType;
 use noirc_errors::Span;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -22,12 +26,74 @@ impl NoirStruct {
     }
 }
 
+/// An enum declaration. Each variant carries zero or more unnamed payload fields.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoirEnum {
+    pub name: Ident,
+    pub generics: Vec<Ident>,
+    pub variants: Vec<(Ident, Vec<UnresolvedType>)>,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug)]
 pub struct NoirImpl {
     pub type_path: Path,
     pub methods: Vec<NoirFunction>,
 }
 
+/// A trait declaration, listing the signatures of the methods each impl must define
+#[derive(Clone, Debug)]
+pub struct NoirTrait {
+    pub name: Ident,
+    pub methods: Vec<TraitMethod>,
+    pub span: Span,
+}
+
+/// A method signature within a trait. Trait methods have no body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraitMethod {
+    pub name: Ident,
+    pub parameters: Vec<(Pattern, UnresolvedType, AbiFEType)>,
+    pub return_type: UnresolvedType,
+    pub span: Span,
+}
+
+impl TraitMethod {
+    /// Converts this signature into a function with an empty body
+    /// so that it may be resolved like any other function.
+    pub fn into_function(self) -> NoirFunction {
+        NoirFunction::normal(FunctionDefinition {
+            name: self.name,
+            attribute: None,
+            generics: Vec::new(),
+            trait_bounds: Vec::new(),
+            parameters: self.parameters,
+            body: BlockExpression(Vec::new()),
+            span: self.span,
+            return_type: self.return_type,
+            return_visibility: AbiFEType::Private,
+        })
+    }
+}
+
+/// An `impl Trait for Type` block
+#[derive(Clone, Debug)]
+pub struct NoirTraitImpl {
+    pub trait_name: Path,
+    pub object_type: UnresolvedType,
+    pub object_type_span: Span,
+    pub methods: Vec<NoirFunction>,
+}
+
+/// A type alias such as `type Point = (Field, Field);`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoirTypeAlias {
+    pub name: Ident,
+    pub generics: Vec<Ident>,
+    pub typ: UnresolvedType,
+    pub span: Span,
+}
+
 impl Display for NoirStruct {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "struct {} {{", self.name)?;
@@ -40,6 +106,23 @@ impl Display for NoirStruct {
     }
 }
 
+impl Display for NoirEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "enum {} {{", self.name)?;
+
+        for (name, fields) in self.variants.iter() {
+            if fields.is_empty() {
+                writeln!(f, "    {},", name)?;
+            } else {
+                let fields = vecmap(fields, ToString::to_string);
+                writeln!(f, "    {}({}),", name, fields.join(", "))?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
 impl Display for NoirImpl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "impl {} {{", self.type_path)?;
@@ -54,3 +137,51 @@ impl Display for NoirImpl {
         write!(f, "}}")
     }
 }
+
+impl Display for NoirTrait {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "trait {} {{", self.name)?;
+
+        for method in self.methods.iter() {
+            writeln!(f, "    {}", method)?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl Display for TraitMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parameters = vecmap(&self.parameters, |(name, r#type, visibility)| {
+            format!("{}: {} {}", name, visibility, r#type)
+        });
+
+        write!(f, "fn {}({}) -> {};", self.name, parameters.join(", "), self.return_type)
+    }
+}
+
+impl Display for NoirTraitImpl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "impl {} for {} {{", self.trait_name, self.object_type)?;
+
+        for method in self.methods.iter() {
+            let method = method.to_string();
+            for line in method.lines() {
+                writeln!(f, "    {}", line)?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl Display for NoirTypeAlias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.generics.is_empty() {
+            write!(f, "type {} = {};", self.name, self.typ)
+        } else {
+            let generics = vecmap(&self.generics, ToString::to_string);
+            write!(f, "type {}<{}> = {};", self.name, generics.join(", "), self.typ)
+        }
+    }
+}
@@ -1,24 +1,27 @@
 use super::dc_mod::collect_defs;
 use super::errors::DefCollectorErrorKind;
 use crate::graph::CrateId;
-use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleId};
+use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId};
 use crate::hir::resolution::errors::ResolverError;
 use crate::hir::resolution::resolver::Resolver;
 use crate::hir::resolution::{
-    import::{resolve_imports, ImportDirective},
-    path_resolver::StandardPathResolver,
+    import::{resolve_imports, ImportDirective, ResolvedImport},
+    path_resolver::{PathResolver, StandardPathResolver},
 };
 use crate::hir::type_check::type_check;
-use crate::hir::type_check::type_check_func;
+use crate::hir::type_check::{check_trait_impl_method, type_check_func};
 use crate::hir::Context;
-use crate::node_interner::{FuncId, NodeInterner, StmtId, StructId};
+use crate::hir_def::traits::{Trait, TraitImpl};
+use crate::node_interner::{EnumId, FuncId, NodeInterner, StmtId, StructId, TraitId, TypeAliasId};
 use crate::util::vecmap;
 use crate::{
-    Generics, Ident, LetStatement, NoirFunction, NoirStruct, ParsedModule, Path, Statement, Type,
+    Generics, Ident, LetStatement, NoirEnum, NoirFunction, NoirStruct, NoirTrait, NoirTypeAlias,
+    ParsedModule, Path, Shared, Statement, Type, TypeBinding, UnresolvedType,
 };
 use fm::FileId;
 use noirc_errors::CollectedErrors;
 use noirc_errors::DiagnosableError;
+use noirc_errors::Span;
 use std::collections::{BTreeMap, HashMap};
 
 /// Stores all of the unresolved functions in a particular file/mod
@@ -39,6 +42,35 @@ pub struct UnresolvedStruct {
     pub struct_def: NoirStruct,
 }
 
+pub struct UnresolvedEnum {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub enum_def: NoirEnum,
+}
+
+pub struct UnresolvedTypeAlias {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub type_alias_def: NoirTypeAlias,
+}
+
+pub struct UnresolvedTrait {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub trait_def: NoirTrait,
+    /// The FuncIds of each method, in the order they were declared in the trait
+    pub method_ids: Vec<FuncId>,
+}
+
+pub struct UnresolvedTraitImpl {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub trait_path: Path,
+    pub object_type: UnresolvedType,
+    pub object_type_span: Span,
+    pub methods: UnresolvedFunctions,
+}
+
 #[derive(Clone)]
 pub struct UnresolvedGlobalConst {
     pub file_id: FileId,
@@ -53,6 +85,10 @@ pub struct DefCollector {
     pub(crate) collected_imports: Vec<ImportDirective>,
     pub(crate) collected_functions: Vec<UnresolvedFunctions>,
     pub(crate) collected_types: HashMap<StructId, UnresolvedStruct>,
+    pub(crate) collected_enums: HashMap<EnumId, UnresolvedEnum>,
+    pub(crate) collected_type_aliases: BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
+    pub(crate) collected_traits: HashMap<TraitId, UnresolvedTrait>,
+    pub(crate) collected_trait_impls: Vec<UnresolvedTraitImpl>,
     pub(crate) collected_consts: Vec<UnresolvedGlobalConst>,
     /// collected impls maps the type name and the module id in which
     /// the impl is defined to the functions contained in that impl
@@ -66,7 +102,11 @@ impl DefCollector {
             collected_imports: vec![],
             collected_functions: vec![],
             collected_types: HashMap::new(),
+            collected_enums: HashMap::new(),
+            collected_type_aliases: BTreeMap::new(),
             collected_impls: HashMap::new(),
+            collected_traits: HashMap::new(),
+            collected_trait_impls: vec![],
             collected_consts: vec![],
         }
     }
@@ -129,8 +169,12 @@ impl DefCollector {
             errors.push(err);
         }
 
-        // Populate module namespaces according to the imports used
+        // Populate module namespaces according to the imports used. Glob imports are added
+        // last since they are shadowed by any other item or import of the same name.
         let current_def_map = context.def_maps.get_mut(&crate_id).unwrap();
+        let (glob_imports, resolved): (Vec<_>, Vec<_>) =
+            resolved.into_iter().partition(|import| import.glob_span.is_some());
+
         for resolved_import in resolved {
             let name = resolved_import.name;
             for ns in resolved_import.resolved_namespace.iter_defs() {
@@ -149,7 +193,27 @@ impl DefCollector {
             }
         }
 
+        add_glob_imports(current_def_map, glob_imports, errors);
+
+        // Structs, enums and type aliases may all refer to each other, so every struct and
+        // enum must be known to the interner before any of them are resolved. This lets structs
+        // refer to each other regardless of declaration order without resolve_struct_fields
+        // nondeterministically unwrapping a value that isn't in the HashMap.
+        for (type_id, typ) in &def_collector.collected_types {
+            context.def_interner.push_empty_struct(*type_id, typ);
+        }
+
+        for (enum_id, typ) in &def_collector.collected_enums {
+            context.def_interner.push_empty_enum(*enum_id, typ);
+        }
+
+        resolve_type_aliases(context, def_collector.collected_type_aliases, crate_id, errors);
         resolve_structs(context, def_collector.collected_types, crate_id, errors);
+        resolve_enums(context, def_collector.collected_enums, crate_id, errors);
+
+        // Trait methods must be known before any function is resolved, since
+        // functions may call them or place them as bounds on their generics
+        resolve_traits(context, def_collector.collected_traits, crate_id, errors);
 
         // We must first resolve and intern the global consts before we can resolve any stmts inside each function.
         // Each function uses its own resolver with a newly created ScopeForest, and must be resolved again to be within a function's scope
@@ -180,10 +244,19 @@ impl DefCollector {
             errors,
         );
 
+        let file_trait_impl_ids = resolve_trait_impls(
+            &mut context.def_interner,
+            crate_id,
+            &context.def_maps,
+            def_collector.collected_trait_impls,
+            errors,
+        );
+
         type_check_global_consts(&mut context.def_interner, file_const_ids, errors);
         // Type check all of the functions in the crate
         type_check_functions(&mut context.def_interner, file_func_ids, errors);
         type_check_functions(&mut context.def_interner, file_method_ids, errors);
+        type_check_functions(&mut context.def_interner, file_trait_impl_ids, errors);
     }
 }
 
@@ -291,22 +364,162 @@ fn type_check_global_consts(
     }
 }
 
+/// Add each item brought into scope by a glob import, unless an item of the same name
+/// is already in scope. Two glob imports bringing different items of the same name
+/// into one module are ambiguous, even if that name is never used.
+fn add_glob_imports(
+    def_map: &mut CrateDefMap,
+    glob_imports: Vec<ResolvedImport>,
+    errors: &mut Vec<CollectedErrors>,
+) {
+    // The item and the glob it was imported by for each name glob imported into
+    // a module, keyed by whether the item is in the type namespace or the value one
+    let mut glob_items: HashMap<(LocalModuleId, Ident, bool), (ModuleDefId, Span)> = HashMap::new();
+
+    for import in glob_imports {
+        let glob_span = import.glob_span.expect("ice: expected a glob import");
+        let is_type = import.resolved_namespace.types.is_some();
+        let key = (import.module_scope, import.name.clone(), is_type);
+
+        for def in import.resolved_namespace.iter_defs() {
+            if let Some((first_def, first_glob)) = glob_items.get(&key) {
+                if *first_def != def {
+                    let err = DefCollectorErrorKind::AmbiguousGlobImport {
+                        name: import.name.clone(),
+                        first_glob: *first_glob,
+                        second_glob: glob_span,
+                    };
+                    errors.push(CollectedErrors {
+                        file_id: def_map.module_file_id(import.module_scope),
+                        errors: vec![err.to_diagnostic()],
+                    });
+                }
+                continue;
+            }
+
+            // Any other item already in scope under this name shadows the glob import
+            let scope = &mut def_map.modules[import.module_scope.0].scope;
+            if scope.add_item_to_namespace(import.name.clone(), def).is_ok() {
+                glob_items.insert(key.clone(), (def, glob_span));
+            }
+        }
+    }
+}
+
+/// Resolve each type alias to the type it stands for. Since aliases are substituted
+/// away wherever they are used, any alias referred to by another is resolved first.
+fn resolve_type_aliases(
+    context: &mut Context,
+    mut type_aliases: BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
+    krate: CrateId,
+    errors: &mut Vec<CollectedErrors>,
+) {
+    let mut in_progress = Vec::new();
+    while let Some(&type_alias_id) = type_aliases.keys().next() {
+        resolve_type_alias(
+            context,
+            &mut type_aliases,
+            type_alias_id,
+            krate,
+            &mut in_progress,
+            errors,
+        );
+    }
+}
+
+fn resolve_type_alias(
+    context: &mut Context,
+    unresolved_aliases: &mut BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
+    type_alias_id: TypeAliasId,
+    krate: CrateId,
+    in_progress: &mut Vec<TypeAliasId>,
+    errors: &mut Vec<CollectedErrors>,
+) {
+    // Aliases which were already resolved, including those of other crates, are skipped
+    let unresolved = match unresolved_aliases.remove(&type_alias_id) {
+        Some(unresolved) => unresolved,
+        None => return,
+    };
+
+    let path_resolver =
+        StandardPathResolver::new(ModuleId { local_id: unresolved.module_id, krate });
+
+    let mut dependencies = Vec::new();
+    let typ = &unresolved.type_alias_def.typ;
+    collect_type_alias_references(typ, &path_resolver, &context.def_maps, &mut dependencies);
+
+    in_progress.push(type_alias_id);
+    for (dependency, span) in dependencies {
+        if in_progress.contains(&dependency) {
+            let name = unresolved.type_alias_def.name.clone();
+            let err = DefCollectorErrorKind::CyclicTypeAlias { name, span };
+            errors.push(CollectedErrors {
+                file_id: unresolved.file_id,
+                errors: vec![err.to_diagnostic()],
+            });
+        } else {
+            resolve_type_alias(context, unresolved_aliases, dependency, krate, in_progress, errors);
+        }
+    }
+    in_progress.pop();
+
+    let (generics, typ, errs) = Resolver::new(
+        &mut context.def_interner,
+        &path_resolver,
+        &context.def_maps,
+        unresolved.file_id,
+    )
+    .resolve_type_alias(unresolved.type_alias_def);
+
+    if !errs.is_empty() {
+        errors.push(CollectedErrors {
+            file_id: unresolved.file_id,
+            errors: vecmap(errs, |err| err.into_diagnostic()),
+        })
+    }
+
+    context.def_interner.update_type_alias(type_alias_id, |type_alias| {
+        type_alias.typ = typ;
+        type_alias.generics = generics;
+    });
+}
+
+/// Find every type alias referred to within the given type, along with the span of each reference
+fn collect_type_alias_references(
+    typ: &UnresolvedType,
+    path_resolver: &StandardPathResolver,
+    def_maps: &HashMap<CrateId, CrateDefMap>,
+    references: &mut Vec<(TypeAliasId, Span)>,
+) {
+    let children: Vec<&UnresolvedType> = match typ {
+        UnresolvedType::Array(_, element) => vec![element.as_ref()],
+        UnresolvedType::Named(path, args) => {
+            if let Ok(Some(ModuleDefId::TypeAliasId(id))) =
+                path_resolver.resolve(def_maps, path.clone())
+            {
+                references.push((id, path.span()));
+            }
+            args.iter().collect()
+        }
+        UnresolvedType::Tuple(fields) => fields.iter().collect(),
+        UnresolvedType::Function(args, ret) => args.iter().chain(Some(ret.as_ref())).collect(),
+        _ => Vec::new(),
+    };
+
+    for child in children {
+        collect_type_alias_references(child, path_resolver, def_maps, references);
+    }
+}
+
 /// Create the mappings from TypeId -> StructType
-/// so that expressions can access the fields of structs
+/// so that expressions can access the fields of structs.
+/// The structs must already have been pushed to the interner.
 fn resolve_structs(
     context: &mut Context,
     structs: HashMap<StructId, UnresolvedStruct>,
     crate_id: CrateId,
     errors: &mut Vec<CollectedErrors>,
 ) {
-    // We must first go through the struct list once to ensure all IDs are pushed to
-    // the def_interner map. This lets structs refer to each other regardless of declaration order
-    // without resolve_struct_fields nondeterministically unwrapping a value
-    // that isn't in the HashMap.
-    for (type_id, typ) in &structs {
-        context.def_interner.push_empty_struct(*type_id, typ);
-    }
-
     for (type_id, typ) in structs {
         let (generics, fields) = resolve_struct_fields(context, crate_id, typ, errors);
         context.def_interner.update_struct(type_id, |struct_def| {
@@ -341,6 +554,90 @@ fn resolve_struct_fields(
     (generics, fields)
 }
 
+/// Resolve the payload types of each enum variant. The enums
+/// themselves must already have been pushed to the interner.
+fn resolve_enums(
+    context: &mut Context,
+    enums: HashMap<EnumId, UnresolvedEnum>,
+    krate: CrateId,
+    errors: &mut Vec<CollectedErrors>,
+) {
+    for (enum_id, unresolved) in enums {
+        let path_resolver =
+            StandardPathResolver::new(ModuleId { local_id: unresolved.module_id, krate });
+
+        let (generics, variants, errs) = Resolver::new(
+            &mut context.def_interner,
+            &path_resolver,
+            &context.def_maps,
+            unresolved.file_id,
+        )
+        .resolve_enum_variants(unresolved.enum_def);
+
+        if !errs.is_empty() {
+            errors.push(CollectedErrors {
+                file_id: unresolved.file_id,
+                errors: vecmap(errs, |err| err.into_diagnostic()),
+            })
+        }
+
+        context.def_interner.update_enum(enum_id, |enum_def| {
+            enum_def.set_variants(variants);
+            enum_def.generics = generics;
+        });
+    }
+}
+
+/// Resolve the signature of each trait method. Each trait is given a fresh type
+/// variable for `Self` which every method of the trait is generic over.
+fn resolve_traits(
+    context: &mut Context,
+    traits: HashMap<TraitId, UnresolvedTrait>,
+    krate: CrateId,
+    errors: &mut Vec<CollectedErrors>,
+) {
+    for (trait_id, unresolved) in traits {
+        let path_resolver =
+            StandardPathResolver::new(ModuleId { local_id: unresolved.module_id, krate });
+
+        let self_id = context.def_interner.next_type_variable_id();
+        let self_var = Shared::new(TypeBinding::Unbound(self_id));
+
+        let trait_def = unresolved.trait_def;
+        let mut resolved = Trait::new(trait_id, trait_def.name, self_id, trait_def.span);
+        let mut errs = vec![];
+
+        for (method, func_id) in trait_def.methods.into_iter().zip(unresolved.method_ids) {
+            let name = method.name.0.contents.clone();
+
+            let resolver = Resolver::new(
+                &mut context.def_interner,
+                &path_resolver,
+                &context.def_maps,
+                unresolved.file_id,
+            );
+            let (func_meta, more_errs) =
+                resolver.resolve_trait_method(method.into_function(), (self_id, self_var.clone()));
+
+            errs.extend(more_errs);
+            context.def_interner.push_fn_meta(func_meta, func_id);
+            context.def_interner.add_trait_method(func_id, trait_id);
+            resolved.methods.insert(name, func_id);
+        }
+
+        if !errs.is_empty() {
+            errors.push(CollectedErrors {
+                file_id: unresolved.file_id,
+                errors: vecmap(errs, |err| err.into_diagnostic()),
+            })
+        }
+
+        // `Self` is always bounded by the trait it was declared in
+        context.def_interner.add_trait_bound(self_id, trait_id);
+        context.def_interner.push_trait(resolved);
+    }
+}
+
 fn resolve_impls(
     interner: &mut NodeInterner,
     crate_id: CrateId,
@@ -358,10 +655,10 @@ fn resolve_impls(
 
         let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
         let self_type = resolver.lookup_struct(path);
-        let self_type_id = self_type.as_ref().map(|typ| typ.borrow().id);
+        let self_type_struct = self_type.as_ref().map(|typ| Type::Struct(typ.clone(), vec![]));
 
         let mut ids =
-            resolve_functions(interner, crate_id, def_maps, methods, self_type_id, errors);
+            resolve_functions(interner, crate_id, def_maps, methods, self_type_struct, errors);
 
         if let Some(typ) = self_type {
             for (file_id, method_id) in &ids {
@@ -389,12 +686,122 @@ fn resolve_impls(
     file_method_ids
 }
 
+/// Resolve the methods of each `impl Trait for Type` block and register the
+/// impl with its trait. The methods are checked against the trait's declarations.
+fn resolve_trait_impls(
+    interner: &mut NodeInterner,
+    crate_id: CrateId,
+    def_maps: &HashMap<CrateId, CrateDefMap>,
+    collected_trait_impls: Vec<UnresolvedTraitImpl>,
+    errors: &mut Vec<CollectedErrors>,
+) -> Vec<(FileId, FuncId)> {
+    let mut file_method_ids = Vec::new();
+
+    for trait_impl in collected_trait_impls {
+        let file_id = trait_impl.file_id;
+        let path_resolver =
+            StandardPathResolver::new(ModuleId { local_id: trait_impl.module_id, krate: crate_id });
+
+        let resolver = Resolver::new(interner, &path_resolver, def_maps, file_id);
+        let (trait_id, object_type, errs) =
+            resolver.lookup_trait_for_impl(trait_impl.trait_path, trait_impl.object_type);
+
+        let mut collected_errors =
+            CollectedErrors { file_id, errors: vecmap(errs, |err| err.into_diagnostic()) };
+
+        let mut ids = resolve_functions(
+            interner,
+            crate_id,
+            def_maps,
+            vec![trait_impl.methods],
+            Some(object_type.clone()),
+            errors,
+        );
+
+        if trait_id != TraitId::dummy_id() && object_type != Type::Error {
+            let trait_def = interner.get_trait(trait_id);
+            let trait_name = trait_def.name.to_string();
+            let trait_methods = trait_def.methods.clone();
+            let self_type_variable = trait_def.self_type_variable;
+
+            if let Some(existing) = trait_def.find_impl(&object_type) {
+                let error = ResolverError::DuplicateDefinition {
+                    name: format!("impl of {} for {}", trait_name, object_type),
+                    first_span: existing.span,
+                    second_span: trait_impl.object_type_span,
+                };
+                collected_errors.errors.push(error.into_diagnostic());
+            }
+
+            let mut methods = BTreeMap::new();
+            for (_, method_id) in &ids {
+                let method_name = interner.function_name(method_id).to_owned();
+
+                let trait_method = match trait_methods.get(&method_name) {
+                    Some(trait_method) => *trait_method,
+                    None => {
+                        let method = interner.function_ident(method_id);
+                        let error = ResolverError::NotATraitMethod {
+                            method,
+                            trait_name: trait_name.clone(),
+                        };
+                        collected_errors.errors.push(error.into_diagnostic());
+                        continue;
+                    }
+                };
+
+                if let Some(first_fn) = methods.insert(method_name.clone(), *method_id) {
+                    let error = ResolverError::DuplicateDefinition {
+                        name: method_name,
+                        first_span: interner.function_ident(&first_fn).span(),
+                        second_span: interner.function_ident(method_id).span(),
+                    };
+                    collected_errors.errors.push(error.into_diagnostic());
+                    continue;
+                }
+
+                let errs = check_trait_impl_method(
+                    interner,
+                    trait_method,
+                    *method_id,
+                    self_type_variable,
+                    &object_type,
+                );
+                collected_errors.errors.extend(errs.into_iter().map(|err| err.into_diagnostic()));
+            }
+
+            let missing_methods: Vec<_> =
+                trait_methods.keys().filter(|name| !methods.contains_key(*name)).cloned().collect();
+
+            if !missing_methods.is_empty() {
+                let error = ResolverError::MissingTraitMethods {
+                    span: trait_impl.object_type_span,
+                    missing_methods,
+                    trait_name,
+                };
+                collected_errors.errors.push(error.into_diagnostic());
+            }
+
+            let span = trait_impl.object_type_span;
+            interner.add_trait_impl(trait_id, TraitImpl { typ: object_type, methods, span });
+        }
+
+        if !collected_errors.errors.is_empty() {
+            errors.push(collected_errors);
+        }
+
+        file_method_ids.append(&mut ids);
+    }
+
+    file_method_ids
+}
+
 fn resolve_functions(
     interner: &mut NodeInterner,
     crate_id: CrateId,
     def_maps: &HashMap<CrateId, CrateDefMap>,
     collected_functions: Vec<UnresolvedFunctions>,
-    self_type: Option<StructId>,
+    self_type: Option<Type>,
     errors: &mut Vec<CollectedErrors>,
 ) -> Vec<(FileId, FuncId)> {
     let mut file_func_ids = Vec::new();
@@ -411,7 +818,7 @@ fn resolve_functions(
                 StandardPathResolver::new(ModuleId { local_id: mod_id, krate: crate_id });
 
             let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file_id);
-            resolver.set_self_type(self_type);
+            resolver.set_self_type(self_type.clone());
 
             let (hir_func, func_meta, errs) = resolver.resolve_function(func);
             interner.push_fn_meta(func_meta, func_id);
@@ -442,3 +849,143 @@ fn type_check_functions(
         .filter(|collected| !collected.errors.is_empty())
         .for_each(|error| errors.push(error));
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use fm::FileType;
+
+    use crate::graph::{CrateName, CrateType};
+    use crate::hir::def_map::CrateDefMap;
+    use crate::hir::Context;
+
+    /// Writes `src` to the root file of a crate in a fresh directory named after the test
+    fn write_crate_root(test_name: &str, crate_name: &str, src: &str) -> PathBuf {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("noirc_dc_crate_{}_{}", std::process::id(), test_name));
+        dir.push(crate_name);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let root_file = dir.join("main.nr");
+        std::fs::write(&root_file, src).unwrap();
+        root_file
+    }
+
+    /// Collects the definitions of the crate `src` depending on the crate `dep_src` named `lib`,
+    /// returning the debug representation of every error found
+    fn collect_errors(test_name: &str, src: &str, dep_src: &str) -> Vec<String> {
+        let mut context = Context::default();
+
+        let root_file = write_crate_root(test_name, "local", src);
+        let root_file_id = context.file_manager.add_file(&root_file, FileType::Root).unwrap();
+        let local_crate = context.crate_graph.add_crate_root(CrateType::Binary, root_file_id);
+
+        let dep_file = write_crate_root(test_name, "dep", dep_src);
+        let dep_file_id = context.file_manager.add_file(&dep_file, FileType::Root).unwrap();
+        let dep_crate = context.crate_graph.add_crate_root(CrateType::Library, dep_file_id);
+        let name = CrateName::new("lib").unwrap();
+        context.crate_graph.add_dep(local_crate, name, dep_crate).unwrap();
+
+        let mut errors = Vec::new();
+        CrateDefMap::collect_defs(local_crate, &mut context, &mut errors);
+        errors
+            .iter()
+            .flat_map(|errors| &errors.errors)
+            .map(|error| format!("{:?}", error))
+            .collect()
+    }
+
+    #[test]
+    fn glob_imports() {
+        let src = "
+            use dep::lib::api::*;
+            use first::*;
+
+            mod first {
+                fn double(x: Field) -> Field {
+                    x * 2
+                }
+            }
+
+            fn main(x: Field) {
+                constrain double(exported(x)) == 2;
+            }
+        ";
+        let dep_src = "
+            mod api {
+                fn exported(x: Field) -> Field {
+                    x
+                }
+            }
+        ";
+
+        let errors = collect_errors("glob_imports", src, dep_src);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn ambiguous_glob_imports() {
+        let src = "
+            use first::*;
+            use second::*;
+
+            mod first {
+                fn double(x: Field) -> Field {
+                    x * 2
+                }
+            }
+
+            mod second {
+                fn double(x: Field) -> Field {
+                    x + x
+                }
+            }
+
+            fn main(x: Field) {
+                constrain double(x) == 2;
+            }
+        ";
+
+        let errors = collect_errors("ambiguous_glob_imports", src, "");
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("`double` is ambiguous"), "{:?}", errors);
+    }
+
+    #[test]
+    fn glob_imports_skip_imported_items() {
+        // `helper` is imported by the dependency's `api` and by `first`, so neither glob imports it
+        let src = "
+            use dep::lib::api::*;
+            use first::*;
+
+            mod first {
+                use crate::second::helper;
+            }
+
+            mod second {
+                fn helper(x: Field) -> Field {
+                    x
+                }
+            }
+
+            fn main(x: Field) {
+                constrain helper(x) == 2;
+            }
+        ";
+        let dep_src = "
+            mod api {
+                use crate::inner::helper;
+            }
+
+            mod inner {
+                fn helper(x: Field) -> Field {
+                    x
+                }
+            }
+        ";
+
+        let errors = collect_errors("glob_imports_skip_imported_items", src, dep_src);
+        assert!(errors.iter().any(|error| error.contains("helper")), "{:?}", errors);
+    }
+}
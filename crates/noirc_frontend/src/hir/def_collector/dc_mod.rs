@@ -2,8 +2,14 @@ use fm::FileId;
 use noirc_errors::{CollectedErrors, CustomDiagnostic, DiagnosableError};
 
 use crate::{
-    graph::CrateId, hir::def_collector::dc_crate::UnresolvedStruct, node_interner::StructId,
-    parser::SubModule, Ident, LetStatement, NoirFunction, NoirImpl, NoirStruct, ParsedModule,
+    graph::CrateId,
+    hir::def_collector::dc_crate::{
+        UnresolvedEnum, UnresolvedStruct, UnresolvedTrait, UnresolvedTraitImpl, UnresolvedTypeAlias,
+    },
+    node_interner::{EnumId, StructId, TraitId},
+    parser::SubModule,
+    Ident, LetStatement, NoirEnum, NoirFunction, NoirImpl, NoirStruct, NoirTrait, NoirTraitImpl,
+    NoirTypeAlias, ParsedModule,
 };
 
 use super::{
@@ -46,6 +52,7 @@ pub fn collect_defs<'a>(
             module_id: collector.module_id,
             path: import.path,
             alias: import.alias,
+            is_glob: import.is_glob,
         });
     }
 
@@ -53,10 +60,18 @@ pub fn collect_defs<'a>(
 
     collector.collect_structs(ast.types, crate_id, errors);
 
+    collector.collect_enums(ast.enums, crate_id, errors);
+
+    collector.collect_type_aliases(context, ast.type_aliases, errors);
+
+    collector.collect_traits(context, ast.traits, crate_id, errors);
+
     let errors_in_same_file = collector.collect_functions(context, ast.functions);
 
     collector.collect_impls(context, ast.impls);
 
+    collector.collect_trait_impls(context, ast.trait_impls);
+
     if !errors_in_same_file.is_empty() {
         errors.push(CollectedErrors { file_id: collector.file_id, errors: errors_in_same_file });
     }
@@ -115,6 +130,26 @@ impl<'a> ModCollector<'a> {
         }
     }
 
+    fn collect_trait_impls(&mut self, context: &mut Context, trait_impls: Vec<NoirTraitImpl>) {
+        for trait_impl in trait_impls {
+            let mut methods = UnresolvedFunctions { file_id: self.file_id, functions: Vec::new() };
+
+            for method in trait_impl.methods {
+                let func_id = context.def_interner.push_empty_fn();
+                methods.push_fn(self.module_id, func_id, method);
+            }
+
+            self.def_collector.collected_trait_impls.push(UnresolvedTraitImpl {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                trait_path: trait_impl.trait_name,
+                object_type: trait_impl.object_type,
+                object_type_span: trait_impl.object_type_span,
+                methods,
+            });
+        }
+    }
+
     fn collect_functions(
         &mut self,
         context: &mut Context,
@@ -202,6 +237,157 @@ impl<'a> ModCollector<'a> {
         }
     }
 
+    /// Collect any enum definitions declared within the ast. Like structs, each enum
+    /// gets its own module, in which its variants are defined.
+    fn collect_enums(
+        &mut self,
+        enums: Vec<NoirEnum>,
+        krate: CrateId,
+        errors: &mut Vec<CollectedErrors>,
+    ) {
+        for enum_definition in enums {
+            let name = enum_definition.name.clone();
+
+            // Create the corresponding module for the enum namespace
+            let id = match self.push_child_module(&name, self.file_id, false) {
+                Ok(local_id) => EnumId(ModuleId { krate, local_id }),
+                Err(mut more_errors) => {
+                    errors.append(&mut more_errors);
+                    continue;
+                }
+            };
+
+            // Add the enum to scope so its path can be looked up later
+            let result = self.def_collector.def_map.modules[self.module_id.0]
+                .scope
+                .define_enum_def(name, id);
+
+            if let Err((first_def, second_def)) = result {
+                let err = DefCollectorErrorKind::DuplicateFunction { first_def, second_def };
+                errors.push(CollectedErrors {
+                    file_id: self.file_id,
+                    errors: vec![err.to_diagnostic()],
+                });
+            }
+
+            // Add each variant to the enum's module so that `Enum::Variant` paths resolve
+            let scope = &mut self.def_collector.def_map.modules[id.0.local_id.0].scope;
+            for (index, (variant, _)) in enum_definition.variants.iter().enumerate() {
+                let result = scope.define_variant_def(variant.clone(), id, index);
+
+                if let Err((first_def, second_def)) = result {
+                    let err = DefCollectorErrorKind::DuplicateVariant { first_def, second_def };
+                    errors.push(CollectedErrors {
+                        file_id: self.file_id,
+                        errors: vec![err.to_diagnostic()],
+                    });
+                }
+            }
+
+            let unresolved = UnresolvedEnum {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                enum_def: enum_definition,
+            };
+            self.def_collector.collected_enums.insert(id, unresolved);
+        }
+    }
+
+    /// Collect any type aliases declared within the ast. Unlike structs and enums,
+    /// aliases do not get a module of their own.
+    fn collect_type_aliases(
+        &mut self,
+        context: &mut Context,
+        type_aliases: Vec<NoirTypeAlias>,
+        errors: &mut Vec<CollectedErrors>,
+    ) {
+        for type_alias in type_aliases {
+            let name = type_alias.name.clone();
+
+            let unresolved = UnresolvedTypeAlias {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                type_alias_def: type_alias,
+            };
+            let id = context.def_interner.push_type_alias(&unresolved);
+
+            // Add the type alias to scope so its path can be looked up later
+            let result = self.def_collector.def_map.modules[self.module_id.0]
+                .scope
+                .define_type_alias_def(name, id);
+
+            if let Err((first_def, second_def)) = result {
+                let err = DefCollectorErrorKind::DuplicateFunction { first_def, second_def };
+                errors.push(CollectedErrors {
+                    file_id: self.file_id,
+                    errors: vec![err.to_diagnostic()],
+                });
+            }
+
+            self.def_collector.collected_type_aliases.insert(id, unresolved);
+        }
+    }
+
+    /// Collect any trait declarations within the ast. Each trait gets its own module
+    /// containing its methods so that they may be called as `Trait::method(...)`.
+    fn collect_traits(
+        &mut self,
+        context: &mut Context,
+        traits: Vec<NoirTrait>,
+        krate: CrateId,
+        errors: &mut Vec<CollectedErrors>,
+    ) {
+        for trait_definition in traits {
+            let name = trait_definition.name.clone();
+
+            // Create the corresponding module for the trait namespace
+            let id = match self.push_child_module(&name, self.file_id, false) {
+                Ok(local_id) => TraitId(ModuleId { krate, local_id }),
+                Err(mut more_errors) => {
+                    errors.append(&mut more_errors);
+                    continue;
+                }
+            };
+
+            // Add the trait to scope so its path can be looked up later
+            let result = self.def_collector.def_map.modules[self.module_id.0]
+                .scope
+                .define_trait_def(name, id);
+
+            if let Err((first_def, second_def)) = result {
+                let err = DefCollectorErrorKind::DuplicateFunction { first_def, second_def };
+                errors.push(CollectedErrors {
+                    file_id: self.file_id,
+                    errors: vec![err.to_diagnostic()],
+                });
+            }
+
+            let scope = &mut self.def_collector.def_map.modules[id.0.local_id.0].scope;
+            let mut method_ids = Vec::with_capacity(trait_definition.methods.len());
+            for method in &trait_definition.methods {
+                let func_id = context.def_interner.push_empty_fn();
+                let result = scope.define_func_def(method.name.clone(), func_id);
+
+                if let Err((first_def, second_def)) = result {
+                    let err = DefCollectorErrorKind::DuplicateFunction { first_def, second_def };
+                    errors.push(CollectedErrors {
+                        file_id: self.file_id,
+                        errors: vec![err.to_diagnostic()],
+                    });
+                }
+                method_ids.push(func_id);
+            }
+
+            let unresolved = UnresolvedTrait {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                trait_def: trait_definition,
+                method_ids,
+            };
+            self.def_collector.collected_traits.insert(id, unresolved);
+        }
+    }
+
     fn collect_submodules(
         &mut self,
         context: &mut Context,
@@ -1,5 +1,7 @@
 use crate::{hir::resolution::import::ImportDirective, Ident};
 
+use noirc_errors::Span;
+
 use noirc_errors::CustomDiagnostic as Diagnostic;
 use noirc_errors::DiagnosableError;
 use thiserror::Error;
@@ -12,12 +14,18 @@ pub enum DefCollectorErrorKind {
     DuplicateModuleDecl { first_def: Ident, second_def: Ident },
     #[error("duplicate import")]
     DuplicateImport { first_def: Ident, second_def: Ident },
+    #[error("duplicate enum variant")]
+    DuplicateVariant { first_def: Ident, second_def: Ident },
     #[error("duplicate global const found in namespace")]
     DuplicateGlobalConst { first_def: Ident, second_def: Ident },
     #[error("unresolved import")]
     UnresolvedModuleDecl { mod_name: Ident },
     #[error("unresolved import")]
     UnresolvedImport { import: ImportDirective },
+    #[error("name imported by multiple glob imports")]
+    AmbiguousGlobImport { name: Ident, first_glob: Span, second_glob: Span },
+    #[error("type alias refers to itself")]
+    CyclicTypeAlias { name: Ident, span: Span },
 }
 
 impl DiagnosableError for DefCollectorErrorKind {
@@ -62,6 +70,19 @@ impl DiagnosableError for DefCollectorErrorKind {
                 diag.add_secondary("second import found here".to_string(), second_span);
                 diag
             }
+            DefCollectorErrorKind::DuplicateVariant { first_def, second_def } => {
+                let first_span = first_def.0.span();
+                let second_span = second_def.0.span();
+                let variant_name = &first_def.0.contents;
+
+                let mut diag = Diagnostic::simple_error(
+                    format!("the variant `{}` is defined multiple times", variant_name),
+                    "first variant found here".to_string(),
+                    first_span,
+                );
+                diag.add_secondary("second variant found here".to_string(), second_span);
+                diag
+            }
             DefCollectorErrorKind::DuplicateGlobalConst { first_def, second_def } => {
                 let first_span = first_def.0.span();
                 let second_span = second_def.0.span();
@@ -91,12 +112,32 @@ impl DiagnosableError for DefCollectorErrorKind {
                     span = span.merge(alias.0.span())
                 }
 
+                let glob = if import.is_glob { "::*" } else { "" };
                 Diagnostic::simple_error(
-                    format!("could not resolve import {}", &import.path.as_string()),
+                    format!("could not resolve import {}{}", &import.path.as_string(), glob),
                     String::new(),
                     span,
                 )
             }
+            DefCollectorErrorKind::AmbiguousGlobImport { name, first_glob, second_glob } => {
+                let mut diag = Diagnostic::simple_error(
+                    format!("`{}` is ambiguous, it is imported by multiple glob imports", name),
+                    format!("`{}` first imported here", name),
+                    *first_glob,
+                );
+                diag.add_secondary(format!("`{}` also imported here", name), *second_glob);
+                diag.add_note(format!("help: import `{}` explicitly to choose one", name));
+                diag
+            }
+            DefCollectorErrorKind::CyclicTypeAlias { name, span } => {
+                let mut diag = Diagnostic::simple_error(
+                    format!("type alias `{}` is defined in terms of itself", name),
+                    "cycle detected here".to_string(),
+                    *span,
+                );
+                diag.add_secondary(format!("`{}` defined here", name), name.0.span());
+                diag
+            }
         }
     }
 }
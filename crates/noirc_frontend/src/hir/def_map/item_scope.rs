@@ -1,6 +1,6 @@
 use super::{namespace::PerNs, ModuleDefId, ModuleId};
 use crate::{
-    node_interner::{FuncId, StmtId, StructId},
+    node_interner::{EnumId, FuncId, StmtId, StructId, TraitId, TypeAliasId},
     Ident,
 };
 use std::collections::{hash_map::Entry, HashMap};
@@ -51,6 +51,10 @@ impl ItemScope {
             ModuleDefId::ModuleId(_) => add_item(&mut self.types),
             ModuleDefId::FunctionId(_) => add_item(&mut self.values),
             ModuleDefId::TypeId(_) => add_item(&mut self.types),
+            ModuleDefId::EnumId(_) => add_item(&mut self.types),
+            ModuleDefId::VariantId(..) => add_item(&mut self.values),
+            ModuleDefId::TypeAliasId(_) => add_item(&mut self.types),
+            ModuleDefId::TraitId(_) => add_item(&mut self.types),
             ModuleDefId::ConstId(_) => add_item(&mut self.values),
         }
     }
@@ -75,6 +79,35 @@ impl ItemScope {
         self.add_definition(name, ModuleDefId::TypeId(local_id))
     }
 
+    pub fn define_enum_def(&mut self, name: Ident, local_id: EnumId) -> Result<(), (Ident, Ident)> {
+        self.add_definition(name, ModuleDefId::EnumId(local_id))
+    }
+
+    pub fn define_variant_def(
+        &mut self,
+        name: Ident,
+        enum_id: EnumId,
+        index: usize,
+    ) -> Result<(), (Ident, Ident)> {
+        self.add_definition(name, ModuleDefId::VariantId(enum_id, index))
+    }
+
+    pub fn define_type_alias_def(
+        &mut self,
+        name: Ident,
+        type_alias_id: TypeAliasId,
+    ) -> Result<(), (Ident, Ident)> {
+        self.add_definition(name, ModuleDefId::TypeAliasId(type_alias_id))
+    }
+
+    pub fn define_trait_def(
+        &mut self,
+        name: Ident,
+        local_id: TraitId,
+    ) -> Result<(), (Ident, Ident)> {
+        self.add_definition(name, ModuleDefId::TraitId(local_id))
+    }
+
     pub fn define_global_const_def(
         &mut self,
         name: Ident,
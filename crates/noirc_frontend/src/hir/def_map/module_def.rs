@@ -1,4 +1,4 @@
-use crate::node_interner::{FuncId, StmtId, StructId};
+use crate::node_interner::{EnumId, FuncId, StmtId, StructId, TraitId, TypeAliasId};
 
 use super::ModuleId;
 
@@ -7,6 +7,11 @@ pub enum ModuleDefId {
     ModuleId(ModuleId),
     FunctionId(FuncId),
     TypeId(StructId),
+    EnumId(EnumId),
+    /// The variant at the given index of an enum
+    VariantId(EnumId, usize),
+    TypeAliasId(TypeAliasId),
+    TraitId(TraitId),
     ConstId(StmtId),
 }
 
@@ -25,6 +30,34 @@ impl ModuleDefId {
         }
     }
 
+    pub fn as_enum(&self) -> Option<EnumId> {
+        match self {
+            ModuleDefId::EnumId(enum_id) => Some(*enum_id),
+            _ => None,
+        }
+    }
+
+    pub fn as_variant(&self) -> Option<(EnumId, usize)> {
+        match self {
+            ModuleDefId::VariantId(enum_id, index) => Some((*enum_id, *index)),
+            _ => None,
+        }
+    }
+
+    pub fn as_type_alias(&self) -> Option<TypeAliasId> {
+        match self {
+            ModuleDefId::TypeAliasId(type_alias_id) => Some(*type_alias_id),
+            _ => None,
+        }
+    }
+
+    pub fn as_trait(&self) -> Option<TraitId> {
+        match self {
+            ModuleDefId::TraitId(trait_id) => Some(*trait_id),
+            _ => None,
+        }
+    }
+
     pub fn as_const(&self) -> Option<StmtId> {
         match self {
             ModuleDefId::ConstId(stmt_id) => Some(*stmt_id),
@@ -38,6 +71,10 @@ impl ModuleDefId {
         match self {
             ModuleDefId::FunctionId(_) => "function",
             ModuleDefId::TypeId(_) => "type",
+            ModuleDefId::EnumId(_) => "enum",
+            ModuleDefId::VariantId(..) => "enum variant",
+            ModuleDefId::TypeAliasId(_) => "type alias",
+            ModuleDefId::TraitId(_) => "trait",
             ModuleDefId::ModuleId(_) => "module",
             ModuleDefId::ConstId(_) => "const",
         }
@@ -96,6 +133,48 @@ impl TryFromModuleDefId for StructId {
     }
 }
 
+impl TryFromModuleDefId for EnumId {
+    fn try_from(id: ModuleDefId) -> Option<Self> {
+        id.as_enum()
+    }
+
+    fn dummy_id() -> Self {
+        EnumId::dummy_id()
+    }
+
+    fn description() -> String {
+        "enum".to_string()
+    }
+}
+
+impl TryFromModuleDefId for (EnumId, usize) {
+    fn try_from(id: ModuleDefId) -> Option<Self> {
+        id.as_variant()
+    }
+
+    fn dummy_id() -> Self {
+        (EnumId::dummy_id(), 0)
+    }
+
+    fn description() -> String {
+        "enum variant".to_string()
+    }
+}
+
+impl TryFromModuleDefId for TraitId {
+    fn try_from(id: ModuleDefId) -> Option<Self> {
+        id.as_trait()
+    }
+
+    fn dummy_id() -> Self {
+        TraitId::dummy_id()
+    }
+
+    fn description() -> String {
+        "trait".to_string()
+    }
+}
+
 impl TryFromModuleDefId for StmtId {
     fn try_from(id: ModuleDefId) -> Option<Self> {
         id.as_const()
@@ -34,6 +34,18 @@ pub enum ResolverError {
     ExpectedConstVariable { name: String, span: Span },
     #[error("Missing expression for declared constant")]
     MissingRhsExpr { name: String, span: Span },
+    #[error("break or continue outside of a loop")]
+    LoopExitOutsideLoop { keyword: &'static str, span: Span },
+    #[error("return outside of a function")]
+    ReturnOutsideFunction { span: Span },
+    #[error("method is not a member of the trait")]
+    NotATraitMethod { method: Ident, trait_name: String },
+    #[error("missing methods from trait impl")]
+    MissingTraitMethods { span: Span, missing_methods: Vec<String>, trait_name: String },
+    #[error("array length is negative or too large")]
+    InvalidArrayLength { span: Span },
+    #[error("incorrect number of generic arguments")]
+    IncorrectGenericCount { span: Span, item_name: String, actual: usize, expected: usize },
 }
 
 impl ResolverError {
@@ -177,6 +189,49 @@ impl ResolverError {
                 "expected expression to be stored for let statement".to_string(),
                 span,
             ),
+            ResolverError::LoopExitOutsideLoop { keyword, span } => Diagnostic::simple_error(
+                format!("`{}` outside of a loop", keyword),
+                format!("cannot `{}` outside of a loop", keyword),
+                span,
+            ),
+            ResolverError::ReturnOutsideFunction { span } => Diagnostic::simple_error(
+                "`return` outside of a function".into(),
+                "cannot `return` outside of a function".into(),
+                span,
+            ),
+            ResolverError::NotATraitMethod { method, trait_name } => Diagnostic::simple_error(
+                format!("method `{}` is not a member of trait `{}`", method, trait_name),
+                format!("not a member of `{}`", trait_name),
+                method.span(),
+            ),
+            ResolverError::MissingTraitMethods { span, missing_methods, trait_name } => {
+                let plural = if missing_methods.len() != 1 { "s" } else { "" };
+                let missing_methods = missing_methods.join(", ");
+                Diagnostic::simple_error(
+                    format!(
+                        "missing method{} from impl of {}: {}",
+                        plural, trait_name, missing_methods
+                    ),
+                    String::new(),
+                    span,
+                )
+            }
+            ResolverError::InvalidArrayLength { span } => Diagnostic::simple_error(
+                "array length is negative or does not fit within a u64".into(),
+                "invalid array length".into(),
+                span,
+            ),
+            ResolverError::IncorrectGenericCount { span, item_name, actual, expected } => {
+                let plural = if expected == 1 { "" } else { "s" };
+                Diagnostic::simple_error(
+                    format!(
+                        "`{}` expects {} generic argument{}, but {} were given",
+                        item_name, expected, plural, actual
+                    ),
+                    format!("expected {} generic argument{}", expected, plural),
+                    span,
+                )
+            }
         }
     }
 }
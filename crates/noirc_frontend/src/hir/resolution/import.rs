@@ -3,12 +3,15 @@ use std::collections::HashMap;
 
 use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId, PerNs};
 use crate::{Ident, Path, PathKind};
+use noirc_errors::Span;
 
 #[derive(Debug)]
 pub struct ImportDirective {
     pub module_id: LocalModuleId,
     pub path: Path,
     pub alias: Option<Ident>,
+    /// A glob import brings every item of the module `path` refers to into scope
+    pub is_glob: bool,
 }
 
 #[derive(Debug)]
@@ -24,6 +27,9 @@ pub struct ResolvedImport {
     pub resolved_namespace: PerNs,
     // The module which we must add the resolved namespace to
     pub module_scope: LocalModuleId,
+    // The span of the glob import this item was brought into scope by, if any.
+    // Items imported by a glob are shadowed by any other item of the same name.
+    pub glob_span: Option<Span>,
 }
 
 pub fn resolve_imports(
@@ -31,7 +37,6 @@ pub fn resolve_imports(
     imports_to_resolve: Vec<ImportDirective>,
     def_maps: &HashMap<CrateId, CrateDefMap>,
 ) -> (Vec<ImportDirective>, Vec<ResolvedImport>) {
-    let num_imports = imports_to_resolve.len();
     let def_map = &def_maps[&crate_id];
 
     let mut unresolved: Vec<ImportDirective> = Vec::new();
@@ -45,23 +50,80 @@ pub fn resolve_imports(
             PathResolution::Unresolved(_) => {
                 unresolved.push(import_directive);
             }
+            PathResolution::Resolved(resolved_namespace) if import_directive.is_glob => {
+                // Only items within a module may be glob imported
+                match resolved_namespace.take_types().and_then(module_of) {
+                    Some(module) => {
+                        resolved.extend(resolve_glob_import(&import_directive, module, def_maps));
+                    }
+                    None => unresolved.push(import_directive),
+                }
+            }
             PathResolution::Resolved(resolved_namespace) => {
                 let name = resolve_path_name(&import_directive);
                 let res = ResolvedImport {
                     name,
                     resolved_namespace,
                     module_scope: import_directive.module_id,
+                    glob_span: None,
                 };
                 resolved.push(res);
             }
         };
     }
 
-    assert!(unresolved.len() + resolved.len() == num_imports);
-
     (unresolved, resolved)
 }
 
+/// Returns the module containing the items of the given definition, if it has one.
+/// Besides modules themselves, structs, enums and traits each have their own module.
+fn module_of(def: ModuleDefId) -> Option<ModuleId> {
+    match def {
+        ModuleDefId::ModuleId(id) => Some(id),
+        ModuleDefId::TypeId(id) => Some(id.0),
+        ModuleDefId::EnumId(id) => Some(id.0),
+        ModuleDefId::TraitId(id) => Some(id.0),
+        _ => None,
+    }
+}
+
+/// Resolves a glob import to one import for each item defined in the given module.
+/// Items the module itself imports are not re-exported through the glob, regardless of
+/// whether the module is in the current crate or in a dependency.
+fn resolve_glob_import(
+    import_directive: &ImportDirective,
+    module: ModuleId,
+    def_maps: &HashMap<CrateId, CrateDefMap>,
+) -> Vec<ResolvedImport> {
+    let scope = &def_maps[&module.krate].modules[module.local_id.0].scope;
+    let glob_span = Some(import_directive.path.span());
+
+    // The scope of a module in a dependency already contains its imports, so keep
+    // only the module's own definitions
+    let definitions = scope.definitions();
+
+    let types = scope
+        .types()
+        .iter()
+        .filter(|(_, (item, _))| definitions.contains(item))
+        .map(|(name, item)| (name, PerNs { types: Some(*item), values: None }));
+    let values = scope
+        .values()
+        .iter()
+        .filter(|(_, (item, _))| definitions.contains(item))
+        .map(|(name, item)| (name, PerNs { types: None, values: Some(*item) }));
+
+    types
+        .chain(values)
+        .map(|(name, resolved_namespace)| ResolvedImport {
+            name: name.clone(),
+            resolved_namespace,
+            module_scope: import_directive.module_id,
+            glob_span,
+        })
+        .collect()
+}
+
 pub fn resolve_path_to_ns(
     import_directive: &ImportDirective,
     def_map: &CrateDefMap,
@@ -125,7 +187,12 @@ fn resolve_name_in_module(
             ModuleDefId::FunctionId(_) => panic!("functions cannot be in the type namespace"),
             // TODO: If impls are ever implemented, types can be used in a path
             ModuleDefId::TypeId(id) => id.0,
+            ModuleDefId::EnumId(id) => id.0,
+            ModuleDefId::TraitId(id) => id.0,
+            ModuleDefId::VariantId(..) => panic!("enum variants cannot be in the type namespace"),
             ModuleDefId::ConstId(_) => panic!("global consts cannot be in the type namespace"),
+            // A type alias has no module of its own for the rest of the path to refer into
+            ModuleDefId::TypeAliasId(_) => return PathResolution::Unresolved(segment.clone()),
         };
         current_mod = &def_maps[&new_module_id.krate].modules[new_module_id.local_id.0];
         // Check if namespace
@@ -166,8 +233,12 @@ fn resolve_external_dep(
     let path_without_crate_name = &path[1..]; // XXX: This will panic if the path is of the form `use dep::std` Ideal algorithm will not distinguish between crate and module
 
     let path = Path { segments: path_without_crate_name.to_vec(), kind: PathKind::Plain };
-    let dep_directive =
-        ImportDirective { module_id: dep_module.local_id, path, alias: directive.alias.clone() };
+    let dep_directive = ImportDirective {
+        module_id: dep_module.local_id,
+        path,
+        alias: directive.alias.clone(),
+        is_glob: directive.is_glob,
+    };
 
     let dep_def_map = def_maps.get(&dep_module.krate).unwrap();
 
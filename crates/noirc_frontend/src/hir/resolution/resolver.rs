@@ -17,11 +17,19 @@ struct ResolverMeta {
     ident: HirIdent,
 }
 
+/// A lambda being resolved, along with the variables it captures from the
+/// scopes below `scope_index`, which is the index of the lambda's own scope.
+struct LambdaContext {
+    captures: Vec<HirIdent>,
+    scope_index: usize,
+}
+
 use crate::hir_def::expr::{
-    HirArrayLiteral, HirBinaryOp, HirBlockExpression, HirCallExpression, HirCastExpression,
-    HirConstructorExpression, HirExpression, HirForExpression, HirIdent, HirIfExpression,
-    HirIndexExpression, HirInfixExpression, HirLiteral, HirMemberAccess, HirMethodCallExpression,
-    HirPrefixExpression,
+    HirArrayLiteral, HirBinaryOp, HirBlockExpression, HirCallExpression, HirCallValueExpression,
+    HirCastExpression, HirConstructorExpression, HirExpression, HirForExpression, HirIdent,
+    HirIfExpression, HirIndexExpression, HirInfixExpression, HirLambda, HirLiteral,
+    HirMatchExpression, HirMemberAccess, HirMethodCallExpression, HirPrefixExpression,
+    HirReturnExpression, HirVariantExpression, HirWhileExpression,
 };
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::rc::Rc;
@@ -29,7 +37,9 @@ use std::rc::Rc;
 use crate::graph::CrateId;
 use crate::hir::def_map::{ModuleDefId, TryFromModuleDefId};
 use crate::hir_def::stmt::{HirAssignStatement, HirLValue, HirPattern};
-use crate::node_interner::{DefinitionId, ExprId, FuncId, NodeInterner, StmtId, StructId};
+use crate::node_interner::{
+    DefinitionId, EnumId, ExprId, FuncId, NodeInterner, StmtId, StructId, TraitId, TypeAliasId,
+};
 use crate::util::vecmap;
 use crate::{
     hir::{def_map::CrateDefMap, resolution::path_resolver::PathResolver},
@@ -37,8 +47,8 @@ use crate::{
     Statement, UnresolvedArraySize,
 };
 use crate::{
-    Generics, LValue, NoirStruct, Path, Pattern, Shared, StructType, Type, TypeBinding,
-    TypeVariable, UnresolvedType, ERROR_IDENT,
+    EnumType, Generics, LValue, NoirEnum, NoirStruct, NoirTypeAlias, Path, Pattern, Shared,
+    StructType, Type, TypeBinding, TypeVariable, TypeVariableId, UnresolvedType, ERROR_IDENT,
 };
 use fm::FileId;
 use noirc_errors::{Location, Span, Spanned};
@@ -65,12 +75,28 @@ pub struct Resolver<'a> {
     errors: Vec<ResolverError>,
     file: FileId,
 
-    /// Set to the current type if we're resolving an impl
-    self_type: Option<StructId>,
+    /// Set to the current type if we're resolving an impl. Within a trait
+    /// declaration this is the generic `Self` type of the trait.
+    self_type: Option<Type>,
 
     /// Contains a mapping of the current struct's generics to
     /// unique type variables if we're resolving a struct. Empty otherwise.
     generics: HashMap<Rc<String>, (TypeVariable, Span)>,
+
+    /// The generics used as the length of an array or string type. These are
+    /// numeric and so may also be used as values within a function body.
+    numeric_generics: HashSet<Rc<String>>,
+
+    /// The number of loops enclosing the expression being resolved,
+    /// used to reject `break` and `continue` outside of a loop.
+    loop_depth: u32,
+
+    /// The declared return type of the function being resolved, if any.
+    /// `return` expressions are type checked against it.
+    return_type: Option<Type>,
+
+    /// The lambdas enclosing the expression being resolved, innermost last.
+    lambdas: Vec<LambdaContext>,
 }
 
 impl<'a> Resolver<'a> {
@@ -87,12 +113,16 @@ impl<'a> Resolver<'a> {
             interner,
             self_type: None,
             generics: HashMap::new(),
+            numeric_generics: HashSet::new(),
             errors: Vec::new(),
             file,
+            loop_depth: 0,
+            return_type: None,
+            lambdas: Vec::new(),
         }
     }
 
-    pub fn set_self_type(&mut self, self_type: Option<StructId>) {
+    pub fn set_self_type(&mut self, self_type: Option<Type>) {
         self.self_type = self_type;
     }
 
@@ -121,6 +151,10 @@ impl<'a> Resolver<'a> {
 
         self.add_generics(func.def.generics.clone());
 
+        for (generic, trait_path) in func.def.trait_bounds.clone() {
+            self.add_trait_bound(generic, trait_path);
+        }
+
         let (hir_func, func_meta) = self.intern_function(func);
         let func_scope_tree = self.scopes.end_function();
 
@@ -129,6 +163,46 @@ impl<'a> Resolver<'a> {
         (hir_func, func_meta, self.errors)
     }
 
+    /// Resolves the signature of a method declared within a trait. Trait methods
+    /// have no body and are generic over `Self`, which is the given type variable.
+    pub fn resolve_trait_method(
+        mut self,
+        func: NoirFunction,
+        self_type_variable: (TypeVariableId, TypeVariable),
+    ) -> (FuncMeta, Vec<ResolverError>) {
+        let (self_id, self_var) = self_type_variable;
+        self.self_type = Some(Type::NamedGeneric(self_var.clone(), Rc::new("Self".into())));
+
+        self.scopes.start_function();
+        let mut func_meta = self.extract_meta(&func);
+        self.scopes.end_function();
+
+        func_meta.typ = match func_meta.typ {
+            Type::Forall(mut generics, typ) => {
+                generics.insert(0, (self_id, self_var));
+                Type::Forall(generics, typ)
+            }
+            typ => Type::Forall(vec![(self_id, self_var)], Box::new(typ)),
+        };
+
+        (func_meta, self.errors)
+    }
+
+    /// Requires the given generic of the current function to implement a trait
+    fn add_trait_bound(&mut self, generic: Ident, trait_path: Path) {
+        // Bounds are parsed along with the generics, so the generic is always defined
+        let (typevar, _) = &self.generics[&generic.0.contents];
+        let id = match &*typevar.borrow() {
+            TypeBinding::Unbound(id) => *id,
+            TypeBinding::Bound(_) => unreachable!("Expected {} to be unbound", generic),
+        };
+
+        let trait_id: TraitId = self.lookup(trait_path);
+        if trait_id != TraitId::dummy_id() {
+            self.interner.add_trait_bound(id, trait_id);
+        }
+    }
+
     fn check_for_unused_variables_in_scope_tree(&mut self, scope_decls: ScopeTree) {
         let mut unused_vars = Vec::new();
         for scope in scope_decls.0.into_iter() {
@@ -180,8 +254,13 @@ impl<'a> Resolver<'a> {
         let id = self.interner.push_definition(name.0.contents.clone(), mutable, is_global, rhs);
         let location = Location::new(name.span(), self.file);
         let ident = HirIdent { location, id };
-        let resolver_meta = ResolverMeta { num_times_used: 0, ident };
 
+        // `_` matches a value without binding it, so it may appear more than once in a scope
+        if name.0.contents == "_" {
+            return ident;
+        }
+
+        let resolver_meta = ResolverMeta { num_times_used: 0, ident };
         let scope = self.scopes.get_mut_scope();
         let old_value = scope.add_key_value(name.0.contents.clone(), resolver_meta);
         if let Some(old_value) = old_value {
@@ -243,27 +322,43 @@ impl<'a> Resolver<'a> {
     // If a variable is not found, then an error is logged and a dummy id
     // is returned, for better error reporting UX
     fn find_variable(&mut self, name: &Ident) -> HirIdent {
-        // Find the definition for this Ident
+        self.find_local(name).unwrap_or_else(|| self.variable_not_declared(name))
+    }
+
+    /// Find the definition for this Ident without issuing an error if there is none.
+    /// A non-global variable declared outside of an enclosing lambda is recorded
+    /// as captured by that lambda.
+    fn find_local(&mut self, name: &Ident) -> Option<HirIdent> {
         let scope_tree = self.scopes.current_scope_tree();
-        let variable = scope_tree.find(&name.0.contents);
+        let (scope_index, variable_found) = scope_tree.find_with_index(&name.0.contents)?;
+        variable_found.num_times_used += 1;
+        let declaration = variable_found.ident;
+
+        if !self.interner.definition(declaration.id).is_global {
+            for lambda in self.lambdas.iter_mut().filter(|lambda| scope_index < lambda.scope_index)
+            {
+                if !lambda.captures.iter().any(|capture| capture.id == declaration.id) {
+                    lambda.captures.push(declaration);
+                }
+            }
+        }
 
         let location = Location::new(name.span(), self.file);
-        let id = if let Some(variable_found) = variable {
-            variable_found.num_times_used += 1;
-            variable_found.ident.id
-        } else {
-            self.push_err(ResolverError::VariableNotDeclared {
-                name: name.0.contents.clone(),
-                span: name.0.span(),
-            });
-            DefinitionId::dummy_id()
-        };
+        Some(HirIdent { location, id: declaration.id })
+    }
 
-        HirIdent { location, id }
+    fn variable_not_declared(&mut self, name: &Ident) -> HirIdent {
+        self.push_err(ResolverError::VariableNotDeclared {
+            name: name.0.contents.clone(),
+            span: name.0.span(),
+        });
+        let location = Location::new(name.span(), self.file);
+        HirIdent { location, id: DefinitionId::dummy_id() }
     }
 
     pub fn intern_function(&mut self, func: NoirFunction) -> (HirFunction, FuncMeta) {
         let func_meta = self.extract_meta(&func);
+        self.return_type = Some(func_meta.return_type().clone());
 
         let hir_func = match func.kind {
             FunctionKind::Builtin | FunctionKind::LowLevel => HirFunction::empty(),
@@ -283,31 +378,26 @@ impl<'a> Resolver<'a> {
         match typ {
             UnresolvedType::FieldElement(is_const) => Type::FieldElement(is_const),
             UnresolvedType::Array(size, elem) => {
-                let resolved_size = match size {
-                    UnresolvedArraySize::Variable => {
-                        let id = self.interner.next_type_variable_id();
-                        let typevar = Shared::new(TypeBinding::Unbound(id));
-                        new_variables.push((id, typevar.clone()));
-
-                        // 'Named'Generic is a bit of a misnomer here, we want a type variable that
-                        // wont be bound over but this one has no name since we do not currently
-                        // require users to explicitly be generic over array lengths.
-                        Type::NamedGeneric(typevar, Rc::new("".into()))
-                    }
-                    UnresolvedArraySize::Fixed(length) => Type::ArrayLength(length),
-                    UnresolvedArraySize::FixedVariable(name) => {
-                        self.resolve_fixed_variable_array_length(name)
-                    }
-                };
+                let resolved_size = self.resolve_array_size(size, new_variables);
                 let elem = Box::new(self.resolve_type_inner(*elem, new_variables));
                 Type::Array(Box::new(resolved_size), elem)
             }
+            UnresolvedType::String(size) => {
+                let resolved_size = self.resolve_array_size(size, new_variables);
+                Type::String(Box::new(resolved_size))
+            }
             UnresolvedType::Integer(is_const, sign, bits) => Type::Integer(is_const, sign, bits),
             UnresolvedType::Bool(is_const) => Type::Bool(is_const),
             UnresolvedType::Unit => Type::Unit,
             UnresolvedType::Unspecified => Type::Error,
             UnresolvedType::Error => Type::Error,
             UnresolvedType::Named(path, args) => {
+                if args.is_empty() && path.as_ident().map_or(false, |i| i == "Self") {
+                    if let Some(self_type) = &self.self_type {
+                        return self_type.clone();
+                    }
+                }
+
                 // Check if the path is a type variable first. We currently disallow generics on type
                 // variables since this is what rust does.
                 if args.is_empty() && path.segments.len() == 1 {
@@ -317,6 +407,16 @@ impl<'a> Resolver<'a> {
                     }
                 }
 
+                if let Some(type_alias_id) = self.try_lookup_type_alias(&path) {
+                    let args = vecmap(args, |arg| self.resolve_type_inner(arg, new_variables));
+                    return self.instantiate_type_alias(type_alias_id, args, path.span());
+                }
+
+                if let Some(definition) = self.try_lookup_enum(&path) {
+                    let args = vecmap(args, |arg| self.resolve_type_inner(arg, new_variables));
+                    return Type::Enum(definition, args);
+                }
+
                 match self.lookup_struct(path) {
                     Some(definition) => {
                         let args = vecmap(args, |arg| self.resolve_type_inner(arg, new_variables));
@@ -328,6 +428,81 @@ impl<'a> Resolver<'a> {
             UnresolvedType::Tuple(fields) => {
                 Type::Tuple(vecmap(fields, |field| self.resolve_type_inner(field, new_variables)))
             }
+            UnresolvedType::Function(args, ret) => {
+                let args = vecmap(args, |arg| self.resolve_type_inner(arg, new_variables));
+                let ret = Box::new(self.resolve_type_inner(*ret, new_variables));
+                Type::Function(args, ret)
+            }
+        }
+    }
+
+    /// Returns the type a type alias stands for, with the given generic arguments substituted in
+    fn instantiate_type_alias(
+        &mut self,
+        type_alias_id: TypeAliasId,
+        args: Vec<Type>,
+        span: Span,
+    ) -> Type {
+        let type_alias = self.interner.get_type_alias(type_alias_id);
+        let expected = type_alias.generics.len();
+
+        if args.len() == expected {
+            type_alias.get_type(&args)
+        } else {
+            let item_name = type_alias.name.to_string();
+            self.push_err(ResolverError::IncorrectGenericCount {
+                span,
+                item_name,
+                actual: args.len(),
+                expected,
+            });
+            Type::Error
+        }
+    }
+
+    /// Resolves the length of an array or string type to either a type-level integer
+    /// or, if no length was given, a fresh generic over the length.
+    fn resolve_array_size(
+        &mut self,
+        size: UnresolvedArraySize,
+        new_variables: &mut Generics,
+    ) -> Type {
+        match size {
+            UnresolvedArraySize::Variable => {
+                let id = self.interner.next_type_variable_id();
+                let typevar = Shared::new(TypeBinding::Unbound(id));
+                new_variables.push((id, typevar.clone()));
+
+                // 'Named'Generic is a bit of a misnomer here, we want a type variable that
+                // wont be bound over but this one has no name since we do not currently
+                // require users to explicitly be generic over array lengths.
+                Type::NamedGeneric(typevar, Rc::new("".into()))
+            }
+            UnresolvedArraySize::Fixed(length) => Type::ArrayLength(length),
+            UnresolvedArraySize::FixedVariable(name) => {
+                if let Some((name, (var, _))) = self.generics.get_key_value(&name.0.contents) {
+                    self.numeric_generics.insert(name.clone());
+                    return Type::NamedGeneric(var.clone(), name.clone());
+                }
+                self.resolve_fixed_variable_array_length(name)
+            }
+            UnresolvedArraySize::Binary(lhs, operator, rhs, span) => {
+                let lhs = self.resolve_array_size(*lhs, new_variables);
+                let rhs = self.resolve_array_size(*rhs, new_variables);
+
+                // Fold the lengths now if they are known so that only lengths
+                // depending on a generic are left to be evaluated later
+                match (lhs.array_length(), rhs.array_length()) {
+                    (Some(lhs), Some(rhs)) => match operator.evaluate(lhs, rhs) {
+                        Some(length) => Type::ArrayLength(length),
+                        None => {
+                            self.push_err(ResolverError::InvalidArrayLength { span });
+                            Type::Error
+                        }
+                    },
+                    _ => Type::ArrayLengthOp(operator, Box::new(lhs), Box::new(rhs)),
+                }
+            }
         }
     }
 
@@ -374,6 +549,15 @@ impl<'a> Resolver<'a> {
         self.resolve_type_inner(typ, &mut vec![])
     }
 
+    /// Translates an UnresolvedType to a Type, or to a fresh type variable
+    /// to be inferred during type checking if it was left unspecified
+    fn resolve_inferred_type(&mut self, typ: UnresolvedType) -> Type {
+        match typ {
+            UnresolvedType::Unspecified => self.interner.next_type_variable(),
+            other => self.resolve_type(other),
+        }
+    }
+
     fn add_generics(&mut self, generics: Vec<Ident>) -> Generics {
         vecmap(generics, |generic| {
             // Map the generic to a fresh type variable
@@ -410,6 +594,28 @@ impl<'a> Resolver<'a> {
         (generics, fields, self.errors)
     }
 
+    pub fn resolve_type_alias(
+        mut self,
+        unresolved: NoirTypeAlias,
+    ) -> (Generics, Type, Vec<ResolverError>) {
+        let generics = self.add_generics(unresolved.generics);
+        let typ = self.resolve_type(unresolved.typ);
+        (generics, typ, self.errors)
+    }
+
+    pub fn resolve_enum_variants(
+        mut self,
+        unresolved: NoirEnum,
+    ) -> (Generics, Vec<(Ident, Vec<Type>)>, Vec<ResolverError>) {
+        let generics = self.add_generics(unresolved.generics);
+
+        let variants = vecmap(unresolved.variants, |(name, fields)| {
+            (name, vecmap(fields, |typ| self.resolve_type(typ)))
+        });
+
+        (generics, variants, self.errors)
+    }
+
     /// Extract metadata from a NoirFunction
     /// to be used in analysis and intern the function parameters
     fn extract_meta(&mut self, func: &NoirFunction) -> FuncMeta {
@@ -487,7 +693,8 @@ impl<'a> Resolver<'a> {
             }
             Statement::Constrain(constrain_stmt) => {
                 let expr_id = self.resolve_expression(constrain_stmt.0);
-                HirStatement::Constrain(HirConstrainStatement(expr_id, self.file))
+                let message = constrain_stmt.1;
+                HirStatement::Constrain(HirConstrainStatement(expr_id, self.file, message))
             }
             Statement::Expression(expr) => HirStatement::Expression(self.resolve_expression(expr)),
             Statement::Semi(expr) => HirStatement::Semi(self.resolve_expression(expr)),
@@ -521,13 +728,39 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    fn resolve_loop_body(&mut self, block: Expression) -> ExprId {
+        self.loop_depth += 1;
+        let block = self.resolve_expression(block);
+        self.loop_depth -= 1;
+        block
+    }
+
+    fn check_inside_loop(&mut self, keyword: &'static str, span: Span) {
+        if self.loop_depth == 0 {
+            self.push_err(ResolverError::LoopExitOutsideLoop { keyword, span });
+        }
+    }
+
     pub fn resolve_expression(&mut self, expr: Expression) -> ExprId {
         let hir_expr = match expr.kind {
             ExpressionKind::Ident(string) => {
                 let span = expr.span;
-                let ident: Ident = Spanned::from(span, string).into();
-                let ident_id = self.find_variable(&ident);
-                HirExpression::Ident(ident_id)
+                let ident: Ident = Spanned::from(span, string.clone()).into();
+                match self.find_local(&ident) {
+                    Some(ident_id) => HirExpression::Ident(ident_id),
+                    None if self.numeric_generics.contains(&string) => {
+                        let (name, (var, _)) = self.generics.get_key_value(&string).unwrap();
+                        if let TypeBinding::Unbound(id) = &*var.borrow() {
+                            self.interner.add_numeric_generic_value(*id, name.clone());
+                        }
+                        HirExpression::NumericGeneric(var.clone())
+                    }
+                    // Otherwise the name may refer to a function being used as a value
+                    None => match self.try_lookup_function(&Path::from_single(string, span)) {
+                        Some(func_id) => HirExpression::FunctionRef(func_id),
+                        None => HirExpression::Ident(self.variable_not_declared(&ident)),
+                    },
+                }
             }
             ExpressionKind::Literal(literal) => HirExpression::Literal(match literal {
                 Literal::Bool(b) => HirLiteral::Bool(b),
@@ -554,10 +787,23 @@ impl<'a> Resolver<'a> {
                 })
             }
             ExpressionKind::Call(call_expr) => {
-                // Get the span and name of path for error reporting
-                let func_id = self.lookup_function(call_expr.func_name);
-                let arguments = vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
-                HirExpression::Call(HirCallExpression { func_id, arguments })
+                if let Some((r#type, variant_index)) = self.try_lookup_variant(&call_expr.func_name)
+                {
+                    let arguments = vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
+                    HirExpression::Variant(HirVariantExpression {
+                        r#type,
+                        variant_index,
+                        arguments,
+                    })
+                } else if let Some(function) = self.try_find_local_function(&call_expr.func_name) {
+                    let arguments = vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
+                    HirExpression::CallValue(HirCallValueExpression { function, arguments })
+                } else {
+                    // Get the span and name of path for error reporting
+                    let func_id = self.lookup_function(call_expr.func_name);
+                    let arguments = vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
+                    HirExpression::Call(HirCallExpression { func_id, arguments })
+                }
             }
             ExpressionKind::MethodCall(call_expr) => {
                 let method = call_expr.method_name;
@@ -572,6 +818,8 @@ impl<'a> Resolver<'a> {
             ExpressionKind::For(for_expr) => {
                 let start_range = self.resolve_expression(for_expr.start_range);
                 let end_range = self.resolve_expression(for_expr.end_range);
+                let max_iterations =
+                    for_expr.max_iterations.map(|max| self.resolve_expression(max));
                 let (identifier, block) = (for_expr.identifier, for_expr.block);
 
                 // TODO: For loop variables are currently mutable by default since we haven't
@@ -579,47 +827,90 @@ impl<'a> Resolver<'a> {
                 let (identifier, block_id) = self.in_new_scope(|this| {
                     (
                         this.add_variable_decl(identifier, true, false, None),
-                        this.resolve_expression(block),
+                        this.resolve_loop_body(block),
                     )
                 });
 
                 HirExpression::For(HirForExpression {
                     start_range,
                     end_range,
+                    max_iterations,
                     block: block_id,
                     identifier,
                 })
             }
+            ExpressionKind::While(while_expr) => HirExpression::While(HirWhileExpression {
+                condition: self.resolve_expression(while_expr.condition),
+                max_iterations: self.resolve_expression(while_expr.max_iterations),
+                block: self.resolve_loop_body(while_expr.block),
+            }),
+            ExpressionKind::Break => {
+                self.check_inside_loop("break", expr.span);
+                HirExpression::Break
+            }
+            ExpressionKind::Continue => {
+                self.check_inside_loop("continue", expr.span);
+                HirExpression::Continue
+            }
+            ExpressionKind::Return(value) => {
+                let value = self.resolve_expression(*value);
+                let return_type = self.return_type.clone().unwrap_or_else(|| {
+                    self.push_err(ResolverError::ReturnOutsideFunction { span: expr.span });
+                    Type::Error
+                });
+                HirExpression::Return(HirReturnExpression { value, return_type })
+            }
             ExpressionKind::If(if_expr) => HirExpression::If(HirIfExpression {
                 condition: self.resolve_expression(if_expr.condition),
                 consequence: self.resolve_expression(if_expr.consequence),
                 alternative: if_expr.alternative.map(|e| self.resolve_expression(e)),
             }),
+            ExpressionKind::Match(match_expr) => {
+                let expression = self.resolve_expression(match_expr.expression);
+                let arms = vecmap(match_expr.arms, |(pattern, branch)| {
+                    self.in_new_scope(|this| {
+                        let pattern = this.resolve_pattern(pattern, false, None);
+                        (pattern, this.resolve_expression(branch))
+                    })
+                });
+                HirExpression::Match(HirMatchExpression { expression, arms })
+            }
             ExpressionKind::Index(indexed_expr) => HirExpression::Index(HirIndexExpression {
                 collection: self.resolve_expression(indexed_expr.collection),
                 index: self.resolve_expression(indexed_expr.index),
             }),
             ExpressionKind::Path(path) => {
-                // If the Path is being used as an Expression, then it is referring to a global constant from a separate module
-                // Otherwise, then it is referring to an Identifier
-                // This lookup allows support of such statements: let x = foo::bar::SOME_CONST + 10;
-                let stmt_id = self.lookup_const(path.clone());
-
-                let hir_let_stmt = self.interner.let_statement(&stmt_id);
-                let ident = hir_let_stmt.ident();
-
-                if self.interner.get_global_const(&stmt_id).is_some() {
-                    HirExpression::Ident(ident)
-                } else {
-                    HirExpression::Ident(match path.as_ident() {
-                        Some(identifier) => self.find_variable(identifier),
-                        None => {
-                            self.push_err(ResolverError::PathIsNotIdent { span: path.span() });
-                            let id = DefinitionId::dummy_id();
-                            let location = Location::new(path.span(), self.file);
-                            HirIdent { id, location }
-                        }
+                if let Some((r#type, variant_index)) = self.try_lookup_variant(&path) {
+                    let arguments = vec![];
+                    HirExpression::Variant(HirVariantExpression {
+                        r#type,
+                        variant_index,
+                        arguments,
                     })
+                } else if let Some(func_id) = self.try_lookup_function(&path) {
+                    HirExpression::FunctionRef(func_id)
+                } else {
+                    // If the Path is being used as an Expression, then it is referring to a global constant from a separate module
+                    // Otherwise, then it is referring to an Identifier
+                    // This lookup allows support of such statements: let x = foo::bar::SOME_CONST + 10;
+                    let stmt_id = self.lookup_const(path.clone());
+
+                    let hir_let_stmt = self.interner.let_statement(&stmt_id);
+                    let ident = hir_let_stmt.ident();
+
+                    if self.interner.get_global_const(&stmt_id).is_some() {
+                        HirExpression::Ident(ident)
+                    } else {
+                        HirExpression::Ident(match path.as_ident() {
+                            Some(identifier) => self.find_variable(identifier),
+                            None => {
+                                self.push_err(ResolverError::PathIsNotIdent { span: path.span() });
+                                let id = DefinitionId::dummy_id();
+                                let location = Location::new(path.span(), self.file);
+                                HirIdent { id, location }
+                            }
+                        })
+                    }
                 }
             }
             ExpressionKind::Block(block_expr) => self.resolve_block(block_expr),
@@ -656,6 +947,26 @@ impl<'a> Resolver<'a> {
                 let elements = vecmap(elements, |elem| self.resolve_expression(elem));
                 HirExpression::Tuple(elements)
             }
+            ExpressionKind::Lambda(lambda) => self.in_new_scope(|this| {
+                let scope_index = this.scopes.current_scope_tree().0.len() - 1;
+                this.lambdas.push(LambdaContext { captures: Vec::new(), scope_index });
+
+                let parameters = vecmap(lambda.parameters, |(pattern, typ)| {
+                    (this.resolve_pattern(pattern, false, None), this.resolve_inferred_type(typ))
+                });
+                let return_type = this.resolve_inferred_type(lambda.return_type);
+
+                // A `return` within the lambda returns from the lambda itself, and
+                // loops outside of the lambda cannot be exited from within it.
+                let outer_return_type = this.return_type.replace(return_type.clone());
+                let outer_loop_depth = std::mem::take(&mut this.loop_depth);
+                let body = this.resolve_expression(lambda.body);
+                this.return_type = outer_return_type;
+                this.loop_depth = outer_loop_depth;
+
+                let captures = this.lambdas.pop().unwrap().captures;
+                HirExpression::Lambda(HirLambda { parameters, return_type, body, captures })
+            }),
         };
 
         let expr_id = self.interner.push_expr(hir_expr);
@@ -708,6 +1019,21 @@ impl<'a> Resolver<'a> {
                     self.resolve_constructor_fields(struct_id, fields, span, resolve_field);
                 HirPattern::Struct(struct_type, fields, span)
             }
+            Pattern::Variant(variant, fields, span) => {
+                let fields = vecmap(fields, |field| {
+                    self.resolve_pattern_mutable(field, mutable, is_global, rhs)
+                });
+
+                let (enum_id, index) = self.lookup(variant);
+                if enum_id != EnumId::dummy_id() {
+                    HirPattern::Variant(self.interner.get_enum(enum_id), index, fields, span)
+                } else {
+                    // The error is already reported, match anything so that no more errors are issued
+                    let name = Ident(Spanned::from(span, ERROR_IDENT.to_owned()));
+                    let id = self.add_variable_decl(name, false, is_global, rhs);
+                    HirPattern::Identifier(id)
+                }
+            }
         }
     }
 
@@ -796,8 +1122,8 @@ impl<'a> Resolver<'a> {
     fn lookup_type(&mut self, path: Path) -> StructId {
         let ident = path.as_ident();
         if ident.map_or(false, |i| i == "Self") {
-            if let Some(id) = &self.self_type {
-                return *id;
+            if let Some(Type::Struct(definition, _)) = &self.self_type {
+                return definition.borrow().id;
             }
         }
 
@@ -809,10 +1135,68 @@ impl<'a> Resolver<'a> {
         (id != StructId::dummy_id()).then(|| self.get_struct(id))
     }
 
+    /// Looks up the function that the given path refers to, if any. No error is
+    /// issued otherwise, since the path may refer to another kind of item.
+    fn try_lookup_function(&self, path: &Path) -> Option<FuncId> {
+        match self.path_resolver.resolve(self.def_maps, path.clone()) {
+            Ok(Some(ModuleDefId::FunctionId(id))) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// If the function called by the given path is a local variable holding a function
+    /// value, returns an expression referring to that variable. Locals shadow functions.
+    fn try_find_local_function(&mut self, path: &Path) -> Option<ExprId> {
+        let ident = self.find_local(path.as_ident()?)?;
+        let expr_id = self.interner.push_expr(HirExpression::Ident(ident));
+        self.interner.push_expr_location(expr_id, path.span(), self.file);
+        Some(expr_id)
+    }
+
+    /// Looks up the enum that the given path refers to, if any. No error is
+    /// issued otherwise, since the path may refer to another kind of item.
+    fn try_lookup_enum(&self, path: &Path) -> Option<Shared<EnumType>> {
+        match self.path_resolver.resolve(self.def_maps, path.clone()) {
+            Ok(Some(ModuleDefId::EnumId(id))) => Some(self.interner.get_enum(id)),
+            _ => None,
+        }
+    }
+
+    /// Looks up the type alias that the given path refers to, if any. No error is
+    /// issued otherwise, since the path may refer to another kind of item.
+    fn try_lookup_type_alias(&self, path: &Path) -> Option<TypeAliasId> {
+        match self.path_resolver.resolve(self.def_maps, path.clone()) {
+            Ok(Some(ModuleDefId::TypeAliasId(id))) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Looks up the enum variant that the given path refers to, if any. No error is
+    /// issued otherwise, since the path may refer to another kind of item.
+    fn try_lookup_variant(&self, path: &Path) -> Option<(Shared<EnumType>, usize)> {
+        match self.path_resolver.resolve(self.def_maps, path.clone()) {
+            Ok(Some(ModuleDefId::VariantId(id, index))) => {
+                Some((self.interner.get_enum(id), index))
+            }
+            _ => None,
+        }
+    }
+
     pub fn lookup_type_for_impl(mut self, path: Path) -> (StructId, Vec<ResolverError>) {
         (self.lookup_type(path), self.errors)
     }
 
+    /// Resolves the trait and the type named by the header of an `impl Trait for Type` block
+    pub fn lookup_trait_for_impl(
+        mut self,
+        trait_path: Path,
+        object_type: UnresolvedType,
+    ) -> (TraitId, Type, Vec<ResolverError>) {
+        let trait_id = self.lookup(trait_path);
+        let object_type = self.resolve_type(object_type);
+        (trait_id, object_type, self.errors)
+    }
+
     fn resolve_path(&mut self, path: Path) -> Option<ModuleDefId> {
         let span = path.span();
         let name = path.as_string();
@@ -847,8 +1231,9 @@ mod test {
     use crate::{hir::resolution::errors::ResolverError, Ident};
 
     use crate::graph::CrateId;
+    use crate::hir::def_collector::dc_crate::UnresolvedTypeAlias;
     use crate::hir_def::function::HirFunction;
-    use crate::node_interner::{FuncId, NodeInterner};
+    use crate::node_interner::{FuncId, NodeInterner, TypeAliasId};
     use crate::{
         hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId},
         parse_program, Path,
@@ -878,6 +1263,28 @@ mod test {
         let file = FileId::default();
 
         let mut errors = Vec::new();
+
+        // Type aliases are resolved before any function, in declaration order
+        for type_alias in program.type_aliases {
+            let name = type_alias.name.0.contents.clone();
+            let unresolved = UnresolvedTypeAlias {
+                file_id: file,
+                module_id: LocalModuleId::dummy_id(),
+                type_alias_def: type_alias,
+            };
+            let id = interner.push_type_alias(&unresolved);
+            path_resolver.insert_type_alias(name, id);
+
+            let resolver = Resolver::new(&mut interner, &path_resolver, &def_maps, file);
+            let (generics, typ, err) = resolver.resolve_type_alias(unresolved.type_alias_def);
+            errors.extend(err);
+
+            interner.update_type_alias(id, |type_alias| {
+                type_alias.typ = typ;
+                type_alias.generics = generics;
+            });
+        }
+
         for func in program.functions {
             let resolver = Resolver::new(&mut interner, &path_resolver, &def_maps, file);
             let (_, _, err) = resolver.resolve_function(func);
@@ -1036,6 +1443,43 @@ mod test {
         assert!(errors.is_empty());
     }
     #[test]
+    fn resolve_while_expr() {
+        let src = r#"
+            fn main(x : Field) {
+                let mut i = 0;
+                while i < 10 max 10 {
+                    i = i + 1;
+                    if i == x {
+                        break;
+                    }
+                    for _j in 0..2 {
+                        continue;
+                    }
+                }
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["main"]);
+        assert!(errors.is_empty());
+    }
+    #[test]
+    fn resolve_loop_exit_outside_loop() {
+        let src = r#"
+            fn main(x : Field) {
+                if x == 0 {
+                    break;
+                }
+                continue;
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["main"]);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        for error in errors {
+            assert!(matches!(error, ResolverError::LoopExitOutsideLoop { .. }));
+        }
+    }
+    #[test]
     fn resolve_call_expr() {
         let src = r#"
             fn main(x : Field) {
@@ -1051,6 +1495,108 @@ mod test {
         assert!(errors.is_empty());
     }
 
+    #[test]
+    fn resolve_lambdas() {
+        let src = r#"
+            fn main(x : Field) {
+                let y = 2;
+                let f = |a| a + x + y;
+                let g = |a: Field| -> Field {
+                    if a == 0 {
+                        return x;
+                    }
+                    f(a)
+                };
+                let _z = foo(g, f(1));
+                let _w = foo(|a| a * 2, 3);
+            }
+
+            fn foo(f: fn(Field) -> Field, x : Field) -> Field {
+                f(x)
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["main", "foo"]);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+    #[test]
+    fn resolve_loop_exit_inside_lambda() {
+        let src = r#"
+            fn main(x : Field) {
+                for _i in 0..3 {
+                    let _f = |a| {
+                        if a == x {
+                            break;
+                        }
+                        a
+                    };
+                }
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["main"]);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(errors[0], ResolverError::LoopExitOutsideLoop { .. }));
+    }
+
+    #[test]
+    fn resolve_numeric_generics() {
+        let src = r#"
+            fn last<N>(x : [Field; N + 1]) -> Field {
+                x[N]
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["last"]);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn resolve_invalid_numeric_generics() {
+        let src = r#"
+            fn foo<T>(_x : T, _y : [Field; 2 - 3]) -> Field {
+                T
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["foo"]);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        assert!(matches!(errors[0], ResolverError::InvalidArrayLength { .. }));
+        assert!(matches!(errors[1], ResolverError::VariableNotDeclared { .. }));
+    }
+
+    #[test]
+    fn resolve_type_aliases() {
+        let src = r#"
+            type Pair<T> = (T, T);
+            type Point = Pair<Field>;
+
+            fn swap(p : Point) -> Pair<Field> {
+                (p.1, p.0)
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["swap"]);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn resolve_type_alias_incorrect_generic_count() {
+        let src = r#"
+            type Pair<T> = (T, T);
+
+            fn first(p : Pair) -> Pair<Field, Field> {
+                p
+            }
+        "#;
+
+        let errors = resolve_src_code(src, vec!["first"]);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        for err in errors {
+            assert!(matches!(err, ResolverError::IncorrectGenericCount { .. }));
+        }
+    }
+
     fn path_unresolved_error(err: ResolverError, expected_unresolved_path: &str) {
         match err {
             ResolverError::PathUnresolved { span: _, name, segment: _ } => {
@@ -1086,5 +1632,9 @@ mod test {
         pub fn insert_func(&mut self, name: String, func_id: FuncId) {
             self.0.insert(name, func_id.into());
         }
+
+        pub fn insert_type_alias(&mut self, name: String, type_alias_id: TypeAliasId) {
+            self.0.insert(name, ModuleDefId::TypeAliasId(type_alias_id));
+        }
     }
 }
@@ -94,6 +94,19 @@ impl<K: std::hash::Hash + Eq + Clone, V> ScopeTree<K, V> {
         None
     }
 
+    /// Like `find`, but also returns the index of the scope the key was found in
+    pub fn find_with_index<Q: ?Sized>(&mut self, key: &Q) -> Option<(usize, &mut V)>
+    where
+        K: std::borrow::Borrow<Q>,
+        Q: std::hash::Hash + Eq,
+    {
+        self.0
+            .iter_mut()
+            .enumerate()
+            .rev()
+            .find_map(|(index, scope)| Some((index, scope.find(key)?)))
+    }
+
     pub fn push_scope(&mut self) {
         self.0.push(Scope::new())
     }
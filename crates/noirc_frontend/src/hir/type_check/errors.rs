@@ -4,6 +4,7 @@ use thiserror::Error;
 
 use crate::hir_def::expr::HirBinaryOp;
 use crate::hir_def::types::Type;
+use crate::util::vecmap;
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum TypeCheckError {
@@ -23,6 +24,16 @@ pub enum TypeCheckError {
     Unstructured { msg: String, span: Span },
     #[error("Error with additional context")]
     Context { err: Box<TypeCheckError>, ctx: &'static str },
+    #[error("Match is not exhaustive, missing {missing:?}")]
+    NonExhaustiveMatch { missing: Vec<String>, span: Span },
+    #[error("Trait {trait_name:?} is not implemented for {typ:?}")]
+    TraitNotImplemented { trait_name: String, typ: String, span: Span },
+    #[error("Method {method_name:?} is implemented for {typ:?} by multiple traits")]
+    AmbiguousTraitMethod { method_name: String, typ: String, trait_names: Vec<String>, span: Span },
+    #[error("Unsupported use of a function value: {reason:?}")]
+    UnsupportedFunctionValue { reason: &'static str, span: Span },
+    #[error("Could not infer the value of numeric generic {name:?}")]
+    UnboundNumericGeneric { name: String, span: Span },
     #[error("Array is not homogeneous")]
     NonHomogeneousArray {
         first_span: Span,
@@ -83,6 +94,52 @@ impl TypeCheckError {
                 let msg = format!("Expected {} argument{}, but found {}", expected, plural, found);
                 Diagnostic::simple_error(msg, String::new(), span)
             }
+            TypeCheckError::NonExhaustiveMatch { missing, span } => {
+                let plural = if missing.len() == 1 { "" } else { "s" };
+                let missing = vecmap(missing, |name| format!("`{}`", name)).join(", ");
+                Diagnostic::simple_error(
+                    format!("Non-exhaustive match, missing arm{} for {}", plural, missing),
+                    "match expression is not exhaustive".to_string(),
+                    span,
+                )
+            }
+            TypeCheckError::TraitNotImplemented { trait_name, typ, span } => {
+                Diagnostic::simple_error(
+                    format!("The trait `{}` is not implemented for `{}`", trait_name, typ),
+                    format!("the trait `{}` is required here", trait_name),
+                    span,
+                )
+            }
+            TypeCheckError::AmbiguousTraitMethod { method_name, typ, trait_names, span } => {
+                let trait_names = vecmap(trait_names, |name| format!("`{}`", name)).join(", ");
+                let mut diag = Diagnostic::simple_error(
+                    format!(
+                        "Multiple applicable methods named `{}` found for `{}`",
+                        method_name, typ
+                    ),
+                    format!("`{}` is implemented by the traits {}", method_name, trait_names),
+                    span,
+                );
+                diag.add_note("help: call the method through its trait to choose one".to_owned());
+                diag
+            }
+            TypeCheckError::UnsupportedFunctionValue { reason, span } => Diagnostic::simple_error(
+                "Unsupported use of a function value".to_owned(),
+                reason.to_owned(),
+                span,
+            ),
+            TypeCheckError::UnboundNumericGeneric { name, span } => {
+                let mut diag = Diagnostic::simple_error(
+                    format!("Could not infer the value of numeric generic `{}`", name),
+                    format!("`{}` is used as a value by the called function", name),
+                    span,
+                );
+                diag.add_note(format!(
+                    "help: give the arguments or the result of this call a type determining `{}`",
+                    name
+                ));
+                diag
+            }
             TypeCheckError::Unstructured { msg, span } => {
                 Diagnostic::simple_error(msg, String::new(), span)
             }
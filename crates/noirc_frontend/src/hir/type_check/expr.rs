@@ -1,16 +1,19 @@
+use std::collections::BTreeSet;
+
 use noirc_errors::Span;
 
 use crate::{
     hir_def::{
         expr::{self, HirBinaryOp, HirExpression, HirLiteral},
-        types::Type,
+        stmt::{HirPattern, HirStatement},
+        types::{EnumType, Type},
     },
-    node_interner::{ExprId, FuncId, NodeInterner},
+    node_interner::{ExprId, FuncId, NodeInterner, StmtId, TraitId},
     util::vecmap,
-    IsConst, Shared, TypeBinding,
+    BinaryOpKind, IsConst, Shared, TypeBinding, TypeBindings,
 };
 
-use super::errors::TypeCheckError;
+use super::{errors::TypeCheckError, stmt::bind_pattern};
 
 pub(crate) fn type_check_expression(
     interner: &mut NodeInterner,
@@ -70,9 +73,10 @@ pub(crate) fn type_check_expression(
                         Shared::new(TypeBinding::Unbound(id)),
                     )
                 }
-                HirLiteral::Str(_) => unimplemented!(
-                    "[Coming Soon] : Currently string literal types have not been implemented"
-                ),
+                HirLiteral::Str(string) => {
+                    let length = Box::new(Type::ArrayLength(string.len() as u64));
+                    Type::String(length)
+                }
             }
         }
         HirExpression::Infix(infix_expr) => {
@@ -101,6 +105,26 @@ pub(crate) fn type_check_expression(
             });
             type_check_function_call(interner, expr_id, &call_expr.func_id, args, errors)
         }
+        HirExpression::CallValue(call_expr) => {
+            let function = type_check_expression(interner, &call_expr.function, errors);
+            let args = vecmap(&call_expr.arguments, |arg| {
+                let typ = type_check_expression(interner, arg, errors);
+                (typ, interner.expr_span(arg))
+            });
+
+            let span = interner.expr_span(expr_id);
+            if let Type::Function(parameters, _) = function.follow_bindings() {
+                if parameters.len() != args.len() {
+                    errors.push(TypeCheckError::ArityMisMatch {
+                        expected: parameters.len() as u16,
+                        found: args.len() as u16,
+                        span,
+                    });
+                }
+            }
+
+            bind_function_type(function, args, span, interner, errors)
+        }
         HirExpression::MethodCall(method_call) => {
             let object_type = type_check_expression(interner, &method_call.object, errors);
             let method_name = method_call.method.0.contents.as_str();
@@ -133,25 +157,36 @@ pub(crate) fn type_check_expression(
             let start_range_type = type_check_expression(interner, &for_expr.start_range, errors);
             let end_range_type = type_check_expression(interner, &for_expr.end_range, errors);
 
-            let span = interner.expr_span(&for_expr.start_range);
-            start_range_type.unify(&Type::constant(Some(span)), span, errors, || {
-                TypeCheckError::TypeCannotBeUsed {
-                    typ: start_range_type.clone(),
-                    place: "for loop",
-                    span,
-                }
-                .add_context("The range of a loop must be const (known at compile-time)")
-            });
+            if let Some(max_iterations) = &for_expr.max_iterations {
+                check_bounded_range(
+                    &for_expr,
+                    max_iterations,
+                    &start_range_type,
+                    &end_range_type,
+                    interner,
+                    errors,
+                );
+            } else {
+                let span = interner.expr_span(&for_expr.start_range);
+                start_range_type.unify(&Type::constant(Some(span)), span, errors, || {
+                    TypeCheckError::TypeCannotBeUsed {
+                        typ: start_range_type.clone(),
+                        place: "for loop",
+                        span,
+                    }
+                    .add_context("The range of a loop must be const (known at compile-time)")
+                });
 
-            let span = interner.expr_span(&for_expr.end_range);
-            end_range_type.unify(&Type::constant(Some(span)), span, errors, || {
-                TypeCheckError::TypeCannotBeUsed {
-                    typ: end_range_type.clone(),
-                    place: "for loop",
-                    span,
-                }
-                .add_context("The range of a loop must be const (known at compile-time)")
-            });
+                let span = interner.expr_span(&for_expr.end_range);
+                end_range_type.unify(&Type::constant(Some(span)), span, errors, || {
+                    TypeCheckError::TypeCannotBeUsed {
+                        typ: end_range_type.clone(),
+                        place: "for loop",
+                        span,
+                    }
+                    .add_context("The range of a loop must be const (known at compile-time)")
+                });
+            }
 
             interner.push_definition_type(for_expr.identifier.id, start_range_type);
 
@@ -163,6 +198,9 @@ pub(crate) fn type_check_expression(
 
             Type::Array(len, Box::new(last_type))
         }
+        HirExpression::While(while_expr) => check_while_expr(&while_expr, interner, errors),
+        HirExpression::Break | HirExpression::Continue => Type::Unit,
+        HirExpression::Return(return_expr) => check_return_expr(&return_expr, interner, errors),
         HirExpression::Block(block_expr) => {
             let mut block_type = Type::Unit;
 
@@ -182,6 +220,9 @@ pub(crate) fn type_check_expression(
                         expr_typ: expr_type.to_string(),
                         expr_span: span,
                     });
+                } else if is_return_statement(interner, stmt) {
+                    // A block ending with `return value;` never produces a value of its own
+                    block_type = interner.next_type_variable();
                 } else {
                     block_type = expr_type;
                 }
@@ -201,9 +242,13 @@ pub(crate) fn type_check_expression(
             }
         }
         HirExpression::If(if_expr) => check_if_expr(&if_expr, expr_id, interner, errors),
+        HirExpression::Match(match_expr) => {
+            check_match_expr(&match_expr, expr_id, interner, errors)
+        }
         HirExpression::Constructor(constructor) => {
             check_constructor(&constructor, expr_id, interner, errors)
         }
+        HirExpression::Variant(variant) => check_variant(&variant, expr_id, interner, errors),
         HirExpression::MemberAccess(access) => {
             check_member_access(access, interner, *expr_id, errors)
         }
@@ -211,6 +256,38 @@ pub(crate) fn type_check_expression(
         HirExpression::Tuple(elements) => {
             Type::Tuple(vecmap(&elements, |elem| type_check_expression(interner, elem, errors)))
         }
+        HirExpression::Lambda(lambda) => {
+            let parameters = vecmap(lambda.parameters, |(pattern, typ)| {
+                bind_pattern(interner, &pattern, typ.clone(), errors);
+                typ
+            });
+
+            let body_type = type_check_expression(interner, &lambda.body, errors);
+            let span = interner.expr_span(&lambda.body);
+            body_type.make_subtype_of(&lambda.return_type, span, errors, || {
+                TypeCheckError::TypeMismatch {
+                    expected_typ: lambda.return_type.to_string(),
+                    expr_typ: body_type.to_string(),
+                    expr_span: span,
+                }
+            });
+
+            Type::Function(parameters, Box::new(lambda.return_type))
+        }
+        HirExpression::FunctionRef(func_id) => {
+            // Like identifiers, references to generic functions are instantiated at each use
+            let (typ, bindings) = interner.function_meta(&func_id).typ.instantiate(interner);
+            let span = interner.expr_span(expr_id);
+            delay_numeric_generic_checks(interner, &bindings, span);
+            interner.store_instantiation_bindings(*expr_id, bindings);
+            interner.set_function_type(*expr_id, typ.clone());
+            typ
+        }
+        // The value of a numeric generic is known at compile-time so it is typed like an integer literal
+        HirExpression::NumericGeneric(_) => {
+            let id = interner.next_type_variable_id();
+            Type::PolymorphicInteger(IsConst::new(interner), Shared::new(TypeBinding::Unbound(id)))
+        }
     };
 
     interner.push_expr_type(expr_id, typ.clone());
@@ -225,19 +302,13 @@ fn type_check_index_expression(
     let index_type = type_check_expression(interner, &index_expr.index, errors);
     let span = interner.expr_span(&index_expr.index);
 
-    index_type.unify(&Type::constant(Some(span)), span, errors, || {
-        // Specialize the error in the case the user has a Field, just not a const one.
-        if matches!(index_type, Type::FieldElement(..)) {
-            TypeCheckError::Unstructured {
-                msg: format!("Array index must be const (known at compile-time), but here a non-const {} was used instead", index_type),
-                span,
-            }
-        } else {
-            TypeCheckError::TypeMismatch {
-                expected_typ: "const Field".to_owned(),
-                expr_typ: index_type.to_string(),
-                expr_span: span,
-            }
+    // Array indices may be witnesses, in which case the access is compiled into a
+    // selector over every element of the array
+    index_type.make_subtype_of(&Type::field(Some(span)), span, errors, || {
+        TypeCheckError::TypeMismatch {
+            expected_typ: "Field".to_owned(),
+            expr_typ: index_type.to_string(),
+            expr_span: span,
         }
     });
 
@@ -319,37 +390,66 @@ fn lookup_method(
     expr_id: &ExprId,
     errors: &mut Vec<TypeCheckError>,
 ) -> Option<FuncId> {
-    match &object_type {
+    let method = match object_type.follow_bindings() {
+        // Methods defined directly on a struct take precedence over trait methods
         Type::Struct(typ, _args) => {
-            let typ = typ.borrow();
-            match typ.methods.get(method_name) {
-                Some(method_id) => Some(*method_id),
-                None => {
-                    errors.push(TypeCheckError::Unstructured {
-                        span: interner.expr_span(expr_id),
-                        msg: format!(
-                            "No method named '{}' found for type '{}'",
-                            method_name, object_type
-                        ),
-                    });
-                    None
-                }
-            }
+            let method = typ.borrow().methods.get(method_name).copied();
+            method.or_else(|| {
+                lookup_trait_impl_method(interner, &object_type, method_name, expr_id, errors)
+            })
         }
+
+        // A generic type only has the methods of the traits it is bounded by.
+        // These are dispatched to the matching impl during monomorphisation.
+        Type::NamedGeneric(binding, _) => match &*binding.borrow() {
+            TypeBinding::Unbound(id) => interner
+                .get_trait_bounds(*id)
+                .iter()
+                .find_map(|bound| interner.get_trait(*bound).methods.get(method_name).copied()),
+            TypeBinding::Bound(_) => unreachable!("follow_bindings should have removed this"),
+        },
+
         // If we fail to resolve the object to a struct type, we have no way of type
         // checking its arguments as we can't even resolve the name of the function
-        Type::Error => None,
+        Type::Error => return None,
 
-        // In the future we could support methods for non-struct types if we have a context
-        // (in the interner?) essentially resembling HashMap<Type, Methods>
-        other => {
-            errors.push(TypeCheckError::Unstructured {
-                span: interner.expr_span(expr_id),
-                msg: format!("Type '{}' must be a struct type to call methods on it", other),
-            });
-            None
-        }
+        other => lookup_trait_impl_method(interner, &other, method_name, expr_id, errors),
+    };
+
+    if method.is_none() {
+        errors.push(TypeCheckError::Unstructured {
+            span: interner.expr_span(expr_id),
+            msg: format!("No method named '{}' found for type '{}'", method_name, object_type),
+        });
     }
+    method
+}
+
+/// Looks up the method with the given name among the trait impls for `typ`.
+/// The call is ambiguous if more than one trait implements a method of that name.
+fn lookup_trait_impl_method(
+    interner: &NodeInterner,
+    typ: &Type,
+    method_name: &str,
+    expr_id: &ExprId,
+    errors: &mut Vec<TypeCheckError>,
+) -> Option<FuncId> {
+    let methods = interner.lookup_trait_impl_methods(typ, method_name);
+
+    if methods.len() > 1 {
+        let mut trait_names =
+            vecmap(&methods, |(trait_id, _)| interner.get_trait(*trait_id).name.to_string());
+        trait_names.sort();
+
+        errors.push(TypeCheckError::AmbiguousTraitMethod {
+            method_name: method_name.to_owned(),
+            typ: typ.to_string(),
+            trait_names,
+            span: interner.expr_span(expr_id),
+        });
+    }
+
+    methods.first().map(|(_, method)| *method)
 }
 
 fn type_check_function_call(
@@ -378,9 +478,111 @@ fn type_check_function_call(
         }
 
         let (function_type, instantiation_bindings) = func_meta.typ.instantiate(interner);
-        interner.store_instantiation_bindings(*expr_id, instantiation_bindings);
+        interner.store_instantiation_bindings(*expr_id, instantiation_bindings.clone());
         interner.set_function_type(*expr_id, function_type.clone());
-        bind_function_type(function_type, arguments, span, interner, errors)
+        let ret = bind_function_type(function_type, arguments, span, interner, errors);
+
+        check_trait_bounds(interner, &instantiation_bindings, span, errors);
+        delay_numeric_generic_checks(interner, &instantiation_bindings, span);
+        ret
+    }
+}
+
+/// Checks that each generic of a called function which is bounded by a trait
+/// was instantiated with a type implementing that trait. If the type is not yet
+/// known, the check is delayed until the whole function has been type checked.
+fn check_trait_bounds(
+    interner: &mut NodeInterner,
+    bindings: &TypeBindings,
+    span: Span,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    let mut delayed = vec![];
+
+    for (id, (_, typ)) in bindings {
+        for bound in interner.get_trait_bounds(*id) {
+            match typ.follow_bindings() {
+                Type::TypeVariable(_) | Type::PolymorphicInteger(..) => {
+                    delayed.push((*bound, typ.clone()));
+                }
+                _ => check_trait_bound(interner, *bound, typ, span, errors),
+            }
+        }
+    }
+
+    for (bound, typ) in delayed {
+        interner.delay_trait_bound_check(bound, typ, span);
+    }
+}
+
+/// Checks the trait bounds whose types were not known at their call site.
+/// Polymorphic integers which were never constrained to an integer type default to a Field.
+pub(super) fn check_delayed_trait_bounds(
+    interner: &mut NodeInterner,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    for (bound, typ, span) in interner.take_delayed_trait_bounds() {
+        check_trait_bound(interner, bound, &typ, span, errors);
+    }
+}
+
+/// The numeric generics a called function uses as values must be known when it is monomorphised.
+/// Since they may be determined by types inferred later on, such as the type of the call's result,
+/// they are checked once the whole function has been type checked.
+fn delay_numeric_generic_checks(interner: &mut NodeInterner, bindings: &TypeBindings, span: Span) {
+    for (id, (_, typ)) in bindings {
+        if let Some(name) = interner.get_numeric_generic_value(*id).cloned() {
+            interner.delay_numeric_generic_check(name, typ.clone(), span);
+        }
+    }
+}
+
+/// Checks that each numeric generic used as a value by a called function was inferred
+pub(super) fn check_delayed_numeric_generics(
+    interner: &mut NodeInterner,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    for (name, typ, span) in interner.take_delayed_numeric_generics() {
+        if !is_known_length(&typ) {
+            errors.push(TypeCheckError::UnboundNumericGeneric { name: name.to_string(), span });
+        }
+    }
+}
+
+/// An array length is known if it contains no unbound type variables. Generics of the
+/// calling function are known since they are in turn bound wherever it is called.
+fn is_known_length(typ: &Type) -> bool {
+    match typ.follow_bindings() {
+        Type::TypeVariable(_) | Type::PolymorphicInteger(..) => false,
+        Type::ArrayLengthOp(_, lhs, rhs) => is_known_length(&lhs) && is_known_length(&rhs),
+        _ => true,
+    }
+}
+
+fn check_trait_bound(
+    interner: &NodeInterner,
+    bound: TraitId,
+    typ: &Type,
+    span: Span,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    let trait_def = interner.get_trait(bound);
+
+    let implemented = match typ.follow_bindings() {
+        Type::NamedGeneric(binding, _) => match &*binding.borrow() {
+            TypeBinding::Unbound(id) => interner.get_trait_bounds(*id).contains(&bound),
+            TypeBinding::Bound(_) => unreachable!(),
+        },
+        Type::Error => true,
+        other => trait_def.find_impl(&other).is_some(),
+    };
+
+    if !implemented {
+        errors.push(TypeCheckError::TraitNotImplemented {
+            trait_name: trait_def.name.to_string(),
+            typ: typ.to_string(),
+            span,
+        });
     }
 }
 
@@ -482,6 +684,16 @@ pub fn infix_operand_type_rules(
                 Err(format!("Types in a binary operation should match, but found {} and {}", lhs_type, rhs_type))
             }
         }
+        // The type of an unannotated closure parameter is inferred from the other operand
+        (TypeVariable(var), other) | (other, TypeVariable(var)) => {
+            if let TypeBinding::Bound(binding) = &*var.borrow() {
+                return infix_operand_type_rules(binding, op, other, errors);
+            }
+            match other.try_bind_to(var) {
+                Ok(()) => Ok(other.clone()),
+                Err(_) => Err(format!("Types in a binary operation should match, but found {} and {}", lhs_type, rhs_type)),
+            }
+        }
         (Integer(..), typ) | (typ,Integer(..)) => {
             Err(format!("Integer cannot be used with type {}", typ))
         }
@@ -496,6 +708,9 @@ pub fn infix_operand_type_rules(
 
         // The result of two Fields is always a witness
         (FieldElement(is_const_x), FieldElement(is_const_y)) => {
+            if op.kind == BinaryOpKind::Modulo {
+                return Err("The remainder operator is only defined for integers, try converting the Fields into integers".to_string());
+            }
             let is_const = is_const_x.and(is_const_y, op.location.span);
             Ok(FieldElement(is_const))
         }
@@ -506,6 +721,85 @@ pub fn infix_operand_type_rules(
     }
 }
 
+/// The range of a loop with a maximum number of iterations may not be known at compile-time,
+/// but its start and end must have the same type
+fn check_bounded_range(
+    for_expr: &expr::HirForExpression,
+    max_iterations: &ExprId,
+    start_range_type: &Type,
+    end_range_type: &Type,
+    interner: &mut NodeInterner,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    let span = interner.expr_span(&for_expr.end_range);
+    start_range_type.make_subtype_of(end_range_type, span, errors, || {
+        TypeCheckError::TypeMismatch {
+            expected_typ: start_range_type.to_string(),
+            expr_typ: end_range_type.to_string(),
+            expr_span: span,
+        }
+    });
+
+    let max_type = type_check_expression(interner, max_iterations, errors);
+    let span = interner.expr_span(max_iterations);
+    max_type.unify(&Type::constant(Some(span)), span, errors, || {
+        TypeCheckError::TypeCannotBeUsed { typ: max_type.clone(), place: "for loop", span }
+            .add_context("The maximum number of iterations must be known at compile-time")
+    });
+}
+
+fn check_while_expr(
+    while_expr: &expr::HirWhileExpression,
+    interner: &mut NodeInterner,
+    errors: &mut Vec<TypeCheckError>,
+) -> Type {
+    let cond_type = type_check_expression(interner, &while_expr.condition, errors);
+    let expr_span = interner.expr_span(&while_expr.condition);
+    cond_type.unify(&Type::Bool(IsConst::new(interner)), expr_span, errors, || {
+        TypeCheckError::TypeMismatch {
+            expected_typ: Type::Bool(IsConst::No(None)).to_string(),
+            expr_typ: cond_type.to_string(),
+            expr_span,
+        }
+    });
+
+    let max_type = type_check_expression(interner, &while_expr.max_iterations, errors);
+    let span = interner.expr_span(&while_expr.max_iterations);
+    max_type.unify(&Type::constant(Some(span)), span, errors, || {
+        TypeCheckError::TypeCannotBeUsed { typ: max_type.clone(), place: "while loop", span }
+            .add_context("The maximum number of iterations must be known at compile-time")
+    });
+
+    type_check_expression(interner, &while_expr.block, errors);
+    Type::Unit
+}
+
+fn check_return_expr(
+    return_expr: &expr::HirReturnExpression,
+    interner: &mut NodeInterner,
+    errors: &mut Vec<TypeCheckError>,
+) -> Type {
+    let value_type = type_check_expression(interner, &return_expr.value, errors);
+    let expr_span = interner.expr_span(&return_expr.value);
+    value_type.make_subtype_of(&return_expr.return_type, expr_span, errors, || {
+        TypeCheckError::TypeMismatch {
+            expected_typ: return_expr.return_type.to_string(),
+            expr_typ: value_type.to_string(),
+            expr_span,
+        }
+    });
+
+    // Execution does not continue after a return, so it can be used where any type is expected
+    interner.next_type_variable()
+}
+
+fn is_return_statement(interner: &NodeInterner, stmt: &StmtId) -> bool {
+    match interner.statement(stmt) {
+        HirStatement::Semi(expr) => matches!(interner.expression(&expr), HirExpression::Return(_)),
+        _ => false,
+    }
+}
+
 fn check_if_expr(
     if_expr: &expr::HirIfExpression,
     expr_id: &ExprId,
@@ -553,6 +847,96 @@ fn check_if_expr(
     }
 }
 
+fn check_match_expr(
+    match_expr: &expr::HirMatchExpression,
+    expr_id: &ExprId,
+    interner: &mut NodeInterner,
+    errors: &mut Vec<TypeCheckError>,
+) -> Type {
+    let expr_type = type_check_expression(interner, &match_expr.expression, errors);
+
+    let arm_types = vecmap(&match_expr.arms, |(pattern, branch)| {
+        bind_pattern(interner, pattern, expr_type.clone(), errors);
+        (type_check_expression(interner, branch, errors), branch)
+    });
+
+    let first_type = arm_types[0].0.clone();
+    for (arm_type, branch) in arm_types.iter().skip(1) {
+        let expr_span = interner.expr_span(branch);
+        arm_type.unify(&first_type, expr_span, errors, || {
+            TypeCheckError::TypeMismatch {
+                expected_typ: first_type.to_string(),
+                expr_typ: arm_type.to_string(),
+                expr_span,
+            }
+            .add_context("Expected the types of all match arms to be equal")
+        });
+    }
+
+    if let Type::Enum(enum_type, _) = &expr_type {
+        let span = interner.expr_span(expr_id);
+        check_exhaustiveness(&enum_type.borrow(), &match_expr.arms, span, errors);
+    }
+
+    first_type
+}
+
+/// A match is exhaustive if one of its arms matches any value, or if every variant
+/// of the enum has an arm. The payloads of a variant pattern are always irrefutable.
+fn check_exhaustiveness(
+    enum_type: &EnumType,
+    arms: &[(HirPattern, ExprId)],
+    span: Span,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    let mut missing: BTreeSet<usize> = (0..enum_type.num_variants()).collect();
+
+    for (pattern, _) in arms {
+        match pattern {
+            HirPattern::Variant(_, index, _, _) => missing.remove(index),
+            _ => return,
+        };
+    }
+
+    if !missing.is_empty() {
+        let missing = vecmap(missing, |index| enum_type.variant_name(index).to_string());
+        errors.push(TypeCheckError::NonExhaustiveMatch { missing, span });
+    }
+}
+
+fn check_variant(
+    variant: &expr::HirVariantExpression,
+    expr_id: &ExprId,
+    interner: &mut NodeInterner,
+    errors: &mut Vec<TypeCheckError>,
+) -> Type {
+    let typ = &variant.r#type;
+    let typ_ref = typ.borrow();
+    let generics = typ_ref.instantiate(interner);
+    let fields = typ_ref.get_variant_fields(variant.variant_index, &generics);
+
+    let arg_types = vecmap(&variant.arguments, |arg| type_check_expression(interner, arg, errors));
+
+    if fields.len() != arg_types.len() {
+        errors.push(TypeCheckError::ArityMisMatch {
+            expected: fields.len() as u16,
+            found: arg_types.len() as u16,
+            span: interner.expr_span(expr_id),
+        });
+    }
+
+    for ((param_type, arg_type), arg) in fields.iter().zip(arg_types).zip(&variant.arguments) {
+        let span = interner.expr_span(arg);
+        arg_type.make_subtype_of(param_type, span, errors, || TypeCheckError::TypeMismatch {
+            expected_typ: param_type.to_string(),
+            expr_typ: arg_type.to_string(),
+            expr_span: span,
+        });
+    }
+
+    Type::Enum(typ.clone(), generics)
+}
+
 fn check_constructor(
     constructor: &expr::HirConstructorExpression,
     expr_id: &ExprId,
@@ -656,6 +1040,15 @@ pub fn comparator_operand_type_rules(
                 Err(format!("Types in a binary operation should match, but found {} and {}", lhs_type, rhs_type))
             }
         }
+        (TypeVariable(var), other) | (other, TypeVariable(var)) => {
+            if let TypeBinding::Bound(binding) = &*var.borrow() {
+                return comparator_operand_type_rules(other, binding, op, errors);
+            }
+            match other.try_bind_to(var) {
+                Ok(()) => Ok(Bool(IsConst::No(None))),
+                Err(_) => Err(format!("Types in a binary operation should match, but found {} and {}", lhs_type, rhs_type)),
+            }
+        }
         (Integer(..), typ) | (typ,Integer(..)) => {
             Err(format!("Integer cannot be used with type {}", typ))
         }
@@ -690,6 +1083,14 @@ pub fn comparator_operand_type_rules(
             // We could check if all elements of all arrays are const but I am lazy
             Ok(Bool(IsConst::No(Some(op.location.span))))
         }
+        (String(x_size), String(y_size)) if matches!(op.kind, Equal | NotEqual) => {
+            if x_size != y_size {
+                return Err(format!("Can only compare strings of the same length. Here LHS is of length {}, and RHS is {} ",
+                    x_size, y_size));
+            }
+
+            Ok(Bool(IsConst::No(Some(op.location.span))))
+        }
         (lhs, rhs) => Err(format!("Unsupported types for comparison: {} and {}", lhs, rhs)),
     }
 }
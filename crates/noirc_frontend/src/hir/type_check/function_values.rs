@@ -0,0 +1,199 @@
+use crate::{
+    hir_def::{
+        expr::HirExpression,
+        stmt::{HirLValue, HirPattern, HirStatement},
+        types::Type,
+    },
+    node_interner::{ExprId, FuncId, NodeInterner, StmtId},
+    FunctionKind,
+};
+
+use super::errors::TypeCheckError;
+
+/// Function values are resolved to the function they refer to during monomorphisation,
+/// so they may only be bound to variables, passed as arguments to functions, or called.
+/// This checks each function-typed expression within `expr` once all types are inferred.
+pub(super) fn check_function_values(
+    interner: &NodeInterner,
+    expr: ExprId,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    check_expression(interner, expr, false, errors);
+}
+
+/// `is_value_position` is true if `expr` is in a position where a function value is allowed
+fn check_expression(
+    interner: &NodeInterner,
+    expr: ExprId,
+    is_value_position: bool,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    let expression = interner.expression(&expr);
+
+    if let Type::Function(..) = interner.id_type(expr).follow_bindings() {
+        let reason = match &expression {
+            _ if !is_value_position => Some(
+                "function values can only be bound to variables, passed to functions or called",
+            ),
+            HirExpression::Lambda(_) | HirExpression::Ident(_) | HirExpression::Error => None,
+            HirExpression::FunctionRef(func_id) => match interner.function_meta(func_id).kind {
+                FunctionKind::Normal => None,
+                FunctionKind::Builtin | FunctionKind::LowLevel => {
+                    Some("builtin and low level functions cannot be used as values")
+                }
+            },
+            _ => Some("the function a value refers to must be known without evaluating it"),
+        };
+
+        if let Some(reason) = reason {
+            let span = interner.expr_span(&expr);
+            errors.push(TypeCheckError::UnsupportedFunctionValue { reason, span });
+        }
+    }
+
+    let mut check = |child: &ExprId| check_expression(interner, *child, false, errors);
+
+    match expression {
+        HirExpression::Ident(_)
+        | HirExpression::Literal(_)
+        | HirExpression::Break
+        | HirExpression::Continue
+        | HirExpression::NumericGeneric(_)
+        | HirExpression::Error => (),
+        HirExpression::Block(block) => {
+            for statement in block.statements() {
+                check_statement(interner, *statement, errors);
+            }
+        }
+        HirExpression::Prefix(prefix) => check(&prefix.rhs),
+        HirExpression::Infix(infix) => {
+            check(&infix.lhs);
+            check(&infix.rhs);
+        }
+        HirExpression::Index(index) => {
+            check(&index.collection);
+            check(&index.index);
+        }
+        HirExpression::Constructor(constructor) => {
+            constructor.fields.iter().for_each(|(_, field)| check(field));
+        }
+        HirExpression::Variant(variant) => variant.arguments.iter().for_each(check),
+        HirExpression::MemberAccess(access) => check(&access.lhs),
+        HirExpression::Call(call) => {
+            check_generic_instantiation(interner, call.func_id, expr, errors);
+            for argument in &call.arguments {
+                check_expression(interner, *argument, true, errors);
+            }
+        }
+        HirExpression::CallValue(call) => {
+            check_expression(interner, call.function, true, errors);
+            for argument in &call.arguments {
+                if let Type::Function(..) = interner.id_type(*argument).follow_bindings() {
+                    let reason = "function values cannot yet be passed to other function values";
+                    let span = interner.expr_span(argument);
+                    errors.push(TypeCheckError::UnsupportedFunctionValue { reason, span });
+                } else {
+                    check_expression(interner, *argument, false, errors);
+                }
+            }
+        }
+        // Method calls are only left in the tree if their method could not be found
+        HirExpression::MethodCall(method_call) => {
+            check(&method_call.object);
+            method_call.arguments.iter().for_each(check);
+        }
+        HirExpression::Cast(cast) => check(&cast.lhs),
+        HirExpression::For(for_expr) => {
+            check(&for_expr.start_range);
+            check(&for_expr.end_range);
+            for_expr.max_iterations.iter().for_each(&mut check);
+            check(&for_expr.block);
+        }
+        HirExpression::While(while_expr) => {
+            check(&while_expr.condition);
+            check(&while_expr.max_iterations);
+            check(&while_expr.block);
+        }
+        HirExpression::Return(return_expr) => check(&return_expr.value),
+        HirExpression::If(if_expr) => {
+            check(&if_expr.condition);
+            check(&if_expr.consequence);
+            if_expr.alternative.iter().for_each(&mut check);
+        }
+        HirExpression::Match(match_expr) => {
+            check(&match_expr.expression);
+            match_expr.arms.iter().for_each(|(_, branch)| check(branch));
+        }
+        HirExpression::Tuple(elements) => elements.iter().for_each(check),
+        HirExpression::Lambda(lambda) => check(&lambda.body),
+        HirExpression::FunctionRef(func_id) => {
+            check_generic_instantiation(interner, func_id, expr, errors);
+        }
+    }
+}
+
+fn check_statement(interner: &NodeInterner, statement: StmtId, errors: &mut Vec<TypeCheckError>) {
+    match interner.statement(&statement) {
+        HirStatement::Let(let_statement) => {
+            let mut pattern = &let_statement.pattern;
+            while let HirPattern::Mutable(inner, _) = pattern {
+                pattern = inner.as_ref();
+            }
+
+            let binds_identifier = matches!(pattern, HirPattern::Identifier(_));
+            check_expression(interner, let_statement.expression, binds_identifier, errors);
+        }
+        HirStatement::Constrain(constrain) => {
+            check_expression(interner, constrain.0, false, errors)
+        }
+        HirStatement::Assign(assign) => {
+            check_lvalue(interner, &assign.lvalue, errors);
+            check_expression(interner, assign.expression, false, errors);
+        }
+        HirStatement::Expression(expr) | HirStatement::Semi(expr) => {
+            check_expression(interner, expr, false, errors);
+        }
+        HirStatement::Error => (),
+    }
+}
+
+fn check_lvalue(interner: &NodeInterner, lvalue: &HirLValue, errors: &mut Vec<TypeCheckError>) {
+    match lvalue {
+        HirLValue::Ident(_) => (),
+        HirLValue::MemberAccess { object, .. } => check_lvalue(interner, object, errors),
+        HirLValue::Index { array, index } => {
+            check_lvalue(interner, array, errors);
+            check_expression(interner, *index, false, errors);
+        }
+    }
+}
+
+/// Generic functions cannot be instantiated with a type containing a function,
+/// since the values of such a type would not be in a position a function value is allowed.
+fn check_generic_instantiation(
+    interner: &NodeInterner,
+    func_id: FuncId,
+    expr: ExprId,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    if func_id == FuncId::dummy_id() {
+        return;
+    }
+
+    let bindings = interner.get_instantiation_bindings(expr);
+    if bindings.values().any(|(_, typ)| contains_function(typ)) {
+        let reason = "generic types cannot be instantiated with function types";
+        let span = interner.expr_span(&expr);
+        errors.push(TypeCheckError::UnsupportedFunctionValue { reason, span });
+    }
+}
+
+fn contains_function(typ: &Type) -> bool {
+    match typ.follow_bindings() {
+        Type::Function(..) => true,
+        Type::Array(_, element) => contains_function(&element),
+        Type::Tuple(fields) => fields.iter().any(contains_function),
+        Type::Struct(_, args) | Type::Enum(_, args) => args.iter().any(contains_function),
+        _ => false,
+    }
+}
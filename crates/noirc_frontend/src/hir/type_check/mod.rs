@@ -1,5 +1,6 @@
 mod errors;
 mod expr;
+mod function_values;
 mod stmt;
 
 // Type checking at the moment is very simple due to what is supported in the grammar.
@@ -7,9 +8,11 @@ mod stmt;
 // all foreseeable types, if it is needed then we would need to switch to Hindley-Milner type or maybe bidirectional
 
 pub use errors::TypeCheckError;
-use expr::type_check_expression;
+use expr::{check_delayed_numeric_generics, check_delayed_trait_bounds, type_check_expression};
+use function_values::check_function_values;
 
 use crate::node_interner::{FuncId, NodeInterner};
+use crate::{Type, TypeVariableId};
 
 pub(crate) use self::stmt::{bind_pattern, type_check};
 
@@ -46,6 +49,44 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
         });
     }
 
+    // Now that the types within the function have been inferred,
+    // check the trait bounds and numeric generics which could not be checked at their call site
+    check_delayed_trait_bounds(interner, &mut errors);
+    check_delayed_numeric_generics(interner, &mut errors);
+    check_function_values(interner, *func_as_expr, &mut errors);
+
+    errors
+}
+
+/// Checks that the signature of a method within a trait impl matches the
+/// signature declared in the trait once `Self` is replaced with the impl's type.
+pub fn check_trait_impl_method(
+    interner: &mut NodeInterner,
+    trait_method: FuncId,
+    impl_method: FuncId,
+    self_type_variable: TypeVariableId,
+    object_type: &Type,
+) -> Vec<TypeCheckError> {
+    let mut errors = vec![];
+    let span = interner.function_ident(&impl_method).span();
+
+    let (expected, bindings) = interner.function_meta(&trait_method).typ.instantiate(interner);
+    let (actual, _) = interner.function_meta(&impl_method).typ.instantiate(interner);
+
+    if let Some((_, self_type)) = bindings.get(&self_type_variable) {
+        self_type.unify(object_type, span, &mut errors, || TypeCheckError::TypeMismatch {
+            expected_typ: object_type.to_string(),
+            expr_typ: self_type.to_string(),
+            expr_span: span,
+        });
+    }
+
+    actual.unify(&expected, span, &mut errors, || TypeCheckError::TypeMismatch {
+        expected_typ: expected.to_string(),
+        expr_typ: actual.to_string(),
+        expr_span: span,
+    });
+
     errors
 }
 
@@ -209,6 +250,265 @@ mod test {
         type_check_src_code(src, vec![String::from("main"), String::from("foo")]);
     }
 
+    #[test]
+    fn basic_while_expr() {
+        let src = r#"
+            fn main(x : Field) {
+                let mut i = 0;
+                while i != x max 10 {
+                    i = i + 1;
+                    if i == 5 {
+                        break;
+                    }
+                    continue;
+                }
+            }
+
+        "#;
+
+        type_check_src_code(src, vec![String::from("main"), String::from("foo")]);
+    }
+
+    #[test]
+    fn bounded_for_expr() {
+        let src = r#"
+            fn main(x : Field, a : [u32; 4]) {
+                let mut s = 0;
+                for i in 0..x max 4 {
+                    s = s + a[i];
+                }
+            }
+
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn for_expr_with_witness_range() {
+        let src = r#"
+            fn main(x : u32) {
+                for _i in 0..x {
+                }
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn basic_return_expr() {
+        let src = r#"
+            fn main(x : Field) -> pub Field {
+                if x == 0 {
+                    return 1;
+                }
+                for i in 0..3 {
+                    if i == x {
+                        return i;
+                    }
+                }
+                let y = if x == 5 { return 2 } else { x };
+                return y + 1;
+            }
+
+        "#;
+
+        type_check_src_code(src, vec![String::from("main"), String::from("foo")]);
+    }
+
+    #[test]
+    fn basic_match_expr() {
+        let src = r#"
+            fn main(x : Field) -> pub Field {
+                let y = match (x, 2) {
+                    (a, _) => a + 1,
+                };
+                match y {
+                    z => z,
+                    _ => 0,
+                }
+            }
+
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn basic_string_expr() {
+        let src = r#"
+            fn main(x : str<5>) {
+                let bytes: [u8; 5] = "hello";
+                let _eq = x == "world";
+                let _len = len(x);
+                let _first = bytes[0];
+            }
+
+            fn len(_bytes : [u8]) -> Field {
+                0
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main"), String::from("len")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn string_length_mismatch() {
+        let src = r#"
+            fn main(x : str<5>) {
+                let _eq = x == "hi";
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remainder_of_fields() {
+        let src = r#"
+            fn main(x : Field, y : Field) {
+                let _z = x % y;
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    fn numeric_generics() {
+        let src = r#"
+            fn main(x : [Field; 4]) {
+                let _last = last(x);
+                let _y: [Field; 4] = keep(x);
+                let _z: [Field; 4] = keep(keep(x));
+            }
+
+            fn last<N>(x : [Field; N + 1]) -> Field {
+                x[N]
+            }
+
+            fn keep<N>(x : [Field; N]) -> [Field; N] {
+                x
+            }
+        "#;
+
+        let functions = vec![String::from("main"), String::from("last"), String::from("keep")];
+        type_check_src_code(src, functions);
+    }
+
+    #[test]
+    fn numeric_generic_as_value() {
+        let src = r#"
+            fn sum<N>(x : [Field; 2 * N]) -> Field {
+                let mut s = 0;
+                for i in 0..2 * N {
+                    s = s + x[i];
+                }
+                s
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("sum")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn numeric_generic_length_mismatch() {
+        let src = r#"
+            fn main(x : [Field; 4]) {
+                let _y: [Field; 5] = keep(x);
+            }
+
+            fn keep<N>(x : [Field; N - 1]) -> [Field; N - 1] {
+                x
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main"), String::from("keep")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unbound_numeric_generic_value() {
+        let src = r#"
+            fn main() {
+                let _x = size();
+            }
+
+            fn size<N>() -> Field {
+                N
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main"), String::from("size")]);
+    }
+
+    #[test]
+    fn function_values() {
+        let src = r#"
+            fn main(x : Field) {
+                let f = |y| y + x;
+                let g = double;
+                let _z = apply(f, g(x));
+                let _w = apply(|y| y * 3, 1);
+            }
+
+            fn double(x : Field) -> Field {
+                x * 2
+            }
+
+            fn apply(f : fn(Field) -> Field, x : Field) -> Field {
+                f(x)
+            }
+        "#;
+
+        let functions = vec![String::from("main"), String::from("double"), String::from("apply")];
+        type_check_src_code(src, functions);
+    }
+
+    #[test]
+    #[should_panic]
+    fn function_value_chosen_by_if() {
+        let src = r#"
+            fn main(x : bool) {
+                let f = if x { |y| y + 1 } else { |y| y + 2 };
+                let _z = f(3);
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn function_value_in_array() {
+        let src = r#"
+            fn main(x : Field) {
+                let fs = [|y| y + x, |y| y * x];
+                let f = fs[0];
+                let _z = f(3);
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn returned_function_value() {
+        let src = r#"
+            fn main(x : Field) -> fn(Field) -> Field {
+                |y| y + x
+            }
+        "#;
+
+        type_check_src_code(src, vec![String::from("main")]);
+    }
+
     // This is the same Stub that is in the resolver, maybe we can pull this out into a test module and re-use?
     struct TestPathResolver(HashMap<String, ModuleDefId>);
 
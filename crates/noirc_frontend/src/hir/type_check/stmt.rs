@@ -99,6 +99,31 @@ pub fn bind_pattern(
                 });
             }
         },
+        HirPattern::Variant(enum_type, index, fields, span) => match typ {
+            Type::Enum(inner, args) if &inner == enum_type => {
+                let field_types = inner.borrow().get_variant_fields(*index, &args);
+
+                if field_types.len() != fields.len() {
+                    errors.push(TypeCheckError::ArityMisMatch {
+                        expected: field_types.len() as u16,
+                        found: fields.len() as u16,
+                        span: *span,
+                    });
+                }
+
+                for (field, field_type) in fields.iter().zip(field_types) {
+                    bind_pattern(interner, field, field_type, errors);
+                }
+            }
+            Type::Error => (),
+            other => {
+                errors.push(TypeCheckError::TypeMismatch {
+                    expected_typ: enum_type.borrow().to_string(),
+                    expr_typ: other.to_string(),
+                    expr_span: *span,
+                });
+            }
+        },
     }
 }
 
@@ -184,9 +209,9 @@ fn type_check_lvalue(
             let index_type = type_check_expression(interner, &index, errors);
             let expr_span = interner.expr_span(&index);
 
-            index_type.unify(&Type::constant(Some(expr_span)), expr_span, errors, || {
+            index_type.make_subtype_of(&Type::field(Some(expr_span)), expr_span, errors, || {
                 TypeCheckError::TypeMismatch {
-                    expected_typ: "const Field".to_owned(),
+                    expected_typ: "Field".to_owned(),
                     expr_typ: index_type.to_string(),
                     expr_span,
                 }
@@ -5,7 +5,8 @@ use noirc_errors::Location;
 use crate::node_interner::{DefinitionId, ExprId, FuncId, StmtId, StructId};
 use crate::{BinaryOp, BinaryOpKind, Ident, Shared, UnaryOp};
 
-use super::types::{StructType, Type};
+use super::stmt::HirPattern;
+use super::types::{EnumType, StructType, Type, TypeVariable};
 
 #[derive(Debug, Clone)]
 pub enum HirExpression {
@@ -16,13 +17,25 @@ pub enum HirExpression {
     Infix(HirInfixExpression),
     Index(HirIndexExpression),
     Constructor(HirConstructorExpression),
+    Variant(HirVariantExpression),
     MemberAccess(HirMemberAccess),
     Call(HirCallExpression),
+    CallValue(HirCallValueExpression),
     MethodCall(HirMethodCallExpression),
     Cast(HirCastExpression),
     For(HirForExpression),
+    While(HirWhileExpression),
+    Break,
+    Continue,
+    Return(HirReturnExpression),
     If(HirIfExpression),
+    Match(HirMatchExpression),
     Tuple(Vec<ExprId>),
+    Lambda(HirLambda),
+    /// A global function used as a value rather than called directly
+    FunctionRef(FuncId),
+    /// A numeric generic, such as the `N` in `fn foo<N>(x: [Field; N])`, used as a value
+    NumericGeneric(TypeVariable),
     Error,
 }
 
@@ -33,6 +46,24 @@ impl HirExpression {
     }
 }
 
+/// A `match` expression. The arms are tried in order and the first
+/// whose pattern matches the value is evaluated.
+#[derive(Debug, Clone)]
+pub struct HirMatchExpression {
+    pub expression: ExprId,
+    pub arms: Vec<(HirPattern, ExprId)>,
+}
+
+/// A closure. `captures` holds each local variable from an enclosing scope that
+/// is used within the body, in the order they are first used. Captures are by value.
+#[derive(Debug, Clone)]
+pub struct HirLambda {
+    pub parameters: Vec<(HirPattern, Type)>,
+    pub return_type: Type,
+    pub body: ExprId,
+    pub captures: Vec<HirIdent>,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct HirIdent {
     pub location: Location,
@@ -44,9 +75,24 @@ pub struct HirForExpression {
     pub identifier: HirIdent,
     pub start_range: ExprId,
     pub end_range: ExprId,
+    pub max_iterations: Option<ExprId>,
+    pub block: ExprId,
+}
+
+#[derive(Debug, Clone)]
+pub struct HirWhileExpression {
+    pub condition: ExprId,
+    pub max_iterations: ExprId,
     pub block: ExprId,
 }
 
+/// A `return` expression, along with the declared return type of the enclosing function
+#[derive(Debug, Clone)]
+pub struct HirReturnExpression {
+    pub value: ExprId,
+    pub return_type: Type,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct HirBinaryOp {
     pub kind: BinaryOpKind,
@@ -115,6 +161,14 @@ pub struct HirCallExpression {
     pub arguments: Vec<ExprId>,
 }
 
+/// A call to a function value, such as a closure or a function-typed parameter,
+/// rather than to a function known by name.
+#[derive(Debug, Clone)]
+pub struct HirCallValueExpression {
+    pub function: ExprId,
+    pub arguments: Vec<ExprId>,
+}
+
 /// These nodes are temporary, they're
 /// lowered into HirCallExpression nodes
 /// after type checking resolves the object
@@ -135,6 +189,14 @@ impl HirMethodCallExpression {
     }
 }
 
+/// An enum value built from the variant at `variant_index`, with the given payload
+#[derive(Debug, Clone)]
+pub struct HirVariantExpression {
+    pub r#type: Shared<EnumType>,
+    pub variant_index: usize,
+    pub arguments: Vec<ExprId>,
+}
+
 #[derive(Debug, Clone)]
 pub struct HirConstructorExpression {
     pub type_id: StructId,
@@ -50,6 +50,7 @@ fn get_param_name<'a>(pattern: &HirPattern, interner: &'a NodeInterner) -> Optio
         HirPattern::Mutable(pattern, _) => get_param_name(pattern, interner),
         HirPattern::Tuple(_, _) => None,
         HirPattern::Struct(_, _, _) => None,
+        HirPattern::Variant(_, _, _, _) => None,
     }
 }
 
@@ -74,6 +75,7 @@ impl Parameters {
             HirPattern::Mutable(_, span) => *span,
             HirPattern::Tuple(_, span) => *span,
             HirPattern::Struct(_, _, span) => *span,
+            HirPattern::Variant(_, _, _, span) => *span,
         });
 
         let merged_span = spans.pop().unwrap();
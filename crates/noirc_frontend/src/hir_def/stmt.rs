@@ -1,6 +1,6 @@
 use super::expr::HirIdent;
 use crate::node_interner::ExprId;
-use crate::{Ident, Shared, StructType, Type};
+use crate::{EnumType, Ident, Shared, StructType, Type};
 use fm::FileId;
 use noirc_errors::Span;
 
@@ -27,7 +27,7 @@ pub struct HirAssignStatement {
 }
 
 #[derive(Debug, Clone)]
-pub struct HirConstrainStatement(pub ExprId, pub FileId);
+pub struct HirConstrainStatement(pub ExprId, pub FileId, pub Option<String>);
 
 #[derive(Debug, Clone)]
 pub struct BinaryStatement {
@@ -52,6 +52,8 @@ pub enum HirPattern {
     Mutable(Box<HirPattern>, Span),
     Tuple(Vec<HirPattern>, Span),
     Struct(Shared<StructType>, Vec<(Ident, HirPattern)>, Span),
+    /// The variant of the given enum at the given index, with a pattern for each payload field
+    Variant(Shared<EnumType>, usize, Vec<HirPattern>, Span),
 }
 
 impl HirPattern {
@@ -61,6 +63,7 @@ impl HirPattern {
             HirPattern::Mutable(_, _) => 0,
             HirPattern::Tuple(fields, _) => fields.len(),
             HirPattern::Struct(_, fields, _) => fields.len(),
+            HirPattern::Variant(_, _, fields, _) => fields.len(),
         }
     }
 
@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+
+use noirc_errors::Span;
+
+use crate::node_interner::{FuncId, TraitId};
+use crate::{Ident, Type, TypeVariableId};
+
+/// A trait declaration. Each method of a trait is a function without a body
+/// which is generic over `Self`. Calls to these methods are dispatched to the
+/// matching impl once `Self` is known, during monomorphisation.
+#[derive(Debug, Clone)]
+pub struct Trait {
+    pub id: TraitId,
+    pub name: Ident,
+    /// The type variable standing in for `Self` within the methods' signatures
+    pub self_type_variable: TypeVariableId,
+    pub methods: BTreeMap<String, FuncId>,
+    pub impls: Vec<TraitImpl>,
+    pub span: Span,
+}
+
+/// An impl of a trait for a single concrete type
+#[derive(Debug, Clone)]
+pub struct TraitImpl {
+    pub typ: Type,
+    pub methods: BTreeMap<String, FuncId>,
+    pub span: Span,
+}
+
+impl Trait {
+    pub fn new(id: TraitId, name: Ident, self_type_variable: TypeVariableId, span: Span) -> Trait {
+        Trait { id, name, self_type_variable, methods: BTreeMap::new(), impls: Vec::new(), span }
+    }
+
+    /// Returns the impl of this trait for the given type, if there is one
+    pub fn find_impl(&self, typ: &Type) -> Option<&TraitImpl> {
+        self.impls.iter().find(|trait_impl| typ.matches_impl_type(&trait_impl.typ))
+    }
+}
+
+impl std::fmt::Display for Trait {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
@@ -9,7 +9,7 @@ use noirc_abi::{AbiFEType, AbiType};
 use noirc_errors::Span;
 
 use crate::{
-    node_interner::{FuncId, StructId},
+    node_interner::{EnumId, FuncId, StructId, TypeAliasId},
     util::vecmap,
     Ident, Signedness,
 };
@@ -188,6 +188,121 @@ impl std::fmt::Display for StructType {
     }
 }
 
+#[derive(Debug, Eq)]
+pub struct EnumType {
+    pub id: EnumId,
+    pub name: Ident,
+
+    /// Each variant along with the types of its payload fields, in declaration order.
+    /// Like struct fields, the payload types should only be accessed through
+    /// get_variant_fields() or get_variants() to apply any generic arguments.
+    variants: Vec<(Ident, Vec<Type>)>,
+
+    pub generics: Generics,
+    pub span: Span,
+}
+
+impl std::hash::Hash for EnumType {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl PartialEq for EnumType {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl EnumType {
+    pub fn new(id: EnumId, name: Ident, span: Span, generics: Generics) -> EnumType {
+        EnumType { id, name, variants: Vec::new(), generics, span }
+    }
+
+    pub fn set_variants(&mut self, variants: Vec<(Ident, Vec<Type>)>) {
+        assert!(self.variants.is_empty());
+        self.variants = variants;
+    }
+
+    pub fn num_variants(&self) -> usize {
+        self.variants.len()
+    }
+
+    pub fn variant_name(&self, index: usize) -> &Ident {
+        &self.variants[index].0
+    }
+
+    /// Returns the index of the variant with the given name, if there is one.
+    pub fn variant_index(&self, variant_name: &str) -> Option<usize> {
+        self.variants.iter().position(|(name, _)| name.0.contents == variant_name)
+    }
+
+    /// Returns the payload types of the variant at the given index.
+    pub fn get_variant_fields(&self, index: usize, generic_args: &[Type]) -> Vec<Type> {
+        let substitutions = self.substitutions(generic_args);
+        vecmap(&self.variants[index].1, |typ| typ.substitute(&substitutions))
+    }
+
+    /// Returns the payload types of every variant, in declaration order.
+    pub fn get_variants(&self, generic_args: &[Type]) -> Vec<Vec<Type>> {
+        let substitutions = self.substitutions(generic_args);
+        vecmap(&self.variants, |(_, fields)| vecmap(fields, |typ| typ.substitute(&substitutions)))
+    }
+
+    /// Instantiate this enum type, returning a Vec of new generic args
+    /// in the same order as self.generics
+    pub fn instantiate(&self, interner: &mut NodeInterner) -> Vec<Type> {
+        vecmap(&self.generics, |_| interner.next_type_variable())
+    }
+
+    fn substitutions(&self, generic_args: &[Type]) -> TypeBindings {
+        assert_eq!(self.generics.len(), generic_args.len());
+
+        self.generics
+            .iter()
+            .zip(generic_args)
+            .map(|((old_id, old_var), new)| (*old_id, (old_var.clone(), new.clone())))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for EnumType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// A type alias. Aliases are substituted away when they are resolved,
+/// so they never appear within a Type themselves.
+#[derive(Debug, Clone)]
+pub struct TypeAliasType {
+    pub id: TypeAliasId,
+    pub name: Ident,
+    pub typ: Type,
+    pub generics: Generics,
+    pub span: Span,
+}
+
+impl TypeAliasType {
+    pub fn new(id: TypeAliasId, name: Ident, span: Span, generics: Generics) -> TypeAliasType {
+        TypeAliasType { id, name, typ: Type::Error, generics, span }
+    }
+
+    /// Returns the aliased type with the given generic arguments substituted in
+    pub fn get_type(&self, generic_args: &[Type]) -> Type {
+        assert_eq!(self.generics.len(), generic_args.len());
+
+        let substitutions = self
+            .generics
+            .iter()
+            .zip(generic_args)
+            .map(|((old_id, old_var), new)| (*old_id, (old_var.clone(), new.clone())))
+            .collect();
+
+        self.typ.substitute(&substitutions)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Type {
     FieldElement(IsConst),
@@ -195,8 +310,10 @@ pub enum Type {
     Integer(IsConst, Signedness, u32), // u32 = Integer(unsigned, 32)
     PolymorphicInteger(IsConst, TypeVariable),
     Bool(IsConst),
+    String(Box<Type>), // String(4) = str<4>, which is laid out as [u8; 4]
     Unit,
     Struct(Shared<StructType>, Vec<Type>),
+    Enum(Shared<EnumType>, Vec<Type>),
     Tuple(Vec<Type>),
     TypeVariable(TypeVariable),
 
@@ -218,9 +335,60 @@ pub enum Type {
     /// bind to an integer without special checks to bind it to a non-type.
     ArrayLength(u64),
 
+    /// Arithmetic over type-level integers, such as the `N + 1` in `[Field; N + 1]`.
+    /// Only created when an operand depends on a generic, otherwise it is folded
+    /// into an ArrayLength during name resolution.
+    ArrayLengthOp(ArrayLengthOperator, Box<Type>, Box<Type>),
+
     Error,
 }
 
+/// The operators allowed within the length of an array or string type
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ArrayLengthOperator {
+    Add,
+    Subtract,
+    Multiply,
+}
+
+impl ArrayLengthOperator {
+    /// Returns None if the result would overflow or be negative
+    pub fn evaluate(self, lhs: u64, rhs: u64) -> Option<u64> {
+        match self {
+            ArrayLengthOperator::Add => lhs.checked_add(rhs),
+            ArrayLengthOperator::Subtract => lhs.checked_sub(rhs),
+            ArrayLengthOperator::Multiply => lhs.checked_mul(rhs),
+        }
+    }
+
+    /// Solve `lhs op rhs == result` for lhs
+    fn solve_lhs(self, result: u64, rhs: u64) -> Option<u64> {
+        match self {
+            ArrayLengthOperator::Add => result.checked_sub(rhs),
+            ArrayLengthOperator::Subtract => result.checked_add(rhs),
+            ArrayLengthOperator::Multiply => (rhs != 0 && result % rhs == 0).then(|| result / rhs),
+        }
+    }
+
+    /// Solve `lhs op rhs == result` for rhs
+    fn solve_rhs(self, result: u64, lhs: u64) -> Option<u64> {
+        match self {
+            ArrayLengthOperator::Subtract => lhs.checked_sub(result),
+            ArrayLengthOperator::Add | ArrayLengthOperator::Multiply => self.solve_lhs(result, lhs),
+        }
+    }
+}
+
+impl std::fmt::Display for ArrayLengthOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArrayLengthOperator::Add => write!(f, "+"),
+            ArrayLengthOperator::Subtract => write!(f, "-"),
+            ArrayLengthOperator::Multiply => write!(f, "*"),
+        }
+    }
+}
+
 pub type TypeVariable = Shared<TypeBinding>;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -428,6 +596,11 @@ impl Type {
         Type::field(span)
     }
 
+    /// The element type of the byte arrays that strings are laid out as
+    pub fn byte() -> Type {
+        Type::Integer(IsConst::No(None), Signedness::Unsigned, 8)
+    }
+
     pub fn type_variable(id: TypeVariableId) -> Type {
         Type::TypeVariable(Shared::new(TypeBinding::Unbound(id)))
     }
@@ -441,6 +614,7 @@ impl std::fmt::Display for Type {
             }
             Type::Array(len, typ) => match len.array_length() {
                 Some(len) => write!(f, "[{}; {}]", typ, len),
+                None if len.is_named_length() => write!(f, "[{}; {}]", typ, len),
                 None => write!(f, "[{}]", typ),
             },
             Type::Integer(is_const, sign, num_bits) => match sign {
@@ -465,11 +639,24 @@ impl std::fmt::Display for Type {
                     write!(f, "{}<{}>", s.borrow(), args.join(", "))
                 }
             }
+            Type::Enum(e, args) => {
+                let args = vecmap(args, |arg| arg.to_string());
+                if args.is_empty() {
+                    write!(f, "{}", e.borrow())
+                } else {
+                    write!(f, "{}<{}>", e.borrow(), args.join(", "))
+                }
+            }
             Type::Tuple(elements) => {
                 let elements = vecmap(elements, ToString::to_string);
                 write!(f, "({})", elements.join(", "))
             }
             Type::Bool(is_const) => write!(f, "{}bool", is_const),
+            Type::String(len) => match len.array_length() {
+                Some(len) => write!(f, "str<{}>", len),
+                None if len.is_named_length() => write!(f, "str<{}>", len),
+                None => write!(f, "str"),
+            },
             Type::Unit => write!(f, "()"),
             Type::Error => write!(f, "error"),
             Type::TypeVariable(id) => write!(f, "{}", id.borrow()),
@@ -479,6 +666,13 @@ impl std::fmt::Display for Type {
                 TypeBinding::Unbound(_) => write!(f, "{}", name),
             },
             Type::ArrayLength(n) => n.fmt(f),
+            Type::ArrayLengthOp(operator, lhs, rhs) => {
+                let operand = |typ: &Type| match typ {
+                    Type::ArrayLengthOp(..) => format!("({})", typ),
+                    other => other.to_string(),
+                };
+                write!(f, "{} {} {}", operand(lhs), operator, operand(rhs))
+            }
             Type::Forall(typevars, typ) => {
                 let typevars = vecmap(typevars, |(var, _)| var.to_string());
                 write!(f, "forall {}. {}", typevars.join(" "), typ)
@@ -725,11 +919,17 @@ impl Type {
                 Ok(())
             }
 
+            (ArrayLengthOp(..), _) | (_, ArrayLengthOp(..)) => {
+                self.try_unify_array_lengths(other, span)
+            }
+
             (Array(len_a, elem_a), Array(len_b, elem_b)) => {
                 len_a.try_unify(len_b, span)?;
                 elem_a.try_unify(elem_b, span)
             }
 
+            (String(len_a), String(len_b)) => len_a.try_unify(len_b, span),
+
             (Tuple(elems_a), Tuple(elems_b)) => {
                 if elems_a.len() != elems_b.len() {
                     Err(SpanKind::None)
@@ -755,6 +955,28 @@ impl Type {
                 }
             }
 
+            (Enum(enum_a, args_a), Enum(enum_b, args_b)) => {
+                if enum_a == enum_b {
+                    for (a, b) in args_a.iter().zip(args_b) {
+                        a.try_unify(b, span)?;
+                    }
+                    Ok(())
+                } else {
+                    Err(SpanKind::None)
+                }
+            }
+
+            (Function(args_a, ret_a), Function(args_b, ret_b)) => {
+                if args_a.len() != args_b.len() {
+                    return Err(SpanKind::None);
+                }
+
+                for (a, b) in args_a.iter().zip(args_b) {
+                    a.try_unify(b, span)?;
+                }
+                ret_a.try_unify(ret_b, span)
+            }
+
             (FieldElement(const_a), FieldElement(const_b)) => const_a.unify(const_b, span),
 
             (Integer(const_a, signed_a, bits_a), Integer(const_b, signed_b, bits_b)) => {
@@ -844,11 +1066,24 @@ impl Type {
                 other.try_bind_to(binding)
             }
 
+            (ArrayLengthOp(..), _) | (_, ArrayLengthOp(..)) => {
+                self.try_unify_array_lengths(other, span)
+            }
+
             (Array(len_a, elem_a), Array(len_b, elem_b)) => {
                 len_a.is_subtype_of(len_b, span)?;
                 elem_a.is_subtype_of(elem_b, span)
             }
 
+            (String(len_a), String(len_b)) => len_a.is_subtype_of(len_b, span),
+
+            // A str<N> is laid out as a [u8; N] so it may be passed anywhere a byte array is
+            // expected, e.g. to std::hash::sha256.
+            (String(len_a), Array(len_b, elem_b)) => {
+                len_a.is_subtype_of(len_b, span)?;
+                Type::byte().is_subtype_of(elem_b, span)
+            }
+
             (Tuple(elems_a), Tuple(elems_b)) => {
                 if elems_a.len() != elems_b.len() {
                     Err(SpanKind::None)
@@ -873,6 +1108,30 @@ impl Type {
                 }
             }
 
+            (Enum(enum_a, args_a), Enum(enum_b, args_b)) => {
+                if enum_a == enum_b && args_a.len() == args_b.len() {
+                    for (a, b) in args_a.iter().zip(args_b) {
+                        a.is_subtype_of(b, span)?;
+                    }
+                    Ok(())
+                } else {
+                    Err(SpanKind::None)
+                }
+            }
+
+            // Parameter types are checked in the opposite direction: a function accepting
+            // any Field may be used where one accepting only const Fields is expected.
+            (Function(args_a, ret_a), Function(args_b, ret_b)) => {
+                if args_a.len() != args_b.len() {
+                    return Err(SpanKind::None);
+                }
+
+                for (a, b) in args_a.iter().zip(args_b) {
+                    b.is_subtype_of(a, span)?;
+                }
+                ret_a.is_subtype_of(ret_b, span)
+            }
+
             (FieldElement(const_a), FieldElement(const_b)) => const_a.is_subtype_of(const_b, span),
 
             (Integer(const_a, signed_a, bits_a), Integer(const_b, signed_b, bits_b)) => {
@@ -917,12 +1176,65 @@ impl Type {
                 TypeBinding::Bound(binding) => binding.array_length(),
                 TypeBinding::Unbound(_) => None,
             },
-            Type::Array(len, _elem) => len.array_length(),
+            Type::Array(len, _elem) | Type::String(len) => len.array_length(),
             Type::ArrayLength(size) => Some(*size),
+            Type::ArrayLengthOp(operator, lhs, rhs) => {
+                operator.evaluate(lhs.array_length()?, rhs.array_length()?)
+            }
             _ => None,
         }
     }
 
+    /// True if this is an unknown array length which was written out in the source,
+    /// like the `N` in `[Field; N]`, rather than omitted as in `[Field]`.
+    fn is_named_length(&self) -> bool {
+        match self {
+            Type::NamedGeneric(_, name) => !name.is_empty(),
+            Type::TypeVariable(binding) => match &*binding.borrow() {
+                TypeBinding::Bound(binding) => binding.is_named_length(),
+                TypeBinding::Unbound(_) => false,
+            },
+            Type::ArrayLengthOp(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Unify two array lengths, at least one of which is an ArrayLengthOp. Lengths which
+    /// are both known are compared directly. Otherwise an operation over a single unknown
+    /// operand is solved for that operand if the other length is known, e.g. unifying
+    /// `N + 1` with `4` binds `N` to `3`.
+    fn try_unify_array_lengths(&self, other: &Type, span: Span) -> Result<(), SpanKind> {
+        use Type::*;
+        if let (Some(a), Some(b)) = (self.array_length(), other.array_length()) {
+            return if a == b { Ok(()) } else { Err(SpanKind::None) };
+        }
+
+        match (self, other) {
+            (ArrayLengthOp(op_a, lhs_a, rhs_a), ArrayLengthOp(op_b, lhs_b, rhs_b))
+                if op_a == op_b =>
+            {
+                lhs_a.is_subtype_of(lhs_b, span)?;
+                rhs_a.is_subtype_of(rhs_b, span)
+            }
+            (ArrayLengthOp(operator, lhs, rhs), other)
+            | (other, ArrayLengthOp(operator, lhs, rhs)) => {
+                let result = other.array_length().ok_or(SpanKind::None)?;
+                let solved = match (lhs.array_length(), rhs.array_length()) {
+                    (None, Some(rhs_value)) => {
+                        operator.solve_lhs(result, rhs_value).map(|n| (n, lhs))
+                    }
+                    (Some(lhs_value), None) => {
+                        operator.solve_rhs(result, lhs_value).map(|n| (n, rhs))
+                    }
+                    _ => None,
+                };
+                let (value, operand) = solved.ok_or(SpanKind::None)?;
+                ArrayLength(value).is_subtype_of(operand, span)
+            }
+            _ => unreachable!("try_unify_array_lengths called without an ArrayLengthOp"),
+        }
+    }
+
     // Note; use strict_eq instead of partial_eq when comparing field types
     // in this method, you most likely want to distinguish between public and private
     pub fn as_abi_type(&self, fe_type: AbiFEType) -> AbiType {
@@ -950,14 +1262,30 @@ impl Type {
                 TypeBinding::Bound(typ) => typ.as_abi_type(fe_type),
                 TypeBinding::Unbound(_) => Type::default_int_type(None).as_abi_type(fe_type),
             },
-            Type::Bool(_) => {
-                AbiType::Integer { sign: noirc_abi::Sign::Unsigned, width: 1, visibility: fe_type }
+            Type::Bool(_) => AbiType::Boolean(fe_type),
+            Type::String(size) => {
+                let size = size
+                    .array_length()
+                    .expect("Cannot have variable sized strings as a parameter to main");
+                AbiType::String { visibility: fe_type, length: size as u128 }
             }
             Type::Error => unreachable!(),
             Type::Unit => unreachable!(),
-            Type::ArrayLength(_) => unreachable!(),
-            Type::Struct(..) => todo!("as_abi_type not yet implemented for struct types"),
-            Type::Tuple(_) => todo!("as_abi_type not yet implemented for tuple types"),
+            Type::ArrayLength(_) | Type::ArrayLengthOp(..) => unreachable!(),
+            Type::Struct(def, args) => {
+                // The fields are sorted by name, which is also the order
+                // in which the struct is laid out after monomorphisation
+                let fields = def.borrow().get_fields(args);
+                let fields = vecmap(fields, |(name, typ)| (name, typ.as_abi_type(fe_type)));
+                AbiType::Struct { visibility: fe_type, fields }
+            }
+            Type::Enum(def, _) => {
+                panic!("Enum {} cannot be used as a parameter to main", def.borrow())
+            }
+            Type::Tuple(fields) => {
+                let fields = vecmap(fields, |typ| typ.as_abi_type(fe_type));
+                AbiType::Tuple { visibility: fe_type, fields }
+            }
             Type::TypeVariable(_) => unreachable!(),
             Type::NamedGeneric(..) => unreachable!(),
             Type::Forall(..) => unreachable!(),
@@ -1043,6 +1371,12 @@ impl Type {
                 let element = Box::new(element.substitute(type_bindings));
                 Type::Array(size, element)
             }
+            Type::String(size) => Type::String(Box::new(size.substitute(type_bindings))),
+            Type::ArrayLengthOp(operator, lhs, rhs) => {
+                let lhs = Box::new(lhs.substitute(type_bindings));
+                let rhs = Box::new(rhs.substitute(type_bindings));
+                Type::ArrayLengthOp(*operator, lhs, rhs)
+            }
             Type::PolymorphicInteger(_, binding)
             | Type::NamedGeneric(binding, _)
             | Type::TypeVariable(binding) => substitute_binding(binding),
@@ -1053,6 +1387,10 @@ impl Type {
                 let args = vecmap(args, |arg| arg.substitute(type_bindings));
                 Type::Struct(fields.clone(), args)
             }
+            Type::Enum(def, args) => {
+                let args = vecmap(args, |arg| arg.substitute(type_bindings));
+                Type::Enum(def.clone(), args)
+            }
             Type::Tuple(fields) => {
                 let fields = vecmap(fields, |field| field.substitute(type_bindings));
                 Type::Tuple(fields)
@@ -1086,7 +1424,11 @@ impl Type {
     fn occurs(&self, target_id: TypeVariableId) -> bool {
         match self {
             Type::Array(len, elem) => len.occurs(target_id) || elem.occurs(target_id),
-            Type::Struct(_, generic_args) => generic_args.iter().any(|arg| arg.occurs(target_id)),
+            Type::String(len) => len.occurs(target_id),
+            Type::ArrayLengthOp(_, lhs, rhs) => lhs.occurs(target_id) || rhs.occurs(target_id),
+            Type::Struct(_, generic_args) | Type::Enum(_, generic_args) => {
+                generic_args.iter().any(|arg| arg.occurs(target_id))
+            }
             Type::Tuple(fields) => fields.iter().any(|field| field.occurs(target_id)),
             Type::PolymorphicInteger(_, binding)
             | Type::NamedGeneric(binding, _)
@@ -1122,10 +1464,23 @@ impl Type {
             Array(size, elem) => {
                 Array(Box::new(size.follow_bindings()), Box::new(elem.follow_bindings()))
             }
+            String(size) => String(Box::new(size.follow_bindings())),
+            ArrayLengthOp(operator, lhs, rhs) => match self.array_length() {
+                Some(length) => ArrayLength(length),
+                None => {
+                    let lhs = Box::new(lhs.follow_bindings());
+                    let rhs = Box::new(rhs.follow_bindings());
+                    ArrayLengthOp(*operator, lhs, rhs)
+                }
+            },
             Struct(def, args) => {
                 let args = vecmap(args, |arg| arg.follow_bindings());
                 Struct(def.clone(), args)
             }
+            Enum(def, args) => {
+                let args = vecmap(args, |arg| arg.follow_bindings());
+                Enum(def.clone(), args)
+            }
             Tuple(args) => Tuple(vecmap(args, |arg| arg.follow_bindings())),
 
             TypeVariable(var) | PolymorphicInteger(_, var) | NamedGeneric(var, _) => {
@@ -1149,4 +1504,37 @@ impl Type {
             }
         }
     }
+    /// True if this type is the type a trait impl was declared for. Constness is
+    /// ignored since an impl applies to both const and non-const values of a type.
+    /// Unbound type variables never match an impl, except for polymorphic integers
+    /// which default to a Field.
+    pub fn matches_impl_type(&self, impl_type: &Type) -> bool {
+        use Type::*;
+        match (self.follow_bindings(), impl_type) {
+            (FieldElement(_), FieldElement(_)) => true,
+            (PolymorphicInteger(..), FieldElement(_)) => true,
+            (Bool(_), Bool(_)) => true,
+            (Unit, Unit) => true,
+            (Integer(_, sign_a, bits_a), Integer(_, sign_b, bits_b)) => {
+                sign_a == *sign_b && bits_a == *bits_b
+            }
+            (Array(len_a, elem_a), Array(len_b, elem_b)) => {
+                len_a.matches_impl_type(len_b) && elem_a.matches_impl_type(elem_b)
+            }
+            (String(len_a), String(len_b)) => len_a.matches_impl_type(len_b),
+            (ArrayLength(a), ArrayLength(b)) => a == *b,
+            (Struct(def_a, args_a), Struct(def_b, args_b)) => {
+                def_a == *def_b && args_a.iter().zip(args_b).all(|(a, b)| a.matches_impl_type(b))
+            }
+            (Enum(def_a, args_a), Enum(def_b, args_b)) => {
+                def_a == *def_b && args_a.iter().zip(args_b).all(|(a, b)| a.matches_impl_type(b))
+            }
+            (Tuple(elems_a), Tuple(elems_b)) => {
+                elems_a.len() == elems_b.len()
+                    && elems_a.iter().zip(elems_b).all(|(a, b)| a.matches_impl_type(b))
+            }
+            (NamedGeneric(var_a, _), NamedGeneric(var_b, _)) => var_a == *var_b,
+            _ => false,
+        }
+    }
 }
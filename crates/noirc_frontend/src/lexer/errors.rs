@@ -21,6 +21,10 @@ pub enum LexerErrorKind {
     LogicalAnd { span: Span },
     #[error("LogicalOr used instead of bitwise or")]
     LogicalOr { span: Span },
+    #[error("InvalidEscape : {:?} is not a valid escape sequence", found)]
+    InvalidEscape { span: Span, found: char },
+    #[error("UnterminatedStringLiteral")]
+    UnterminatedStringLiteral { span: Span },
 }
 
 impl LexerErrorKind {
@@ -33,6 +37,8 @@ impl LexerErrorKind {
             LexerErrorKind::TooManyBits { span, .. } => *span,
             LexerErrorKind::LogicalAnd { span } => *span,
             LexerErrorKind::LogicalOr { span } => *span,
+            LexerErrorKind::InvalidEscape { span, .. } => *span,
+            LexerErrorKind::UnterminatedStringLiteral { span } => *span,
         }
     }
 
@@ -68,7 +74,7 @@ impl LexerErrorKind {
             LexerErrorKind::TooManyBits { span, max, got } => (
                 "integer literal too large".to_string(),
                 format!(
-                    "The maximum number of bits needed to represent a field is {}, This integer type needs {} bits",
+                    "Integers of this signedness can have at most {} bits, This integer type needs {} bits",
                     max, got
                 ),
                 *span,
@@ -83,6 +89,16 @@ impl LexerErrorKind {
                 "Try `|` instead, or use `if` only if you require short-circuiting".to_string(),
                 *span,
             ),
+            LexerErrorKind::InvalidEscape { span, found } => (
+                "invalid escape sequence".to_string(),
+                format!(" \\{} is not a valid escape, expected one of \\n \\r \\t \\0 \\\\ or \\\"", found),
+                *span,
+            ),
+            LexerErrorKind::UnterminatedStringLiteral { span } => (
+                "unterminated string literal".to_string(),
+                "this string is missing its closing quote".to_string(),
+                *span,
+            ),
         }
     }
 }
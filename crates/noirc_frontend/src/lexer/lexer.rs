@@ -85,7 +85,7 @@ impl<'a> Lexer<'a> {
             let span = Span::new(self.position..self.position + 1);
             Err(LexerErrorKind::LogicalAnd { span })
         } else {
-            self.single_char_token(Token::Ampersand)
+            self.single_double_peek_token('=', Token::Ampersand, Token::AmpersandAssign)
         }
     }
 
@@ -94,7 +94,7 @@ impl<'a> Lexer<'a> {
             let span = Span::new(self.position..self.position + 1);
             Err(LexerErrorKind::LogicalOr { span })
         } else {
-            self.single_char_token(Token::Pipe)
+            self.single_double_peek_token('=', Token::Pipe, Token::PipeAssign)
         }
     }
 
@@ -114,19 +114,19 @@ impl<'a> Lexer<'a> {
             Some('-') => self.glue(Token::Minus),
             Some('&') => self.ampersand(),
             Some('|') => self.pipe(),
-            Some('%') => self.single_char_token(Token::Percent),
-            Some('^') => self.single_char_token(Token::Caret),
+            Some('%') => self.glue(Token::Percent),
+            Some('^') => self.glue(Token::Caret),
             Some(';') => self.single_char_token(Token::Semicolon),
-            Some('*') => self.single_char_token(Token::Star),
+            Some('*') => self.glue(Token::Star),
             Some('(') => self.single_char_token(Token::LeftParen),
             Some(')') => self.single_char_token(Token::RightParen),
             Some(',') => self.single_char_token(Token::Comma),
-            Some('+') => self.single_char_token(Token::Plus),
+            Some('+') => self.glue(Token::Plus),
             Some('{') => self.single_char_token(Token::LeftBrace),
             Some('}') => self.single_char_token(Token::RightBrace),
             Some('[') => self.single_char_token(Token::LeftBracket),
             Some(']') => self.single_char_token(Token::RightBracket),
-            Some('"') => Ok(self.eat_string_literal()),
+            Some('"') => self.eat_string_literal(),
             Some('#') => self.eat_attribute(),
             Some(ch) if ch.is_ascii_alphanumeric() || ch == '_' => self.eat_alpha_numeric(ch),
             Some(ch) => {
@@ -161,6 +161,22 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Called after lexing `<<` or `>>` starting at `start` to check whether
+    /// the shift is followed by `=`, forming a shift-assign operator.
+    fn shift_or_shift_assign(
+        &mut self,
+        start: Position,
+        shift: Token,
+        shift_assign: Token,
+    ) -> SpannedTokenResult {
+        if self.peek_char_is('=') {
+            self.next_char();
+            Ok(shift_assign.into_span(start, start + 2))
+        } else {
+            Ok(shift.into_span(start, start + 1))
+        }
+    }
+
     /// Given that some tokens can contain two characters, such as <= , !=, >=
     /// Glue will take the first character of the token and check if it can be glued onto the next character
     /// forming a double token
@@ -175,7 +191,7 @@ impl<'a> Lexer<'a> {
                     Ok(Token::LessEqual.into_span(start, start + 1))
                 } else if self.peek_char_is('<') {
                     self.next_char();
-                    Ok(Token::ShiftLeft.into_span(start, start + 1))
+                    self.shift_or_shift_assign(start, Token::ShiftLeft, Token::ShiftLeftAssign)
                 } else {
                     Ok(prev_token.into_single_span(start))
                 }
@@ -187,21 +203,47 @@ impl<'a> Lexer<'a> {
                     Ok(Token::GreaterEqual.into_span(start, start + 1))
                 } else if self.peek_char_is('>') {
                     self.next_char();
-                    Ok(Token::ShiftRight.into_span(start, start + 1))
+                    self.shift_or_shift_assign(start, Token::ShiftRight, Token::ShiftRightAssign)
                 } else {
                     Ok(prev_token.into_single_span(start))
                 }
             }
             Token::Bang => self.single_double_peek_token('=', prev_token, Token::NotEqual),
-            Token::Assign => self.single_double_peek_token('=', prev_token, Token::Equal),
-            Token::Minus => self.single_double_peek_token('>', prev_token, Token::Arrow),
+            Token::Assign => {
+                let start = self.position;
+                if self.peek_char_is('=') {
+                    self.next_char();
+                    Ok(Token::Equal.into_span(start, start + 1))
+                } else if self.peek_char_is('>') {
+                    self.next_char();
+                    Ok(Token::FatArrow.into_span(start, start + 1))
+                } else {
+                    Ok(prev_token.into_single_span(start))
+                }
+            }
+            Token::Minus => {
+                let start = self.position;
+                if self.peek_char_is('>') {
+                    self.next_char();
+                    Ok(Token::Arrow.into_span(start, start + 1))
+                } else if self.peek_char_is('=') {
+                    self.next_char();
+                    Ok(Token::MinusAssign.into_span(start, start + 1))
+                } else {
+                    Ok(prev_token.into_single_span(start))
+                }
+            }
+            Token::Plus => self.single_double_peek_token('=', prev_token, Token::PlusAssign),
+            Token::Star => self.single_double_peek_token('=', prev_token, Token::StarAssign),
+            Token::Percent => self.single_double_peek_token('=', prev_token, Token::PercentAssign),
+            Token::Caret => self.single_double_peek_token('=', prev_token, Token::CaretAssign),
             Token::Colon => self.single_double_peek_token(':', prev_token, Token::DoubleColon),
             Token::Slash => {
                 if self.peek_char_is('/') {
                     self.next_char();
                     return self.parse_comment();
                 }
-                Ok(spanned_prev_token)
+                self.single_double_peek_token('=', prev_token, Token::SlashAssign)
             }
             Token::Underscore => {
                 let next_char = self.peek_char();
@@ -345,11 +387,42 @@ impl<'a> Lexer<'a> {
         let integer_token = Token::Int(integer);
         Ok(integer_token.into_span(start, end))
     }
-    fn eat_string_literal(&mut self) -> SpannedToken {
-        let (str_literal, start_span, end_span) = self.eat_while(None, |ch| ch != '"');
-        let str_literal_token = Token::Str(str_literal);
-        self.next_char(); // Advance past the closing quote
-        str_literal_token.into_span(start_span, end_span)
+    fn eat_string_literal(&mut self) -> SpannedTokenResult {
+        let start = self.position;
+        let mut string = String::new();
+
+        while let Some(ch) = self.next_char() {
+            match ch {
+                '"' => {
+                    // The span excludes the closing quote, which has now been consumed
+                    let end = self.position - 1;
+                    return Ok(Token::Str(string).into_span(start, end));
+                }
+                '\\' => {
+                    let escaped = match self.next_char() {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('0') => '\0',
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some(found) => {
+                            return Err(LexerErrorKind::InvalidEscape {
+                                span: Span::exclusive(self.position - 1, self.position),
+                                found,
+                            })
+                        }
+                        None => break,
+                    };
+                    string.push(escaped);
+                }
+                ch => string.push(ch),
+            }
+        }
+
+        Err(LexerErrorKind::UnterminatedStringLiteral {
+            span: Span::exclusive(start, self.position),
+        })
     }
     fn parse_comment(&mut self) -> SpannedTokenResult {
         let _ = self.eat_while(None, |ch| ch != '\n');
@@ -374,7 +447,7 @@ impl<'a> Iterator for Lexer<'a> {
 
 #[test]
 fn test_single_double_char() {
-    let input = "! != + ( ) { } [ ] | , ; : :: < <= > >= & - -> . .. % / * = == << >>";
+    let input = "! != + ( ) { } [ ] | , ; : :: < <= > >= & - -> . .. % / * = == => << >>";
 
     let expected = vec![
         Token::Bang,
@@ -405,6 +478,7 @@ fn test_single_double_char() {
         Token::Star,
         Token::Assign,
         Token::Equal,
+        Token::FatArrow,
         Token::ShiftLeft,
         Token::ShiftRight,
         Token::EOF,
@@ -418,6 +492,40 @@ fn test_single_double_char() {
     }
 }
 
+#[test]
+fn test_compound_assign_operators() {
+    let input = "+= -= *= /= %= &= |= ^= <<= >>= x+=1";
+
+    let expected = vec![
+        Token::PlusAssign,
+        Token::MinusAssign,
+        Token::StarAssign,
+        Token::SlashAssign,
+        Token::PercentAssign,
+        Token::AmpersandAssign,
+        Token::PipeAssign,
+        Token::CaretAssign,
+        Token::ShiftLeftAssign,
+        Token::ShiftRightAssign,
+        Token::Ident("x".to_string()),
+        Token::PlusAssign,
+        Token::Int(1_i128.into()),
+        Token::EOF,
+    ];
+
+    let mut lexer = Lexer::new(input);
+
+    for token in expected.into_iter() {
+        let got = lexer.next_token().unwrap();
+        assert_eq!(got, token);
+    }
+
+    // `<<=` spans all three characters
+    let mut lexer = Lexer::new("<<=");
+    let got = lexer.next_token().unwrap();
+    assert_eq!(got.to_span(), Span::new(0..2));
+}
+
 #[test]
 fn test_custom_gate_syntax() {
     let input = "#[foreign(sha256)]#[foreign(blake2s)]#[builtin(sum)]";
@@ -435,6 +543,37 @@ fn test_custom_gate_syntax() {
     }
 }
 
+#[test]
+fn test_attribute() {
+    let input = "#[test] fn";
+
+    let expected = vec![Token::Attribute(Attribute::Test), Token::Keyword(Keyword::Fn)];
+
+    let mut lexer = Lexer::new(input);
+    for token in expected.into_iter() {
+        let got = lexer.next_token().unwrap();
+        assert_eq!(got, token);
+    }
+}
+
+#[test]
+fn test_arithmetic_attributes() {
+    let input = "#[checked] fn #[unchecked] fn";
+
+    let expected = vec![
+        Token::Attribute(Attribute::Checked),
+        Token::Keyword(Keyword::Fn),
+        Token::Attribute(Attribute::Unchecked),
+        Token::Keyword(Keyword::Fn),
+    ];
+
+    let mut lexer = Lexer::new(input);
+    for token in expected.into_iter() {
+        let got = lexer.next_token().unwrap();
+        assert_eq!(got, token);
+    }
+}
+
 #[test]
 fn test_int_type() {
     let input = "u16 i16 i108 u104.5";
@@ -491,6 +630,32 @@ fn test_eat_string_literal() {
         assert_eq!(got, token);
     }
 }
+
+#[test]
+fn test_eat_string_literal_escapes() {
+    let input = r#"let _word = "a\"b\n\t\\c\0""#;
+
+    let expected = vec![
+        Token::Keyword(Keyword::Let),
+        Token::Ident("_word".to_string()),
+        Token::Assign,
+        Token::Str("a\"b\n\t\\c\0".to_string()),
+    ];
+    let mut lexer = Lexer::new(input);
+
+    for token in expected.into_iter() {
+        let got = lexer.next_token().unwrap();
+        assert_eq!(got, token);
+    }
+}
+
+#[test]
+fn test_invalid_string_literals() {
+    for input in [r#""bad \q escape""#, r#""no closing quote"#] {
+        let mut lexer = Lexer::new(input);
+        assert!(lexer.next_token().is_err(), "expected {} to fail to lex", input);
+    }
+}
 #[test]
 fn test_eat_hex_int() {
     let input = "0x05";
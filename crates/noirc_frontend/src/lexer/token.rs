@@ -108,6 +108,8 @@ pub enum Token {
     RightBracket,
     // ->
     Arrow,
+    // =>
+    FatArrow,
     // |
     Pipe,
     // #
@@ -126,6 +128,26 @@ pub enum Token {
     Underscore,
     // =
     Assign,
+    // +=
+    PlusAssign,
+    // -=
+    MinusAssign,
+    // *=
+    StarAssign,
+    // /=
+    SlashAssign,
+    // %=
+    PercentAssign,
+    // &=
+    AmpersandAssign,
+    // |=
+    PipeAssign,
+    // ^=
+    CaretAssign,
+    // <<=
+    ShiftLeftAssign,
+    // >>=
+    ShiftRightAssign,
     #[allow(clippy::upper_case_acronyms)]
     EOF,
 
@@ -168,6 +190,7 @@ impl fmt::Display for Token {
             Token::LeftBracket => write!(f, "["),
             Token::RightBracket => write!(f, "]"),
             Token::Arrow => write!(f, "->"),
+            Token::FatArrow => write!(f, "=>"),
             Token::Pipe => write!(f, "|"),
             Token::Pound => write!(f, "#"),
             Token::Comma => write!(f, ","),
@@ -175,6 +198,16 @@ impl fmt::Display for Token {
             Token::DoubleColon => write!(f, "::"),
             Token::Semicolon => write!(f, ";"),
             Token::Assign => write!(f, "="),
+            Token::PlusAssign => write!(f, "+="),
+            Token::MinusAssign => write!(f, "-="),
+            Token::StarAssign => write!(f, "*="),
+            Token::SlashAssign => write!(f, "/="),
+            Token::PercentAssign => write!(f, "%="),
+            Token::AmpersandAssign => write!(f, "&="),
+            Token::PipeAssign => write!(f, "|="),
+            Token::CaretAssign => write!(f, "^="),
+            Token::ShiftLeftAssign => write!(f, "<<="),
+            Token::ShiftRightAssign => write!(f, ">>="),
             Token::Bang => write!(f, "!"),
             Token::Underscore => write!(f, "_"),
             Token::EOF => write!(f, "end of input"),
@@ -220,6 +253,25 @@ impl Token {
         matches!(self, Token::Ident(_))
     }
 
+    /// If this token is a compound assignment operator such as `+=`, returns the
+    /// token of the binary operator it applies, e.g. `+`.
+    pub fn compound_assign_operator(&self) -> Option<Token> {
+        let operator = match self {
+            Token::PlusAssign => Token::Plus,
+            Token::MinusAssign => Token::Minus,
+            Token::StarAssign => Token::Star,
+            Token::SlashAssign => Token::Slash,
+            Token::PercentAssign => Token::Percent,
+            Token::AmpersandAssign => Token::Ampersand,
+            Token::PipeAssign => Token::Pipe,
+            Token::CaretAssign => Token::Caret,
+            Token::ShiftLeftAssign => Token::ShiftLeft,
+            Token::ShiftRightAssign => Token::ShiftRight,
+            _ => return None,
+        };
+        Some(operator)
+    }
+
     pub(super) fn into_single_span(self, position: Position) -> SpannedToken {
         self.into_span(position, position)
     }
@@ -228,6 +280,9 @@ impl Token {
     }
 }
 
+/// The maximum bit size of an unsigned integer type
+pub const MAX_UNSIGNED_BITS: u32 = 256;
+
 #[derive(PartialEq, Eq, Hash, Debug, Clone, PartialOrd, Ord)]
 pub enum IntType {
     Unsigned(u32), // u32 = Unsigned(32)
@@ -265,7 +320,8 @@ impl IntType {
             Err(_) => return Ok(None),
         };
 
-        let max_bits = FieldElement::max_num_bits();
+        // Unsigned integers which do not fit in a field element are represented by several limbs
+        let max_bits = if is_signed { FieldElement::max_num_bits() } else { MAX_UNSIGNED_BITS };
 
         if str_as_u32 > max_bits {
             return Err(LexerErrorKind::TooManyBits { span, max: max_bits, got: str_as_u32 });
@@ -283,9 +339,14 @@ impl IntType {
 // Attributes are special language markers in the target language
 // An example of one is `#[SHA256]` . Currently only Foreign attributes are supported
 // Calls to functions which have the foreign attribute are executed in the host language
+// Functions marked with the test attribute are compiled and executed by `nargo test`
+// The checked and unchecked attributes select whether the integer arithmetic of a function may overflow
 pub enum Attribute {
     Foreign(String),
     Builtin(String),
+    Test,
+    Checked,
+    Unchecked,
 }
 
 impl fmt::Display for Attribute {
@@ -293,6 +354,9 @@ impl fmt::Display for Attribute {
         match *self {
             Attribute::Foreign(ref k) => write!(f, "#[foreign({})]", k),
             Attribute::Builtin(ref k) => write!(f, "#[builtin({})]", k),
+            Attribute::Test => write!(f, "#[test]"),
+            Attribute::Checked => write!(f, "#[checked]"),
+            Attribute::Unchecked => write!(f, "#[unchecked]"),
         }
     }
 }
@@ -306,6 +370,14 @@ impl Attribute {
             .filter(|string_segment| !string_segment.is_empty())
             .collect();
 
+        // The test, checked and unchecked attributes do not take an argument
+        match word_segments[..] {
+            ["test"] => return Ok(Token::Attribute(Attribute::Test)),
+            ["checked"] => return Ok(Token::Attribute(Attribute::Checked)),
+            ["unchecked"] => return Ok(Token::Attribute(Attribute::Unchecked)),
+            _ => (),
+        }
+
         if word_segments.len() != 2 {
             return Err(LexerErrorKind::MalformedFuncAttribute { span, found: word.to_owned() });
         }
@@ -325,7 +397,9 @@ impl Attribute {
 
     pub fn builtin(self) -> Option<String> {
         match self {
-            Attribute::Foreign(_) => None,
+            Attribute::Foreign(_) | Attribute::Test | Attribute::Checked | Attribute::Unchecked => {
+                None
+            }
             Attribute::Builtin(name) => Some(name),
         }
     }
@@ -333,7 +407,23 @@ impl Attribute {
     pub fn foreign(self) -> Option<String> {
         match self {
             Attribute::Foreign(name) => Some(name),
-            Attribute::Builtin(_) => None,
+            Attribute::Builtin(_) | Attribute::Test | Attribute::Checked | Attribute::Unchecked => {
+                None
+            }
+        }
+    }
+
+    pub fn is_test(&self) -> bool {
+        matches!(self, Attribute::Test)
+    }
+
+    /// Returns whether the integer arithmetic of a function with this attribute is checked,
+    /// or None when the attribute does not select it
+    pub fn checked_arithmetic(&self) -> Option<bool> {
+        match self {
+            Attribute::Checked => Some(true),
+            Attribute::Unchecked => Some(false),
+            _ => None,
         }
     }
 
@@ -350,6 +440,9 @@ impl AsRef<str> for Attribute {
         match self {
             Attribute::Foreign(string) => string,
             Attribute::Builtin(string) => string,
+            Attribute::Test => "test",
+            Attribute::Checked => "checked",
+            Attribute::Unchecked => "unchecked",
         }
     }
 }
@@ -361,11 +454,18 @@ pub enum Keyword {
     Crate,
     Fn,
     Struct,
+    Enum,
+    Trait,
+    Type,
     Impl,
     If,
     Mod,
     Else,
     While,
+    Break,
+    Continue,
+    Return,
+    Match,
     As,
     For,
     In,
@@ -376,6 +476,7 @@ pub enum Keyword {
     Pub,
     Const,
     Bool,
+    Str,
     //
     SetPub,
     //
@@ -392,6 +493,9 @@ impl fmt::Display for Keyword {
             Keyword::Crate => write!(f, "crate"),
             Keyword::Fn => write!(f, "fn"),
             Keyword::Struct => write!(f, "struct"),
+            Keyword::Enum => write!(f, "enum"),
+            Keyword::Trait => write!(f, "trait"),
+            Keyword::Type => write!(f, "type"),
             Keyword::Impl => write!(f, "impl"),
             Keyword::If => write!(f, "if"),
             Keyword::Mod => write!(f, "mod"),
@@ -399,6 +503,10 @@ impl fmt::Display for Keyword {
             Keyword::In => write!(f, "in"),
             Keyword::Else => write!(f, "else"),
             Keyword::While => write!(f, "while"),
+            Keyword::Break => write!(f, "break"),
+            Keyword::Continue => write!(f, "continue"),
+            Keyword::Return => write!(f, "return"),
+            Keyword::Match => write!(f, "match"),
             Keyword::Constrain => write!(f, "constrain"),
             Keyword::Mut => write!(f, "mut"),
             Keyword::Let => write!(f, "let"),
@@ -409,6 +517,7 @@ impl fmt::Display for Keyword {
             Keyword::Field => write!(f, "Field"),
             Keyword::Const => write!(f, "const"),
             Keyword::Bool => write!(f, "bool"),
+            Keyword::Str => write!(f, "str"),
         }
     }
 }
@@ -421,6 +530,9 @@ impl Keyword {
         let keyword = match word {
             "fn" => Keyword::Fn,
             "struct" => Keyword::Struct,
+            "enum" => Keyword::Enum,
+            "trait" => Keyword::Trait,
+            "type" => Keyword::Type,
             "impl" => Keyword::Impl,
             "dep" => Keyword::Dep,
             "crate" => Keyword::Crate,
@@ -430,6 +542,10 @@ impl Keyword {
             "in" => Keyword::In,
             "else" => Keyword::Else,
             "while" => Keyword::While,
+            "break" => Keyword::Break,
+            "continue" => Keyword::Continue,
+            "return" => Keyword::Return,
+            "match" => Keyword::Match,
             "constrain" => Keyword::Constrain,
             "let" => Keyword::Let,
             "as" => Keyword::As,
@@ -443,6 +559,7 @@ impl Keyword {
             // Native Types
             "Field" => Keyword::Field,
             "bool" => Keyword::Bool,
+            "str" => Keyword::Str,
 
             "true" => return Some(Token::Bool(true)),
             "false" => return Some(Token::Bool(false)),
@@ -14,6 +14,11 @@ pub enum Expression {
     Index(Index),
     Cast(Cast),
     For(For),
+    While(While),
+    Break,
+    Continue,
+    /// A return of the given value, which has the return type of the enclosing function
+    Return(Box<Expression>, Type),
     If(If),
     Tuple(Vec<Expression>),
     ExtractTupleField(Box<Expression>, usize),
@@ -22,7 +27,8 @@ pub enum Expression {
     CallLowLevel(CallLowLevel),
 
     Let(Let),
-    Constrain(Box<Expression>, Location),
+    /// A constraint, with the message to report if it fails
+    Constrain(Box<Expression>, Location, Option<String>),
     Assign(Assign),
     Semi(Box<Expression>),
 }
@@ -49,7 +55,19 @@ pub struct For {
 
     pub start_range: Box<Expression>,
     pub end_range: Box<Expression>,
+    /// The number of iterations the loop is unrolled to, when its range is not known at compile-time
+    pub max_iterations: Option<Box<Expression>>,
     pub block: Box<Expression>,
+    pub location: Location,
+}
+
+/// A loop which runs while its condition holds, up to `max_iterations` times
+#[derive(Debug, Clone)]
+pub struct While {
+    pub condition: Box<Expression>,
+    pub max_iterations: Box<Expression>,
+    pub block: Box<Expression>,
+    pub location: Location,
 }
 
 #[derive(Debug, Clone)]
@@ -57,7 +75,6 @@ pub enum Literal {
     Array(ArrayLiteral),
     Integer(FieldElement, Type),
     Bool(bool),
-    Str(String),
 }
 
 #[derive(Debug, Clone)]
@@ -73,6 +90,7 @@ pub struct Binary {
     pub lhs: Box<Expression>,
     pub operator: BinaryOp,
     pub rhs: Box<Expression>,
+    pub location: Location,
 }
 
 #[derive(Debug, Clone)]
@@ -86,6 +104,7 @@ pub struct If {
 pub struct Cast {
     pub lhs: Box<Expression>,
     pub r#type: Type,
+    pub location: Location,
 }
 
 #[derive(Debug, Clone)]
@@ -99,6 +118,7 @@ pub struct ArrayLiteral {
 pub struct Call {
     pub func_id: FuncId,
     pub arguments: Vec<Expression>,
+    pub location: Location,
 }
 
 #[derive(Debug, Clone)]
@@ -118,6 +138,7 @@ pub struct CallBuiltin {
 pub struct Index {
     pub collection: Box<Expression>,
     pub index: Box<Expression>,
+    pub location: Location,
 }
 
 #[derive(Debug, Clone)]
@@ -144,7 +165,7 @@ pub struct BinaryStatement {
 #[derive(Debug, Clone)]
 pub enum LValue {
     Ident(Ident),
-    Index { array: Box<LValue>, index: Box<Expression> },
+    Index { array: Box<LValue>, index: Box<Expression>, location: Location },
     MemberAccess { object: Box<LValue>, field_index: usize },
 }
 
@@ -157,6 +178,11 @@ pub struct Function {
     pub body: Expression,
 
     pub return_type: Type,
+    pub location: Location,
+
+    /// Whether integer arithmetic in this function is checked for overflows,
+    /// or None if it follows the setting of the package
+    pub checked_arithmetic: Option<bool>,
 }
 
 /// A monomorphised Type has all type variables removed
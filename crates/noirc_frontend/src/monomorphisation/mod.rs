@@ -1,5 +1,7 @@
 use std::collections::{BTreeMap, HashMap, VecDeque};
 
+use acvm::FieldElement;
+
 use crate::{
     hir_def::{
         expr::*,
@@ -8,7 +10,7 @@ use crate::{
     },
     node_interner::{self, NodeInterner, StmtId},
     util::vecmap,
-    FunctionKind, IsConst, TypeBinding, TypeBindings,
+    BinaryOpKind, FunctionKind, IsConst, Signedness, TypeBinding, TypeBindings,
 };
 
 use self::ast::{DefinitionId, FuncId, Program};
@@ -16,17 +18,28 @@ use self::ast::{DefinitionId, FuncId, Program};
 pub mod ast;
 pub mod printer;
 
-struct Monomorphiser {
+struct Monomorphiser<'interner> {
     // Store monomorphised globals and locals separately,
     // only locals are cleared on each function call and only globals are monomorphised.
     // Nested HashMaps in globals lets us avoid cloning HirTypes when calling .get()
     globals: HashMap<node_interner::FuncId, HashMap<HirType, FuncId>>,
     locals: HashMap<node_interner::DefinitionId, DefinitionId>,
 
-    /// Queue of functions to monomorphise next
-    queue: VecDeque<(node_interner::FuncId, FuncId, TypeBindings)>,
+    /// Queue of functions to monomorphise next, along with the function
+    /// values passed to any function-typed parameters
+    queue: VecDeque<(node_interner::FuncId, FuncId, TypeBindings, Vec<Option<FunctionValue>>)>,
+
+    /// The function each function-typed variable refers to. Function values are
+    /// resolved statically, only the environment of a closure exists at runtime.
+    function_values: HashMap<node_interner::DefinitionId, FunctionValue>,
+
+    /// Closures lifted into functions of their own, waiting to be added to the program
+    lambdas: Vec<ast::Function>,
 
-    interner: NodeInterner,
+    /// The arithmetic mode of the function being monomorphised, which its closures inherit
+    checked_arithmetic: Option<bool>,
+
+    interner: &'interner NodeInterner,
 
     next_local_id: u32,
     next_function_id: u32,
@@ -34,28 +47,45 @@ struct Monomorphiser {
 
 type HirType = crate::Type;
 
-pub fn monomorphise(main: node_interner::FuncId, interner: NodeInterner) -> Program {
+/// A function known at compile-time, along with the type of its environment
+/// if it is a closure. The environment is passed as the function's first argument.
+#[derive(Debug, Clone)]
+struct FunctionValue {
+    id: FuncId,
+    env: Option<ast::Type>,
+}
+
+pub fn monomorphise(main: node_interner::FuncId, interner: &NodeInterner) -> Program {
     let mut monomorphiser = Monomorphiser::new(interner);
     let mut functions = monomorphiser.compile_main(main);
+    functions.functions.append(&mut monomorphiser.lambdas);
 
     while !monomorphiser.queue.is_empty() {
-        let (next_fn_id, new_id, bindings) = monomorphiser.queue.pop_front().unwrap();
+        let (next_fn_id, new_id, bindings, function_args) =
+            monomorphiser.queue.pop_front().unwrap();
         monomorphiser.locals.clear();
 
         perform_instantiation_bindings(&bindings);
-        functions.push_function(monomorphiser.function(next_fn_id, new_id));
+        functions.push_function(monomorphiser.function(next_fn_id, new_id, function_args));
+        functions.functions.append(&mut monomorphiser.lambdas);
         undo_instantiation_bindings(bindings);
     }
 
+    // Lambdas are lifted while compiling the function they are defined in,
+    // so the functions may not be in the order of their ids
+    functions.functions.sort_by_key(|function| function.id.0);
     functions
 }
 
-impl Monomorphiser {
-    fn new(interner: NodeInterner) -> Monomorphiser {
+impl<'interner> Monomorphiser<'interner> {
+    fn new(interner: &'interner NodeInterner) -> Self {
         Monomorphiser {
             globals: HashMap::new(),
             locals: HashMap::new(),
             queue: VecDeque::new(),
+            function_values: HashMap::new(),
+            lambdas: Vec::new(),
+            checked_arithmetic: None,
             next_local_id: 0,
             next_function_id: 1,
             interner,
@@ -95,7 +125,7 @@ impl Monomorphiser {
     /// The main function is special, we need to check for a return type and if present,
     /// insert an extra constrain on the return value.
     fn compile_main(&mut self, main_id: node_interner::FuncId) -> Program {
-        let mut main = self.function(main_id, FuncId(0));
+        let mut main = self.function(main_id, FuncId(0), vec![]);
         let main_meta = self.interner.function_meta(&main_id);
 
         if main.return_type != ast::Type::Unit {
@@ -110,40 +140,78 @@ impl Monomorphiser {
                 Box::new(ast::Expression::Ident(ast::Ident { id, location: None, name, typ }));
             let rhs = Box::new(main.body);
             let operator = ast::BinaryOp::Equal;
-            let eq = ast::Expression::Binary(ast::Binary { operator, lhs, rhs });
-
             let location = self.interner.function_meta(&main_id).location;
-            main.body = ast::Expression::Constrain(Box::new(eq), location);
+            let eq = ast::Expression::Binary(ast::Binary { operator, lhs, rhs, location });
+
+            main.body = ast::Expression::Constrain(Box::new(eq), location, None);
         }
 
-        let abi = main_meta.into_abi(&self.interner);
+        let abi = main_meta.into_abi(self.interner);
         Program::new(main, abi)
     }
 
-    fn function(&mut self, f: node_interner::FuncId, id: FuncId) -> ast::Function {
+    fn function(
+        &mut self,
+        f: node_interner::FuncId,
+        id: FuncId,
+        function_args: Vec<Option<FunctionValue>>,
+    ) -> ast::Function {
         let meta = self.interner.function_meta(&f);
         let name = self.interner.function_name(&f).to_owned();
 
         let return_type = Self::convert_type(meta.return_type());
-        let parameters = self.parameters(meta.parameters);
+        let parameters = self.parameters(meta.parameters, function_args);
+        let checked_arithmetic =
+            meta.attributes.and_then(|attribute| attribute.checked_arithmetic());
+        self.checked_arithmetic = checked_arithmetic;
         let body = self.expr_infer(*self.interner.function(&f).as_expr());
+        let location = meta.location;
 
-        ast::Function { id, name, parameters, body, return_type }
+        ast::Function { id, name, parameters, body, return_type, location, checked_arithmetic }
     }
 
     /// Monomorphise each parameter, expanding tuple/struct patterns into multiple parameters
-    /// and binding any generic types found.
+    /// and binding any generic types found. `function_args` holds the function value passed
+    /// to each function-typed parameter, if any.
     fn parameters(
         &mut self,
         params: Parameters,
+        function_args: Vec<Option<FunctionValue>>,
     ) -> Vec<(ast::DefinitionId, bool, String, ast::Type)> {
         let mut new_params = Vec::with_capacity(params.len());
+        let mut function_args = function_args.into_iter();
         for parameter in params {
-            self.parameter(parameter.0, &parameter.1, &mut new_params);
+            match function_args.next().flatten() {
+                Some(value) => self.function_parameter(parameter.0, value, &mut new_params),
+                None => self.parameter(parameter.0, &parameter.1, &mut new_params),
+            }
         }
         new_params
     }
 
+    /// A function-typed parameter only needs to exist at runtime if the
+    /// function passed to it is a closure, to hold the closure's environment.
+    fn function_parameter(
+        &mut self,
+        param: HirPattern,
+        value: FunctionValue,
+        new_params: &mut Vec<(ast::DefinitionId, bool, String, ast::Type)>,
+    ) {
+        match param {
+            HirPattern::Identifier(ident) => {
+                if let Some(env_type) = &value.env {
+                    let new_id = self.next_definition_id();
+                    let name = self.interner.definition_name(ident.id).to_owned();
+                    new_params.push((new_id, false, name, env_type.clone()));
+                    self.define_local(ident.id, new_id);
+                }
+                self.function_values.insert(ident.id, value);
+            }
+            HirPattern::Mutable(pattern, _) => self.function_parameter(*pattern, value, new_params),
+            _ => unreachable!("function-typed parameters can only be bound to identifiers"),
+        }
+    }
+
     fn parameter(
         &mut self,
         param: HirPattern,
@@ -175,6 +243,7 @@ impl Monomorphiser {
                     self.parameter(field, typ, new_params);
                 }
             }
+            HirPattern::Variant(..) => unreachable!("variant patterns only appear in match arms"),
         }
     }
 
@@ -189,7 +258,7 @@ impl Monomorphiser {
 
         match self.interner.expression(&expr) {
             HirExpression::Ident(ident) => self.ident(ident),
-            HirExpression::Literal(HirLiteral::Str(contents)) => Literal(Str(contents)),
+            HirExpression::Literal(HirLiteral::Str(contents)) => Self::string_literal(contents),
             HirExpression::Literal(HirLiteral::Bool(value)) => Literal(Bool(value)),
             HirExpression::Literal(HirLiteral::Integer(value)) => {
                 let typ = Self::convert_type(&self.interner.id_type(expr));
@@ -211,12 +280,14 @@ impl Monomorphiser {
                 let lhs = Box::new(self.expr_infer(infix.lhs));
                 let rhs = Box::new(self.expr_infer(infix.rhs));
                 let operator = infix.operator.kind;
-                ast::Expression::Binary(ast::Binary { lhs, rhs, operator })
+                let location = self.interner.expr_location(&expr);
+                ast::Expression::Binary(ast::Binary { lhs, rhs, operator, location })
             }
 
             HirExpression::Index(index) => ast::Expression::Index(ast::Index {
                 collection: Box::new(self.expr_infer(index.collection)),
                 index: Box::new(self.expr_infer(index.index)),
+                location: self.interner.expr_location(&expr),
             }),
 
             HirExpression::MemberAccess(access) => {
@@ -226,15 +297,19 @@ impl Monomorphiser {
             }
 
             HirExpression::Call(call) => self.function_call(call, expr),
+            HirExpression::CallValue(call) => self.call_value(call, expr),
 
             HirExpression::Cast(cast) => ast::Expression::Cast(ast::Cast {
                 lhs: Box::new(self.expr_infer(cast.lhs)),
                 r#type: Self::convert_type(&cast.r#type),
+                location: self.interner.expr_location(&expr),
             }),
 
             HirExpression::For(for_expr) => {
                 let start = self.expr_infer(for_expr.start_range);
                 let end = self.expr_infer(for_expr.end_range);
+                let max_iterations =
+                    for_expr.max_iterations.map(|max| Box::new(self.expr_infer(max)));
                 let index_variable = self.next_definition_id();
                 self.define_local(for_expr.identifier.id, index_variable);
 
@@ -246,10 +321,27 @@ impl Monomorphiser {
                     index_type: Self::convert_type(&self.interner.id_type(for_expr.start_range)),
                     start_range: Box::new(start),
                     end_range: Box::new(end),
+                    max_iterations,
                     block,
+                    location: self.interner.expr_location(&expr),
                 })
             }
 
+            HirExpression::While(while_expr) => ast::Expression::While(ast::While {
+                condition: Box::new(
+                    self.expr(while_expr.condition, &HirType::Bool(IsConst::No(None))),
+                ),
+                max_iterations: Box::new(self.expr_infer(while_expr.max_iterations)),
+                block: Box::new(self.expr_infer(while_expr.block)),
+                location: self.interner.expr_location(&expr),
+            }),
+            HirExpression::Break => ast::Expression::Break,
+            HirExpression::Continue => ast::Expression::Continue,
+            HirExpression::Return(return_expr) => {
+                let typ = Self::convert_type(&self.interner.id_type(return_expr.value));
+                ast::Expression::Return(Box::new(self.expr_infer(return_expr.value)), typ)
+            }
+
             HirExpression::If(if_expr) => {
                 let cond = self.expr(if_expr.condition, &HirType::Bool(IsConst::No(None)));
                 let then = self.expr(if_expr.consequence, typ);
@@ -261,11 +353,28 @@ impl Monomorphiser {
                 })
             }
 
+            HirExpression::Match(match_expr) => self.match_expr(match_expr, typ),
+
             HirExpression::Tuple(fields) => {
                 let fields = vecmap(fields, |id| self.expr(id, typ));
                 ast::Expression::Tuple(fields)
             }
             HirExpression::Constructor(constructor) => self.constructor(constructor, typ),
+            HirExpression::Variant(variant) => self.variant(variant, typ),
+
+            // The generic is bound to the length it was instantiated with for this function
+            HirExpression::NumericGeneric(binding) => {
+                let value = HirType::TypeVariable(binding)
+                    .array_length()
+                    .expect("the type checker ensures numeric generics used as values are known");
+                let typ = Self::convert_type(&self.interner.id_type(expr));
+                Literal(Integer(FieldElement::from(value as u128), typ))
+            }
+
+            // The type checker only allows function values where they are handled by `function_value`
+            HirExpression::Lambda(_) | HirExpression::FunctionRef(_) => {
+                unreachable!("function values cannot be used as regular expressions")
+            }
 
             HirExpression::MethodCall(_) | HirExpression::Error => unreachable!(),
         }
@@ -277,7 +386,7 @@ impl Monomorphiser {
             HirStatement::Constrain(constrain) => {
                 let expr = self.expr(constrain.0, &HirType::Bool(IsConst::No(None)));
                 let location = self.interner.expr_location(&constrain.0);
-                ast::Expression::Constrain(Box::new(expr), location)
+                ast::Expression::Constrain(Box::new(expr), location, constrain.2)
             }
             HirStatement::Assign(assign) => self.assign(assign),
             HirStatement::Expression(expr) => self.expr_infer(expr),
@@ -287,11 +396,162 @@ impl Monomorphiser {
     }
 
     fn let_statement(&mut self, let_statement: HirLetStatement) -> ast::Expression {
+        if is_function_type(&self.interner.id_type(let_statement.expression)) {
+            return self.let_function(let_statement);
+        }
+
         let expr = self.expr_infer(let_statement.expression);
         let expected_type = self.interner.id_type(let_statement.expression);
         self.unpack_pattern(let_statement.pattern, expr, &expected_type)
     }
 
+    /// Binding a function value only binds its environment, if it has one. Calls through
+    /// the variable are resolved to the function itself.
+    fn let_function(&mut self, let_statement: HirLetStatement) -> ast::Expression {
+        let (value, env) = self.function_value(let_statement.expression);
+
+        let mut pattern = let_statement.pattern;
+        while let HirPattern::Mutable(inner, _) = pattern {
+            pattern = *inner;
+        }
+
+        let ident = match pattern {
+            HirPattern::Identifier(ident) => ident,
+            _ => unreachable!("function values can only be bound to identifiers"),
+        };
+
+        self.function_values.insert(ident.id, value);
+        match env {
+            Some(env) => {
+                let new_id = self.next_definition_id();
+                self.define_local(ident.id, new_id);
+                ast::Expression::Let(ast::Let {
+                    id: new_id,
+                    name: self.interner.definition_name(ident.id).to_owned(),
+                    expression: Box::new(env),
+                })
+            }
+            None => ast::Expression::Block(vec![]),
+        }
+    }
+
+    /// Resolves a function-typed expression to the function it refers to, along with
+    /// the expression for its environment if it is a closure.
+    fn function_value(
+        &mut self,
+        expr: node_interner::ExprId,
+    ) -> (FunctionValue, Option<ast::Expression>) {
+        match self.interner.expression(&expr) {
+            HirExpression::Lambda(lambda) => self.lambda(lambda, expr),
+            HirExpression::FunctionRef(func_id) => {
+                let meta = self.interner.function_meta(&func_id);
+                assert_eq!(meta.kind, FunctionKind::Normal, "only normal functions can be values");
+
+                let typ = self.interner.function_type(expr).follow_bindings();
+                let func_id = self.dispatch_trait_method(func_id, expr);
+                let id = self
+                    .lookup_global(func_id, &typ)
+                    .unwrap_or_else(|| self.queue_function(func_id, expr, typ, vec![]));
+
+                (FunctionValue { id, env: None }, None)
+            }
+            HirExpression::Ident(ident) => self.function_variable(&ident),
+            _ => unreachable!("the function a function value refers to must be known statically"),
+        }
+    }
+
+    fn function_variable(&mut self, ident: &HirIdent) -> (FunctionValue, Option<ast::Expression>) {
+        let value = self.function_values[&ident.id].clone();
+        let env = value.env.clone().map(|typ| {
+            let id = self.lookup_local(ident.id).unwrap();
+            let name = self.interner.definition_name(ident.id).to_owned();
+            ast::Expression::Ident(ast::Ident { location: Some(ident.location), id, name, typ })
+        });
+        (value, env)
+    }
+
+    /// Lifts a closure into a function of its own whose first parameter is the tuple of
+    /// captured variables. Captured function values only contribute their environment.
+    fn lambda(
+        &mut self,
+        lambda: HirLambda,
+        expr: node_interner::ExprId,
+    ) -> (FunctionValue, Option<ast::Expression>) {
+        let mut captures = Vec::with_capacity(lambda.captures.len());
+        let mut env_values = Vec::with_capacity(lambda.captures.len());
+        let mut env_types = Vec::with_capacity(lambda.captures.len());
+
+        for capture in lambda.captures {
+            let typ = self.interner.id_type(capture.id);
+            if is_function_type(&typ) {
+                if let (FunctionValue { env: Some(env_type), .. }, Some(env)) =
+                    self.function_variable(&capture)
+                {
+                    captures.push(capture);
+                    env_values.push(env);
+                    env_types.push(env_type);
+                }
+            } else {
+                captures.push(capture);
+                env_values.push(ast::Expression::Ident(self.local_ident(&capture).unwrap()));
+                env_types.push(Self::convert_type(&typ));
+            }
+        }
+
+        let env_type = ast::Type::Tuple(env_types);
+        let id = self.next_function_id();
+        let parent_locals = std::mem::take(&mut self.locals);
+
+        let env_id = self.next_definition_id();
+        let mut parameters = vec![(env_id, false, "env".to_owned(), env_type.clone())];
+        let mut body = Vec::with_capacity(captures.len() + 1);
+
+        for (i, capture) in captures.into_iter().enumerate() {
+            let name = self.interner.definition_name(capture.id).to_owned();
+            let env = ast::Ident {
+                location: None,
+                id: env_id,
+                name: "env".into(),
+                typ: env_type.clone(),
+            };
+            let new_id = self.next_definition_id();
+            self.define_local(capture.id, new_id);
+            body.push(ast::Expression::Let(ast::Let {
+                id: new_id,
+                name,
+                expression: Box::new(ast::Expression::ExtractTupleField(
+                    Box::new(ast::Expression::Ident(env)),
+                    i,
+                )),
+            }));
+        }
+
+        for (pattern, typ) in lambda.parameters {
+            self.parameter(pattern, &typ, &mut parameters);
+        }
+
+        body.push(self.expr_infer(lambda.body));
+        let return_type = Self::convert_type(&lambda.return_type);
+        let location = self.interner.expr_location(&expr);
+        let body = ast::Expression::Block(body);
+        let name = "lambda".to_owned();
+
+        let checked_arithmetic = self.checked_arithmetic;
+        self.lambdas.push(ast::Function {
+            id,
+            name,
+            parameters,
+            body,
+            return_type,
+            location,
+            checked_arithmetic,
+        });
+        self.locals = parent_locals;
+
+        let value = FunctionValue { id, env: Some(env_type) };
+        (value, Some(ast::Expression::Tuple(env_values)))
+    }
+
     fn constructor(
         &mut self,
         constructor: HirConstructorExpression,
@@ -328,6 +588,107 @@ impl Monomorphiser {
         ast::Expression::Block(new_exprs)
     }
 
+    /// Enums are represented as a tuple of the variant's tag followed by the payloads
+    /// of every variant in order. The payloads of the other variants are zeroed.
+    fn variant(&mut self, variant: HirVariantExpression, typ: &HirType) -> ast::Expression {
+        let tag = FieldElement::from(variant.variant_index as i128);
+        let mut fields =
+            vec![ast::Expression::Literal(ast::Literal::Integer(tag, ast::Type::Field))];
+        let mut arguments = variant.arguments.into_iter();
+
+        for (index, field_types) in unwrap_enum_type(typ).iter().enumerate() {
+            for field_type in field_types {
+                if index == variant.variant_index {
+                    fields.push(self.expr(arguments.next().unwrap(), field_type));
+                } else {
+                    fields.push(zeroed_value(&Self::convert_type(field_type)));
+                }
+            }
+        }
+
+        ast::Expression::Tuple(fields)
+    }
+
+    /// A match is compiled into a chain of ifs comparing the tag of the matched
+    /// value with the variant of each arm, in order.
+    fn match_expr(&mut self, match_expr: HirMatchExpression, typ: &HirType) -> ast::Expression {
+        let expr_type = self.interner.id_type(match_expr.expression);
+        let location = self.interner.expr_location(&match_expr.expression);
+
+        let fresh_id = self.next_definition_id();
+        let definition = ast::Expression::Let(ast::Let {
+            id: fresh_id,
+            name: "_".into(),
+            expression: Box::new(self.expr_infer(match_expr.expression)),
+        });
+
+        let value = ast::Ident {
+            location: None,
+            id: fresh_id,
+            name: "_".into(),
+            typ: Self::convert_type(&expr_type),
+        };
+
+        let mut arms = Vec::with_capacity(match_expr.arms.len());
+        for (pattern, branch) in match_expr.arms {
+            match pattern {
+                HirPattern::Variant(_, index, fields, _) => {
+                    let variants = unwrap_enum_type(&expr_type);
+                    let offset = 1 + variants[..index].iter().map(Vec::len).sum::<usize>();
+
+                    let tag = ast::Expression::ExtractTupleField(
+                        Box::new(ast::Expression::Ident(value.clone())),
+                        0,
+                    );
+                    let expected = FieldElement::from(index as i128);
+                    let condition = ast::Expression::Binary(ast::Binary {
+                        lhs: Box::new(tag),
+                        operator: BinaryOpKind::Equal,
+                        rhs: Box::new(ast::Expression::Literal(ast::Literal::Integer(
+                            expected,
+                            ast::Type::Field,
+                        ))),
+                        location,
+                    });
+
+                    let field_types = &variants[index];
+                    let mut block = Vec::with_capacity(fields.len() + 1);
+                    for (i, (field, field_type)) in fields.into_iter().zip(field_types).enumerate()
+                    {
+                        let field_value = ast::Expression::ExtractTupleField(
+                            Box::new(ast::Expression::Ident(value.clone())),
+                            offset + i,
+                        );
+                        block.push(self.unpack_pattern(field, field_value, field_type));
+                    }
+                    block.push(self.expr(branch, typ));
+                    arms.push((Some(condition), ast::Expression::Block(block)));
+                }
+                // Any other pattern matches every value, so the remaining arms are unreachable
+                pattern => {
+                    let value = ast::Expression::Ident(value.clone());
+                    let binding = self.unpack_pattern(pattern, value, &expr_type);
+                    let branch = self.expr(branch, typ);
+                    arms.push((None, ast::Expression::Block(vec![binding, branch])));
+                    break;
+                }
+            }
+        }
+
+        // The match is exhaustive, so the last arm needs no condition
+        let mut arms = arms.into_iter().rev();
+        let (_, mut result) = arms.next().unwrap();
+        for (condition, consequence) in arms {
+            result = ast::Expression::If(ast::If {
+                condition: Box::new(condition.unwrap()),
+                consequence: Box::new(consequence),
+                alternative: Some(Box::new(result)),
+            });
+        }
+
+        ast::Expression::Block(vec![definition, result])
+    }
+
     fn block(&mut self, statement_ids: Vec<StmtId>) -> ast::Expression {
         ast::Expression::Block(vecmap(statement_ids, |id| self.statement(id)))
     }
@@ -361,6 +722,7 @@ impl Monomorphiser {
                 });
                 self.unpack_tuple_pattern(value, patterns)
             }
+            HirPattern::Variant(..) => unreachable!("variant patterns only appear in match arms"),
         }
     }
 
@@ -410,12 +772,31 @@ impl Monomorphiser {
         }
     }
 
+    /// Strings are lowered to arrays of their utf-8 bytes
+    fn string_literal(contents: String) -> ast::Expression {
+        let element_type = ast::Type::Integer(Signedness::Unsigned, 8);
+        let contents = vecmap(contents.bytes(), |byte| {
+            let byte = FieldElement::from(byte as i128);
+            ast::Expression::Literal(ast::Literal::Integer(byte, element_type.clone()))
+        });
+        let length = contents.len() as u128;
+        ast::Expression::Literal(ast::Literal::Array(ast::ArrayLiteral {
+            length,
+            contents,
+            element_type,
+        }))
+    }
+
     /// Convert a non-tuple/struct type to a monomorphised type
     fn convert_type(typ: &HirType) -> ast::Type {
         match typ {
             HirType::FieldElement(_) => ast::Type::Field,
             HirType::Integer(_, sign, bits) => ast::Type::Integer(*sign, *bits),
             HirType::Bool(_) => ast::Type::Bool,
+            HirType::String(size) => {
+                let size = size.array_length().expect("the length of a string must be known");
+                ast::Type::Array(size, Box::new(ast::Type::Integer(Signedness::Unsigned, 8)))
+            }
             HirType::Unit => ast::Type::Unit,
 
             HirType::Array(size, element) => {
@@ -454,9 +835,17 @@ impl Monomorphiser {
                 ast::Type::Tuple(fields)
             }
 
+            HirType::Enum(def, args) => {
+                let variants = def.borrow().get_variants(args);
+                let mut fields = vec![ast::Type::Field];
+                fields.extend(variants.iter().flatten().map(Self::convert_type));
+                ast::Type::Tuple(fields)
+            }
+
             HirType::Function(_, _)
             | HirType::Forall(_, _)
             | HirType::ArrayLength(_)
+            | HirType::ArrayLengthOp(..)
             | HirType::Error => unreachable!("Unexpected type {} found", typ),
         }
     }
@@ -467,7 +856,7 @@ impl Monomorphiser {
         expr_id: node_interner::ExprId,
     ) -> ast::Expression {
         let typ = self.interner.function_type(expr_id).follow_bindings();
-        let arguments = vecmap(&call.arguments, |id| self.expr_infer(*id));
+        let (arguments, function_args) = self.call_arguments(&call.arguments);
         let func_id = call.func_id;
 
         let meta = self.interner.function_meta(&func_id);
@@ -481,13 +870,89 @@ impl Monomorphiser {
             }
             FunctionKind::Builtin => self.call_builtin(meta, arguments, call.arguments),
             FunctionKind::Normal => {
-                let func_id = self
-                    .lookup_global(func_id, &typ)
-                    .unwrap_or_else(|| self.queue_function(func_id, expr_id, typ));
+                let func_id = self.dispatch_trait_method(func_id, expr_id);
+
+                // Each call passing function values needs a specialization of its own
+                let func_id = if function_args.iter().all(Option::is_none) {
+                    self.lookup_global(func_id, &typ)
+                        .unwrap_or_else(|| self.queue_function(func_id, expr_id, typ, vec![]))
+                } else {
+                    self.queue_function(func_id, expr_id, typ, function_args)
+                };
+
+                let location = self.interner.expr_location(&expr_id);
+                ast::Expression::Call(ast::Call { func_id, arguments, location })
+            }
+        }
+    }
 
-                ast::Expression::Call(ast::Call { func_id, arguments })
+    /// Monomorphise the arguments of a call. Function values are not passed at runtime,
+    /// instead they are returned separately and only the environment of any closure
+    /// is passed as an argument.
+    fn call_arguments(
+        &mut self,
+        arguments: &[node_interner::ExprId],
+    ) -> (Vec<ast::Expression>, Vec<Option<FunctionValue>>) {
+        let mut new_arguments = Vec::with_capacity(arguments.len());
+        let mut function_args = Vec::with_capacity(arguments.len());
+
+        for argument in arguments {
+            if is_function_type(&self.interner.id_type(*argument)) {
+                let (value, env) = self.function_value(*argument);
+                new_arguments.extend(env);
+                function_args.push(Some(value));
+            } else {
+                new_arguments.push(self.expr_infer(*argument));
+                function_args.push(None);
             }
         }
+
+        (new_arguments, function_args)
+    }
+
+    /// A call to a function value is a direct call to the function it refers to,
+    /// with the closure's environment (if any) as the first argument.
+    fn call_value(
+        &mut self,
+        call: HirCallValueExpression,
+        expr_id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let (value, env) = self.function_value(call.function);
+        let (arguments, function_args) = self.call_arguments(&call.arguments);
+
+        assert!(
+            function_args.iter().all(Option::is_none),
+            "function values cannot be passed to other function values"
+        );
+
+        let arguments = env.into_iter().chain(arguments).collect();
+        let location = self.interner.expr_location(&expr_id);
+        ast::Expression::Call(ast::Call { func_id: value.id, arguments, location })
+    }
+
+    /// Calls to a trait method are redirected to the method of the impl for
+    /// the type that `Self` was instantiated to at this call site.
+    fn dispatch_trait_method(
+        &self,
+        func_id: node_interner::FuncId,
+        expr_id: node_interner::ExprId,
+    ) -> node_interner::FuncId {
+        let trait_id = match self.interner.get_trait_of_method(func_id) {
+            Some(trait_id) => trait_id,
+            None => return func_id,
+        };
+
+        let trait_def = self.interner.get_trait(trait_id);
+        let bindings = self.interner.get_instantiation_bindings(expr_id);
+        let self_type = bindings[&trait_def.self_type_variable].1.follow_bindings();
+        let method_name = self.interner.function_name(&func_id);
+
+        trait_def
+            .find_impl(&self_type)
+            .and_then(|trait_impl| trait_impl.methods.get(method_name).copied())
+            .unwrap_or_else(|| {
+                unreachable!("No impl of trait {} found for type {}", trait_def, self_type)
+            })
     }
 
     fn call_builtin(
@@ -515,14 +980,17 @@ impl Monomorphiser {
         id: node_interner::FuncId,
         expr_id: node_interner::ExprId,
         function_type: HirType,
+        function_args: Vec<Option<FunctionValue>>,
     ) -> FuncId {
         let new_id = self.next_function_id();
-        self.define_global(id, function_type, new_id);
+        if function_args.iter().all(Option::is_none) {
+            self.define_global(id, function_type, new_id);
+        }
 
         let bindings = self.interner.get_instantiation_bindings(expr_id);
         let bindings = self.follow_bindings(bindings);
 
-        self.queue.push_back((id, new_id, bindings));
+        self.queue.push_back((id, new_id, bindings, function_args));
         new_id
     }
 
@@ -558,13 +1026,18 @@ impl Monomorphiser {
             }
             HirLValue::Index { array, index } => {
                 let array = Box::new(self.lvalue(*array));
+                let location = self.interner.expr_location(&index);
                 let index = Box::new(self.expr_infer(index));
-                ast::LValue::Index { array, index }
+                ast::LValue::Index { array, index, location }
             }
         }
     }
 }
 
+fn is_function_type(typ: &HirType) -> bool {
+    matches!(typ.follow_bindings(), HirType::Function(..))
+}
+
 fn unwrap_tuple_type(typ: &HirType) -> Vec<HirType> {
     match typ {
         HirType::Tuple(fields) => fields.clone(),
@@ -587,6 +1060,38 @@ fn unwrap_struct_type(typ: &HirType) -> BTreeMap<String, HirType> {
     }
 }
 
+fn unwrap_enum_type(typ: &HirType) -> Vec<Vec<HirType>> {
+    match typ {
+        HirType::Enum(def, args) => def.borrow().get_variants(args),
+        HirType::TypeVariable(binding) => match &*binding.borrow() {
+            TypeBinding::Bound(binding) => unwrap_enum_type(binding),
+            TypeBinding::Unbound(_) => unreachable!(),
+        },
+        other => unreachable!("unwrap_enum_type: expected enum found {}", other),
+    }
+}
+
+/// A placeholder value of the given type, used for the payloads of the enum
+/// variants which were not constructed.
+fn zeroed_value(typ: &ast::Type) -> ast::Expression {
+    use ast::Expression::Literal;
+    use ast::Literal::*;
+
+    match typ {
+        ast::Type::Field | ast::Type::Integer(..) => {
+            Literal(Integer(FieldElement::zero(), typ.clone()))
+        }
+        ast::Type::Bool => Literal(Bool(false)),
+        ast::Type::Unit => ast::Expression::Block(vec![]),
+        ast::Type::Array(length, element_type) => Literal(Array(ast::ArrayLiteral {
+            length: *length as u128,
+            contents: vecmap(0..*length, |_| zeroed_value(element_type)),
+            element_type: element_type.as_ref().clone(),
+        })),
+        ast::Type::Tuple(fields) => ast::Expression::Tuple(vecmap(fields, zeroed_value)),
+    }
+}
+
 fn perform_instantiation_bindings(bindings: &TypeBindings) {
     for (var, binding) in bindings.values() {
         *var.borrow_mut() = TypeBinding::Bound(binding.clone());
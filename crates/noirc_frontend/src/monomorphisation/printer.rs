@@ -47,6 +47,13 @@ impl AstPrinter {
                 write!(f, " as {})", cast.r#type)
             }
             Expression::For(for_expr) => self.print_for(for_expr, f),
+            Expression::While(while_expr) => self.print_while(while_expr, f),
+            Expression::Break => write!(f, "break"),
+            Expression::Continue => write!(f, "continue"),
+            Expression::Return(value, _) => {
+                write!(f, "return ")?;
+                self.print_expr(value, f)
+            }
             Expression::If(if_expr) => self.print_if(if_expr, f),
             Expression::Tuple(tuple) => self.print_tuple(tuple, f),
             Expression::ExtractTupleField(expr, index) => {
@@ -60,9 +67,13 @@ impl AstPrinter {
                 write!(f, "let {}${} = ", let_expr.name, let_expr.id.0)?;
                 self.print_expr(&let_expr.expression, f)
             }
-            Expression::Constrain(expr, _) => {
+            Expression::Constrain(expr, _, message) => {
                 write!(f, "constrain ")?;
-                self.print_expr(expr, f)
+                self.print_expr(expr, f)?;
+                match message {
+                    Some(message) => write!(f, ", {:?}", message),
+                    None => Ok(()),
+                }
             }
             Expression::Assign(assign) => {
                 self.print_lvalue(&assign.lvalue, f)?;
@@ -97,7 +108,6 @@ impl AstPrinter {
             }
             super::ast::Literal::Integer(x, _) => write!(f, "{}", x),
             super::ast::Literal::Bool(x) => write!(f, "{}", x),
-            super::ast::Literal::Str(s) => write!(f, "{}", s),
         }
     }
 
@@ -183,6 +193,10 @@ impl AstPrinter {
         self.print_expr(&for_expr.start_range, f)?;
         write!(f, " .. ")?;
         self.print_expr(&for_expr.end_range, f)?;
+        if let Some(max_iterations) = &for_expr.max_iterations {
+            write!(f, " max ")?;
+            self.print_expr(max_iterations, f)?;
+        }
         write!(f, " {{")?;
 
         self.indent_level += 1;
@@ -192,6 +206,24 @@ impl AstPrinter {
         write!(f, "}}")
     }
 
+    fn print_while(
+        &mut self,
+        while_expr: &super::ast::While,
+        f: &mut Formatter,
+    ) -> Result<(), std::fmt::Error> {
+        write!(f, "while ")?;
+        self.print_expr(&while_expr.condition, f)?;
+        write!(f, " max ")?;
+        self.print_expr(&while_expr.max_iterations, f)?;
+        write!(f, " {{")?;
+
+        self.indent_level += 1;
+        self.print_expr_expect_block(&while_expr.block, f)?;
+        self.indent_level -= 1;
+        self.next_line(f)?;
+        write!(f, "}}")
+    }
+
     fn print_if(
         &mut self,
         if_expr: &super::ast::If,
@@ -273,7 +305,7 @@ impl AstPrinter {
     fn print_lvalue(&mut self, lvalue: &LValue, f: &mut Formatter) -> std::fmt::Result {
         match lvalue {
             LValue::Ident(ident) => write!(f, "{}${}", ident.name, ident.id.0),
-            LValue::Index { array, index } => {
+            LValue::Index { array, index, .. } => {
                 self.print_lvalue(array, f)?;
                 write!(f, "[")?;
                 self.print_expr(index, f)?;
@@ -1,4 +1,5 @@
 use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
 
 use arena::{Arena, Index};
 use fm::FileId;
@@ -6,10 +7,11 @@ use noirc_errors::{Location, Span, Spanned};
 
 use crate::ast::Ident;
 use crate::graph::CrateId;
-use crate::hir::def_collector::dc_crate::UnresolvedStruct;
+use crate::hir::def_collector::dc_crate::{UnresolvedEnum, UnresolvedStruct, UnresolvedTypeAlias};
 use crate::hir::def_map::{LocalModuleId, ModuleId};
 use crate::hir_def::stmt::HirLetStatement;
-use crate::hir_def::types::{StructType, Type};
+use crate::hir_def::traits::{Trait, TraitImpl};
+use crate::hir_def::types::{EnumType, StructType, Type, TypeAliasType};
 use crate::hir_def::{
     expr::HirExpression,
     function::{FuncMeta, HirFunction},
@@ -78,6 +80,40 @@ impl StructId {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct EnumId(pub ModuleId);
+
+impl EnumId {
+    //dummy id for error reporting
+    // This can be anything, as the program will ultimately fail
+    // after resolution
+    pub fn dummy_id() -> EnumId {
+        EnumId(ModuleId { krate: CrateId::dummy_id(), local_id: LocalModuleId::dummy_id() })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, PartialOrd, Ord)]
+pub struct TypeAliasId(pub usize);
+
+impl TypeAliasId {
+    //dummy id for error reporting
+    pub fn dummy_id() -> TypeAliasId {
+        TypeAliasId(std::usize::MAX)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct TraitId(pub ModuleId);
+
+impl TraitId {
+    //dummy id for error reporting
+    // This can be anything, as the program will ultimately fail
+    // after resolution
+    pub fn dummy_id() -> TraitId {
+        TraitId(ModuleId { krate: CrateId::dummy_id(), local_id: LocalModuleId::dummy_id() })
+    }
+}
+
 macro_rules! into_index {
     ($id_type:ty) => {
         impl From<$id_type> for Index {
@@ -149,6 +185,35 @@ pub struct NodeInterner {
     // methods from impls to the type.
     structs: HashMap<StructId, Shared<StructType>>,
 
+    // Enum map. Like structs, each enum definition is shared across the type nodes referring to it.
+    enums: HashMap<EnumId, Shared<EnumType>>,
+
+    // Type alias map, indexed by TypeAliasId. Unlike structs and enums, aliases are not shared
+    // since they are substituted away wherever they are referred to.
+    type_aliases: Vec<TypeAliasType>,
+
+    // Trait map. Impls are appended to each trait as they are resolved.
+    traits: HashMap<TraitId, Trait>,
+
+    /// Maps each method declared within a trait back to the trait itself, so that
+    /// calls to it can be dispatched to the right impl during monomorphisation.
+    trait_methods: HashMap<FuncId, TraitId>,
+
+    /// The traits each generic type variable is bounded by
+    trait_bounds: HashMap<TypeVariableId, Vec<TraitId>>,
+
+    /// Trait bounds which could not be checked at their call site since the type they
+    /// apply to was not yet known. These are checked once the whole function is type checked.
+    delayed_trait_bounds: Vec<(TraitId, Type, Span)>,
+
+    /// The numeric generics used as values within their function's body, mapped to their names.
+    /// Calls to these functions must determine the value of each such generic.
+    numeric_generic_values: HashMap<TypeVariableId, Rc<String>>,
+
+    /// Numeric generics used as values whose instantiation at a call site must be known
+    /// once the calling function is type checked.
+    delayed_numeric_generics: Vec<(Rc<String>, Type, Span)>,
+
     /// Map from ExprId (referring to a Function/Method call) to its corresponding TypeBindings,
     /// filled out during type checking from instantiated variables. Used during monomorphisation
     /// to map callsite types back onto function parameter types, and undo this binding as needed.
@@ -192,6 +257,14 @@ impl Default for NodeInterner {
             definitions: vec![],
             id_to_type: HashMap::new(),
             structs: HashMap::new(),
+            enums: HashMap::new(),
+            type_aliases: Vec::new(),
+            traits: HashMap::new(),
+            trait_methods: HashMap::new(),
+            trait_bounds: HashMap::new(),
+            delayed_trait_bounds: Vec::new(),
+            numeric_generic_values: HashMap::new(),
+            delayed_numeric_generics: Vec::new(),
             instantiation_bindings: HashMap::new(),
             function_types: HashMap::new(),
             field_indices: HashMap::new(),
@@ -258,7 +331,89 @@ impl NodeInterner {
         f(&mut value)
     }
 
+    pub fn push_empty_enum(&mut self, type_id: EnumId, typ: &UnresolvedEnum) {
+        self.enums.insert(
+            type_id,
+            Shared::new(EnumType::new(
+                type_id,
+                typ.enum_def.name.clone(),
+                typ.enum_def.span,
+                vecmap(&typ.enum_def.generics, |_| {
+                    // Temporary type variable ids, as with structs in push_empty_struct
+                    let id = TypeVariableId(0);
+                    (id, Shared::new(TypeBinding::Unbound(id)))
+                }),
+            )),
+        );
+    }
+
+    pub fn update_enum(&mut self, type_id: EnumId, f: impl FnOnce(&mut EnumType)) {
+        let mut value = self.enums.get_mut(&type_id).unwrap().borrow_mut();
+        f(&mut value)
+    }
+
+    pub fn push_type_alias(&mut self, typ: &UnresolvedTypeAlias) -> TypeAliasId {
+        let type_id = TypeAliasId(self.type_aliases.len());
+
+        self.type_aliases.push(TypeAliasType::new(
+            type_id,
+            typ.type_alias_def.name.clone(),
+            typ.type_alias_def.span,
+            vecmap(&typ.type_alias_def.generics, |_| {
+                // Temporary type variable ids, as with structs in push_empty_struct
+                let id = TypeVariableId(0);
+                (id, Shared::new(TypeBinding::Unbound(id)))
+            }),
+        ));
+
+        type_id
+    }
+
+    pub fn update_type_alias(&mut self, type_id: TypeAliasId, f: impl FnOnce(&mut TypeAliasType)) {
+        f(&mut self.type_aliases[type_id.0])
+    }
+
     /// Returns the interned statement corresponding to `stmt_id`
+    pub fn push_trait(&mut self, trait_def: Trait) {
+        self.traits.insert(trait_def.id, trait_def);
+    }
+
+    pub fn add_trait_impl(&mut self, trait_id: TraitId, trait_impl: TraitImpl) {
+        self.traits.get_mut(&trait_id).unwrap().impls.push(trait_impl);
+    }
+
+    pub fn add_trait_method(&mut self, func_id: FuncId, trait_id: TraitId) {
+        self.trait_methods.insert(func_id, trait_id);
+    }
+
+    pub fn add_trait_bound(&mut self, type_variable: TypeVariableId, trait_id: TraitId) {
+        self.trait_bounds.entry(type_variable).or_default().push(trait_id);
+    }
+
+    pub fn delay_trait_bound_check(&mut self, trait_id: TraitId, typ: Type, span: Span) {
+        self.delayed_trait_bounds.push((trait_id, typ, span));
+    }
+
+    pub fn take_delayed_trait_bounds(&mut self) -> Vec<(TraitId, Type, Span)> {
+        std::mem::take(&mut self.delayed_trait_bounds)
+    }
+
+    pub fn add_numeric_generic_value(&mut self, type_variable: TypeVariableId, name: Rc<String>) {
+        self.numeric_generic_values.insert(type_variable, name);
+    }
+
+    pub fn get_numeric_generic_value(&self, type_variable: TypeVariableId) -> Option<&Rc<String>> {
+        self.numeric_generic_values.get(&type_variable)
+    }
+
+    pub fn delay_numeric_generic_check(&mut self, name: Rc<String>, typ: Type, span: Span) {
+        self.delayed_numeric_generics.push((name, typ, span));
+    }
+
+    pub fn take_delayed_numeric_generics(&mut self) -> Vec<(Rc<String>, Type, Span)> {
+        std::mem::take(&mut self.delayed_numeric_generics)
+    }
+
     pub fn update_statement(&mut self, stmt_id: &StmtId, f: impl FnOnce(&mut HirStatement)) {
         let def =
             self.nodes.get_mut(stmt_id.0).expect("ice: all statement ids should have definitions");
@@ -438,6 +593,45 @@ impl NodeInterner {
         self.structs[&id].clone()
     }
 
+    pub fn get_enum(&self, id: EnumId) -> Shared<EnumType> {
+        self.enums[&id].clone()
+    }
+
+    pub fn get_type_alias(&self, id: TypeAliasId) -> &TypeAliasType {
+        &self.type_aliases[id.0]
+    }
+
+    pub fn get_trait(&self, id: TraitId) -> &Trait {
+        &self.traits[&id]
+    }
+
+    /// Returns the trait the given function was declared in, if it is a trait method
+    pub fn get_trait_of_method(&self, func_id: FuncId) -> Option<TraitId> {
+        self.trait_methods.get(&func_id).copied()
+    }
+
+    pub fn get_trait_bounds(&self, type_variable: TypeVariableId) -> &[TraitId] {
+        self.trait_bounds.get(&type_variable).map_or(&[], |bounds| bounds.as_slice())
+    }
+
+    /// Searches the impls of every trait for a method with the given name
+    /// implemented for the given type. More than one trait may implement
+    /// a method of the same name, in which case the call is ambiguous.
+    pub fn lookup_trait_impl_methods(
+        &self,
+        typ: &Type,
+        method_name: &str,
+    ) -> Vec<(TraitId, FuncId)> {
+        self.traits
+            .values()
+            .filter_map(|trait_def| {
+                let trait_impl = trait_def.find_impl(typ)?;
+                let method = trait_impl.methods.get(method_name)?;
+                Some((trait_def.id, *method))
+            })
+            .collect()
+    }
+
     pub fn get_global_const(&self, stmt_id: &StmtId) -> Option<GlobalConstInfo> {
         self.global_constants.get(stmt_id).cloned()
     }
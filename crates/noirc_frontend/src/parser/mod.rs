@@ -3,8 +3,9 @@ mod errors;
 mod parser;
 
 use crate::token::{Keyword, Token};
-use crate::{ast::ImportStatement, Expression, NoirStruct};
-use crate::{Ident, LetStatement, NoirFunction, NoirImpl, Recoverable, Statement};
+use crate::util::vecmap;
+use crate::{ast::ImportStatement, Expression, NoirEnum, NoirStruct, NoirTrait, NoirTraitImpl};
+use crate::{Ident, LetStatement, NoirFunction, NoirImpl, NoirTypeAlias, Recoverable, Statement};
 
 use chumsky::prelude::*;
 use chumsky::primitive::Container;
@@ -16,9 +17,14 @@ pub use parser::parse_program;
 pub(crate) enum TopLevelStatement {
     Function(NoirFunction),
     Module(Ident),
-    Import(ImportStatement),
+    /// Every import within a single `use`, which may name several items at once
+    Import(Vec<ImportStatement>),
     Struct(NoirStruct),
+    Enum(NoirEnum),
+    TypeAlias(NoirTypeAlias),
     Impl(NoirImpl),
+    Trait(NoirTrait),
+    TraitImpl(NoirTraitImpl),
     SubModule(SubModule),
     GlobalConst(LetStatement),
     Error,
@@ -197,7 +203,11 @@ pub struct ParsedModule {
     pub imports: Vec<ImportStatement>,
     pub functions: Vec<NoirFunction>,
     pub types: Vec<NoirStruct>,
+    pub enums: Vec<NoirEnum>,
+    pub type_aliases: Vec<NoirTypeAlias>,
     pub impls: Vec<NoirImpl>,
+    pub traits: Vec<NoirTrait>,
+    pub trait_impls: Vec<NoirTraitImpl>,
     pub module_decls: Vec<Ident>,
     pub submodules: Vec<SubModule>,
     pub global_constants: Vec<LetStatement>,
@@ -218,10 +228,26 @@ impl ParsedModule {
         self.types.push(typ);
     }
 
+    fn push_enum(&mut self, typ: NoirEnum) {
+        self.enums.push(typ);
+    }
+
+    fn push_type_alias(&mut self, type_alias: NoirTypeAlias) {
+        self.type_aliases.push(type_alias);
+    }
+
     fn push_impl(&mut self, r#impl: NoirImpl) {
         self.impls.push(r#impl);
     }
 
+    fn push_trait(&mut self, r#trait: NoirTrait) {
+        self.traits.push(r#trait);
+    }
+
+    fn push_trait_impl(&mut self, trait_impl: NoirTraitImpl) {
+        self.trait_impls.push(trait_impl);
+    }
+
     fn push_import(&mut self, import_stmt: ImportStatement) {
         self.imports.push(import_stmt);
     }
@@ -271,6 +297,7 @@ impl Precedence {
             Token::Plus => Precedence::Sum,
             Token::Minus => Precedence::Sum,
             Token::Slash => Precedence::Product,
+            Token::Percent => Precedence::Product,
             Token::Star => Precedence::Product,
             _ => return None,
         };
@@ -300,9 +327,16 @@ impl std::fmt::Display for TopLevelStatement {
         match self {
             TopLevelStatement::Function(fun) => fun.fmt(f),
             TopLevelStatement::Module(m) => write!(f, "mod {}", m),
-            TopLevelStatement::Import(i) => i.fmt(f),
+            TopLevelStatement::Import(imports) => {
+                let imports = vecmap(imports, ToString::to_string);
+                write!(f, "{}", imports.join("; "))
+            }
             TopLevelStatement::Struct(s) => s.fmt(f),
+            TopLevelStatement::Enum(e) => e.fmt(f),
+            TopLevelStatement::TypeAlias(t) => t.fmt(f),
             TopLevelStatement::Impl(i) => i.fmt(f),
+            TopLevelStatement::Trait(t) => t.fmt(f),
+            TopLevelStatement::TraitImpl(i) => i.fmt(f),
             TopLevelStatement::SubModule(s) => s.fmt(f),
             TopLevelStatement::GlobalConst(c) => c.fmt(f),
             TopLevelStatement::Error => write!(f, "error"),
@@ -328,6 +362,14 @@ impl std::fmt::Display for ParsedModule {
             write!(f, "{}", type_)?;
         }
 
+        for enum_ in &self.enums {
+            write!(f, "{}", enum_)?;
+        }
+
+        for type_alias in &self.type_aliases {
+            write!(f, "{}", type_alias)?;
+        }
+
         for function in &self.functions {
             write!(f, "{}", function)?;
         }
@@ -336,6 +378,14 @@ impl std::fmt::Display for ParsedModule {
             write!(f, "{}", impl_)?;
         }
 
+        for trait_ in &self.traits {
+            write!(f, "{}", trait_)?;
+        }
+
+        for trait_impl in &self.trait_impls {
+            write!(f, "{}", trait_impl)?;
+        }
+
         for submodule in &self.submodules {
             write!(f, "{}", submodule)?;
         }
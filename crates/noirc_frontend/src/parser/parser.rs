@@ -1,9 +1,9 @@
 use std::iter::repeat;
 
 use super::{
-    foldl_with_span, parameter_name_recovery, parameter_recovery, parenthesized, then_commit,
-    then_commit_ignore, top_level_statement_recovery, ExprParser, NoirParser, ParsedModule,
-    ParserError, Precedence, SubModule, TopLevelStatement,
+    foldl_with_span, parameter_name_recovery, parameter_recovery, parenthesized, spanned,
+    then_commit, then_commit_ignore, top_level_statement_recovery, ExprParser, NoirParser,
+    ParsedModule, ParserError, Precedence, SubModule, TopLevelStatement,
 };
 use crate::ast::{
     Expression, ExpressionKind, LetStatement, Statement, UnresolvedArraySize, UnresolvedType,
@@ -13,9 +13,11 @@ use crate::parser::{force, ignore_then_commit, statement_recovery};
 use crate::token::{Attribute, Keyword, Token, TokenKind};
 use crate::util::vecmap;
 use crate::{
-    AssignStatement, BinaryOp, BinaryOpKind, BlockExpression, ConstrainStatement, ForExpression,
-    FunctionDefinition, Ident, IfExpression, ImportStatement, InfixExpression, IsConst, LValue,
-    NoirFunction, NoirImpl, NoirStruct, Path, PathKind, Pattern, Recoverable, UnaryOp,
+    ArrayLengthOperator, AssignStatement, BinaryOp, BinaryOpKind, BlockExpression,
+    ConstrainStatement, ForExpression, FunctionDefinition, Ident, IfExpression, ImportStatement,
+    InfixExpression, IsConst, LValue, Lambda, MatchExpression, NoirEnum, NoirFunction, NoirImpl,
+    NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Path, PathKind, Pattern, Recoverable,
+    TraitMethod, UnaryOp, WhileExpression,
 };
 
 use chumsky::prelude::*;
@@ -46,9 +48,15 @@ fn module() -> impl NoirParser<ParsedModule> {
                 match statement {
                     TopLevelStatement::Function(f) => program.push_function(f),
                     TopLevelStatement::Module(m) => program.push_module_decl(m),
-                    TopLevelStatement::Import(i) => program.push_import(i),
+                    TopLevelStatement::Import(imports) => {
+                        imports.into_iter().for_each(|i| program.push_import(i))
+                    }
                     TopLevelStatement::Struct(s) => program.push_type(s),
+                    TopLevelStatement::Enum(e) => program.push_enum(e),
+                    TopLevelStatement::TypeAlias(t) => program.push_type_alias(t),
                     TopLevelStatement::Impl(i) => program.push_impl(i),
+                    TopLevelStatement::Trait(t) => program.push_trait(t),
+                    TopLevelStatement::TraitImpl(i) => program.push_trait_impl(i),
                     TopLevelStatement::SubModule(s) => program.push_submodule(s),
                     TopLevelStatement::GlobalConst(c) => program.push_global_const(c),
                     TopLevelStatement::Error => (),
@@ -64,6 +72,9 @@ fn top_level_statement(
     choice((
         function_definition(false).map(TopLevelStatement::Function),
         struct_definition(),
+        enum_definition(),
+        type_alias_definition().then_ignore(force(just(Token::Semicolon))),
+        trait_definition(),
         implementation(),
         submodule(module_parser),
         module_declaration().then_ignore(force(just(Token::Semicolon))),
@@ -98,13 +109,16 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
         .or_not()
         .then_ignore(keyword(Keyword::Fn))
         .then(ident())
-        .then(generics())
+        .then(function_generics())
         .then(parenthesized(function_parameters(allow_self)))
         .then(function_return_type())
         .then(block(expression()))
         .map(
             |(
-                ((((attribute, name), generics), parameters), (return_visibility, return_type)),
+                (
+                    (((attribute, name), (generics, trait_bounds)), parameters),
+                    (return_visibility, return_type),
+                ),
                 body,
             )| {
                 FunctionDefinition {
@@ -112,6 +126,7 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
                     name,
                     attribute, // XXX: Currently we only have one attribute defined. If more attributes are needed per function, we can make this a vector and make attribute definition more expressive
                     generics,
+                    trait_bounds,
                     parameters,
                     body,
                     return_type,
@@ -132,6 +147,30 @@ fn generics() -> impl NoirParser<Vec<Ident>> {
         .map(|opt| opt.unwrap_or_default())
 }
 
+/// The generics of a function, each of which may be bounded by traits: `<T: Hash + Eq, U>`
+fn function_generics() -> impl NoirParser<(Vec<Ident>, Vec<(Ident, Path)>)> {
+    let bounds = just(Token::Colon)
+        .ignore_then(path().separated_by(just(Token::Plus)).at_least(1))
+        .or_not()
+        .map(Option::unwrap_or_default);
+
+    ident()
+        .then(bounds)
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .at_least(1)
+        .delimited_by(just(Token::Less), just(Token::Greater))
+        .or_not()
+        .map(|generics| {
+            let mut trait_bounds = vec![];
+            let generics = vecmap(generics.unwrap_or_default(), |(generic, bounds)| {
+                trait_bounds.extend(bounds.into_iter().map(|bound| (generic.clone(), bound)));
+                generic
+            });
+            (generics, trait_bounds)
+        })
+}
+
 fn struct_definition() -> impl NoirParser<TopLevelStatement> {
     use self::Keyword::Struct;
     use Token::*;
@@ -152,6 +191,37 @@ fn struct_definition() -> impl NoirParser<TopLevelStatement> {
     )
 }
 
+fn enum_definition() -> impl NoirParser<TopLevelStatement> {
+    use self::Keyword::Enum;
+    use Token::*;
+
+    let payload = parse_type()
+        .separated_by(just(Comma))
+        .allow_trailing()
+        .delimited_by(just(LeftParen), just(RightParen))
+        .or_not()
+        .map(Option::unwrap_or_default);
+
+    let variants = ident()
+        .then(payload)
+        .separated_by(just(Comma))
+        .allow_trailing()
+        .at_least(1)
+        .delimited_by(just(LeftBrace), just(RightBrace))
+        .recover_with(nested_delimiters(
+            LeftBrace,
+            RightBrace,
+            [(LeftParen, RightParen), (LeftBracket, RightBracket)],
+            |_| vec![],
+        ));
+
+    keyword(Enum).ignore_then(ident()).then(generics()).then(variants).map_with_span(
+        |((name, generics), variants), span| {
+            TopLevelStatement::Enum(NoirEnum { name, generics, variants, span })
+        },
+    )
+}
+
 fn function_return_type() -> impl NoirParser<(AbiFEType, UnresolvedType)> {
     just(Token::Arrow)
         .ignore_then(optional_visibility())
@@ -211,13 +281,56 @@ fn self_parameter() -> impl NoirParser<(Pattern, UnresolvedType, AbiFEType)> {
     })
 }
 
+fn trait_definition() -> impl NoirParser<TopLevelStatement> {
+    use Token::*;
+
+    let methods = trait_method()
+        .repeated()
+        .delimited_by(just(LeftBrace), just(RightBrace))
+        .recover_with(nested_delimiters(
+            LeftBrace,
+            RightBrace,
+            [(LeftParen, RightParen), (LeftBracket, RightBracket)],
+            |_| vec![],
+        ));
+
+    keyword(Keyword::Trait).ignore_then(ident()).then(methods).map_with_span(
+        |(name, methods), span| TopLevelStatement::Trait(NoirTrait { name, methods, span }),
+    )
+}
+
+fn trait_method() -> impl NoirParser<TraitMethod> {
+    keyword(Keyword::Fn)
+        .ignore_then(ident())
+        .then(parenthesized(function_parameters(true)))
+        .then(function_return_type().map(|(_, return_type)| return_type))
+        .then_ignore(just(Token::Semicolon))
+        .map(|((name, parameters), return_type)| TraitMethod {
+            span: name.0.span(),
+            name,
+            parameters,
+            return_type,
+        })
+}
+
+/// Either an impl of methods for a struct, `impl Foo { ... }`,
+/// or an impl of a trait for any type, `impl Trait for Type { ... }`
 fn implementation() -> impl NoirParser<TopLevelStatement> {
     keyword(Keyword::Impl)
         .ignore_then(path())
+        .then(keyword(Keyword::For).ignore_then(spanned(parse_type())).or_not())
         .then_ignore(just(Token::LeftBrace))
         .then(function_definition(true).repeated())
         .then_ignore(just(Token::RightBrace))
-        .map(|(type_path, methods)| TopLevelStatement::Impl(NoirImpl { type_path, methods }))
+        .map(|((path, object_type), methods)| match object_type {
+            Some((object_type, object_type_span)) => TopLevelStatement::TraitImpl(NoirTraitImpl {
+                trait_name: path,
+                object_type,
+                object_type_span,
+                methods,
+            }),
+            None => TopLevelStatement::Impl(NoirImpl { type_path: path, methods }),
+        })
 }
 
 fn block_expr<'a, P>(expr_parser: P) -> impl NoirParser<Expression> + 'a
@@ -280,19 +393,92 @@ fn module_declaration() -> impl NoirParser<TopLevelStatement> {
     keyword(Keyword::Mod).ignore_then(ident()).map(TopLevelStatement::Module)
 }
 
+fn type_alias_definition() -> impl NoirParser<TopLevelStatement> {
+    let p = ignore_then_commit(keyword(Keyword::Type), ident());
+    let p = p.then(generics());
+    let p = then_commit_ignore(p, just(Token::Assign));
+    let p = then_commit(p, parse_type());
+
+    p.map_with_span(|((name, generics), typ), span| {
+        TopLevelStatement::TypeAlias(NoirTypeAlias { name, generics, typ, span })
+    })
+}
+
 fn use_statement() -> impl NoirParser<TopLevelStatement> {
-    let rename = ignore_then_commit(keyword(Keyword::As), ident()).or_not();
+    let path_kind = choice((
+        keyword(Keyword::Crate).then_ignore(just(Token::DoubleColon)).to(PathKind::Crate),
+        keyword(Keyword::Dep).then_ignore(just(Token::DoubleColon)).to(PathKind::Dep),
+    ))
+    .or_not()
+    .map(|kind| kind.unwrap_or(PathKind::Plain));
+
+    keyword(Keyword::Use).ignore_then(path_kind).then(use_tree()).validate(
+        |(kind, imports), span, emit| {
+            let imports = imports.into_iter().filter_map(|mut import: ImportStatement| {
+                if import.path.segments.is_empty() {
+                    emit(ParserError::with_reason(
+                        "Expected a module to glob import from before `*`".to_string(),
+                        span,
+                    ));
+                    return None;
+                }
+                import.path.kind = kind;
+                Some(import)
+            });
+            TopLevelStatement::Import(imports.collect())
+        },
+    )
+}
 
-    keyword(Keyword::Use)
-        .ignore_then(path())
-        .then(rename)
-        .map(|(path, alias)| TopLevelStatement::Import(ImportStatement { path, alias }))
+/// Parses the items after a `use`, which may be a single path or, like in
+/// `std::{hash, ec::*}`, a tree of paths. The tree is flattened into one import
+/// per item, each prefixed with every path segment leading up to it.
+fn use_tree() -> impl NoirParser<Vec<ImportStatement>> {
+    recursive(|use_tree| {
+        let rename = ignore_then_commit(keyword(Keyword::As), ident()).or_not();
+        let item = ident().then(rename).map(|(name, alias)| {
+            let path = Path { segments: vec![name], kind: PathKind::Plain };
+            vec![ImportStatement { path, alias, is_glob: false }]
+        });
+
+        let glob = just(Token::Star).map(|_| {
+            let path = Path { segments: Vec::new(), kind: PathKind::Plain };
+            vec![ImportStatement { path, alias: None, is_glob: true }]
+        });
+
+        let group = use_tree
+            .separated_by(just(Token::Comma))
+            .allow_trailing()
+            .delimited_by(just(Token::LeftBrace), just(Token::RightBrace))
+            .map(|trees: Vec<Vec<ImportStatement>>| trees.into_iter().flatten().collect());
+
+        ident()
+            .then_ignore(just(Token::DoubleColon))
+            .repeated()
+            .then(choice((item, glob, group)))
+            .map(|(prefix, imports): (Vec<Ident>, Vec<ImportStatement>)| {
+                vecmap(imports, |mut import| {
+                    let segments = std::mem::take(&mut import.path.segments);
+                    import.path.segments = prefix.iter().cloned().chain(segments).collect();
+                    import
+                })
+            })
+    })
 }
 
 fn keyword(keyword: Keyword) -> impl NoirParser<Token> {
     just(Token::Keyword(keyword))
 }
 
+/// An identifier which acts as a keyword in some positions only, so that it stays
+/// available as a variable name everywhere else
+fn contextual_keyword(name: &'static str) -> impl NoirParser<Token> {
+    filter_map(move |span, found: Token| match &found {
+        Token::Ident(ident) if ident == name => Ok(found),
+        _ => Err(ParserError::expected_label(name.to_string(), found, span)),
+    })
+}
+
 fn tokenkind(tokenkind: TokenKind) -> impl NoirParser<Token> {
     filter_map(move |span, found: Token| {
         if found.kind() == tokenkind {
@@ -338,7 +524,18 @@ where
     P: ExprParser + 'a,
 {
     ignore_then_commit(keyword(Keyword::Constrain).labelled("statement"), expr_parser)
-        .map(|expr| Statement::Constrain(ConstrainStatement(expr)))
+        .then(just(Token::Comma).ignore_then(constrain_message()).or_not())
+        .map(|(expr, message)| Statement::Constrain(ConstrainStatement(expr, message)))
+}
+
+/// The message reported when a constraint fails: `constrain x == y, "message"`
+fn constrain_message() -> impl NoirParser<String> {
+    filter_map(|span, token: Token| match token {
+        Token::Str(message) => Ok(message),
+        unexpected => {
+            Err(ParserError::expected_label("constraint message".to_string(), unexpected, span))
+        }
+    })
 }
 
 fn declaration<'a, P>(expr_parser: P) -> impl NoirParser<Statement> + 'a
@@ -356,6 +553,10 @@ fn pattern() -> impl NoirParser<Pattern> {
     recursive(|pattern| {
         let ident_pattern = ident().map(Pattern::Identifier);
 
+        let wildcard_pattern = just(Token::Underscore).map_with_span(|_, span| {
+            Pattern::Identifier(Ident::new(Token::Ident("_".into()), span))
+        });
+
         let mut_pattern = keyword(Keyword::Mut)
             .ignore_then(pattern.clone())
             .map_with_span(|inner, span| Pattern::Mutable(Box::new(inner), span));
@@ -377,21 +578,85 @@ fn pattern() -> impl NoirParser<Pattern> {
             .delimited_by(just(Token::LeftParen), just(Token::RightParen))
             .map_with_span(Pattern::Tuple);
 
-        choice((mut_pattern, tuple_pattern, struct_pattern, ident_pattern))
+        choice((mut_pattern, tuple_pattern, struct_pattern, ident_pattern, wildcard_pattern))
     })
     .labelled("pattern")
 }
 
+/// The pattern of a `match` arm. This is either an enum variant like `Shape::Circle(radius)`,
+/// which must be written with its enum's name, or an irrefutable pattern matching any value.
+fn match_pattern() -> impl NoirParser<Pattern> {
+    let variant_path = path().try_map(|path, span| {
+        if path.segments.len() > 1 {
+            Ok(path)
+        } else {
+            Err(ParserError::with_reason("Expected an enum variant".to_string(), span))
+        }
+    });
+
+    let payload = pattern()
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .delimited_by(just(Token::LeftParen), just(Token::RightParen))
+        .or_not()
+        .map(Option::unwrap_or_default);
+
+    let variant_pattern = variant_path
+        .then(payload)
+        // A path followed by a brace is a struct pattern instead
+        .then_ignore(none_of([Token::LeftBrace]).rewind())
+        .map_with_span(|(variant, fields), span| Pattern::Variant(variant, fields, span));
+
+    variant_pattern.or(pattern())
+}
+
 fn assignment<'a, P>(expr_parser: P) -> impl NoirParser<Statement> + 'a
 where
     P: ExprParser + 'a,
 {
-    let failable =
-        lvalue(expr_parser.clone()).then_ignore(just(Token::Assign)).labelled("statement");
+    let failable = lvalue(expr_parser.clone())
+        .map_with_span(|lvalue, span| (lvalue, span))
+        .then(assign_operator())
+        .labelled("statement");
+
+    then_commit(failable, expr_parser).map_with_span(
+        |(((lvalue, lvalue_span), operator), expression), span| {
+            let operator = match operator {
+                Some(operator) => operator,
+                None => return Statement::Assign(AssignStatement { lvalue, expression }),
+            };
+
+            // Compound assignments `lvalue op= rhs` are desugared to `lvalue = lvalue op rhs`.
+            // Indices which may have side effects are first bound to variables, desugaring
+            // `a[f()] += 1` to `{ let $index0 = f(); a[$index0] = a[$index0] + 1 };`
+            let mut statements = Vec::new();
+            let lvalue = lvalue.bind_indices(&mut statements);
+            let mut lhs = lvalue.as_expression();
+            lhs.span = lvalue_span;
+            let expression = create_infix_expression(lhs, (operator, expression));
+            let assign = Statement::Assign(AssignStatement { lvalue, expression });
+
+            if statements.is_empty() {
+                return assign;
+            }
+            statements.push(assign);
+            let block = ExpressionKind::Block(BlockExpression(statements));
+            Statement::Semi(Expression::new(block, span))
+        },
+    )
+}
 
-    then_commit(failable, expr_parser).map(|(identifier, expression)| {
-        Statement::Assign(AssignStatement { lvalue: identifier, expression })
-    })
+/// Parses either `=`, returning None, or a compound assignment operator
+/// such as `+=`, returning the binary operator it applies.
+fn assign_operator() -> impl NoirParser<Option<BinaryOp>> {
+    let compound_operator = filter_map(|span, token: Token| {
+        match token.compound_assign_operator().as_ref().and_then(Option::<BinaryOpKind>::from) {
+            Some(operator) => Ok(Some(Spanned::from(span, operator))),
+            None => Err(ParserError::expected_label("assignment operator".to_owned(), token, span)),
+        }
+    });
+
+    just(Token::Assign).to(None).or(compound_operator)
 }
 
 enum LValueRhs {
@@ -432,8 +697,10 @@ where
         int_type(),
         named_type(recursive_type_parser.clone()),
         array_type(recursive_type_parser.clone()),
-        tuple_type(recursive_type_parser),
+        tuple_type(recursive_type_parser.clone()),
+        function_type(recursive_type_parser),
         bool_type(),
+        string_type(),
     ))
 }
 
@@ -459,6 +726,13 @@ fn bool_type() -> impl NoirParser<UnresolvedType> {
     maybe_const().then_ignore(keyword(Keyword::Bool)).map(UnresolvedType::Bool)
 }
 
+/// `str<N>`, where the length may be omitted in parameter types to accept strings of any length
+fn string_type() -> impl NoirParser<UnresolvedType> {
+    keyword(Keyword::Str)
+        .ignore_then(array_size().delimited_by(just(Token::Less), just(Token::Greater)).or_not())
+        .map(|size| UnresolvedType::String(size.unwrap_or(UnresolvedArraySize::Variable)))
+}
+
 fn int_type() -> impl NoirParser<UnresolvedType> {
     maybe_const()
         .then(filter_map(|span, token: Token| match token {
@@ -510,6 +784,19 @@ where
     parenthesized(fields).map(UnresolvedType::Tuple)
 }
 
+/// `fn(Field, u8) -> bool`, where the return type defaults to `()` if omitted
+fn function_type<T>(type_parser: T) -> impl NoirParser<UnresolvedType>
+where
+    T: NoirParser<UnresolvedType>,
+{
+    let args = parenthesized(type_parser.clone().separated_by(just(Token::Comma)).allow_trailing());
+    let return_type = just(Token::Arrow).ignore_then(type_parser).or_not();
+
+    keyword(Keyword::Fn).ignore_then(args).then(return_type).map(|(args, ret)| {
+        UnresolvedType::Function(args, Box::new(ret.unwrap_or(UnresolvedType::Unit)))
+    })
+}
+
 fn expression() -> impl ExprParser {
     recursive(|expr| expression_with_precedence(Precedence::Lowest, expr)).labelled("expression")
 }
@@ -632,17 +919,84 @@ where
         .then(expr_parser.clone())
         .then_ignore(just(Token::DoubleDot))
         .then(expr_parser.clone())
+        .then(contextual_keyword("max").ignore_then(expr_parser.clone()).or_not())
         .then(block_expr(expr_parser))
-        .map(|(((identifier, start_range), end_range), block)| {
+        .map(|((((identifier, start_range), end_range), max_iterations), block)| {
             ExpressionKind::For(Box::new(ForExpression {
                 identifier,
                 start_range,
                 end_range,
+                max_iterations,
                 block,
             }))
         })
 }
 
+/// `while condition max N { ... }`, where the loop is unrolled up to `N` times
+fn while_expr<'a, P>(expr_parser: P) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P: ExprParser + 'a,
+{
+    keyword(Keyword::While)
+        .ignore_then(expr_parser.clone())
+        .then_ignore(contextual_keyword("max"))
+        .then(expr_parser.clone())
+        .then(block_expr(expr_parser))
+        .map(|((condition, max_iterations), block)| {
+            ExpressionKind::While(Box::new(WhileExpression { condition, max_iterations, block }))
+        })
+}
+
+/// `match value { Pattern => expression, ... }`, where the comma after an arm is optional
+fn match_expr<'a, P>(expr_parser: P) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P: ExprParser + 'a,
+{
+    let arm = match_pattern()
+        .then_ignore(just(Token::FatArrow))
+        .then(expr_parser.clone())
+        .then_ignore(just(Token::Comma).or_not());
+
+    let arms =
+        arm.repeated().at_least(1).delimited_by(just(Token::LeftBrace), just(Token::RightBrace));
+
+    keyword(Keyword::Match).ignore_then(expr_parser).then(arms).map(|(expression, arms)| {
+        ExpressionKind::Match(Box::new(MatchExpression { expression, arms }))
+    })
+}
+
+/// `|x, y: Field| x + y` or `|x| -> Field { x }`, where any omitted types are inferred
+fn lambda<'a, P>(expr_parser: P) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P: ExprParser + 'a,
+{
+    let parameter = pattern().then(just(Token::Colon).ignore_then(parse_type()).or_not());
+    let parameters = parameter
+        .map(|(pattern, typ)| (pattern, typ.unwrap_or(UnresolvedType::Unspecified)))
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .at_least(1)
+        .delimited_by(just(Token::Pipe), just(Token::Pipe));
+
+    let return_type = just(Token::Arrow).ignore_then(parse_type()).or_not();
+
+    parameters.then(return_type).then(expr_parser).map(|((parameters, return_type), body)| {
+        let return_type = return_type.unwrap_or(UnresolvedType::Unspecified);
+        ExpressionKind::Lambda(Box::new(Lambda { parameters, return_type, body }))
+    })
+}
+
+/// `return value`, where `return` alone returns the unit value
+fn return_expr<P>(expr_parser: P) -> impl NoirParser<ExpressionKind>
+where
+    P: ExprParser,
+{
+    keyword(Keyword::Return).ignore_then(expr_parser.or_not()).map_with_span(|value, span| {
+        let unit = || Expression::new(ExpressionKind::Block(BlockExpression(vec![])), span);
+        ExpressionKind::Return(Box::new(value.unwrap_or_else(unit)))
+    })
+}
+
 fn array_expr<P>(expr_parser: P) -> impl NoirParser<ExpressionKind>
 where
     P: ExprParser,
@@ -723,6 +1077,12 @@ where
         function_call(expr_parser.clone()),
         if_expr(expr_parser.clone()),
         for_expr(expr_parser.clone()),
+        while_expr(expr_parser.clone()),
+        match_expr(expr_parser.clone()),
+        keyword(Keyword::Break).to(ExpressionKind::Break),
+        keyword(Keyword::Continue).to(ExpressionKind::Continue),
+        return_expr(expr_parser.clone()),
+        lambda(expr_parser.clone()),
         array_expr(expr_parser.clone()),
         constructor(expr_parser.clone()),
         block(expr_parser.clone()).map(ExpressionKind::Block),
@@ -817,17 +1177,37 @@ fn literal() -> impl NoirParser<ExpressionKind> {
 }
 
 fn fixed_array_size() -> impl NoirParser<UnresolvedArraySize> {
-    just(Token::Semicolon).ignore_then(filter_map(|span, token: Token| match token.clone() {
-        Token::Int(integer) => Ok(UnresolvedArraySize::Fixed(try_field_to_u64(integer, span)?)),
-        Token::Ident(_) => {
-            // XXX: parse named size as an ident. The actual const integer size will be determined in the hir pass and resolution
-            Ok(UnresolvedArraySize::FixedVariable(Ident::new(token, span)))
-        }
-        _ => {
-            let message = "Expected an integer for the length of the array".to_string();
-            Err(ParserError::with_reason(message, span))
-        }
-    }))
+    just(Token::Semicolon).ignore_then(array_size())
+}
+
+/// An integer, a named size, or `+`, `-` and `*` over these such as `N + 1`
+fn array_size() -> impl NoirParser<UnresolvedArraySize> {
+    recursive(|array_size| {
+        let atom = filter_map(|span, token: Token| match token.clone() {
+            Token::Int(integer) => Ok(UnresolvedArraySize::Fixed(try_field_to_u64(integer, span)?)),
+            Token::Ident(_) => {
+                // XXX: parse named size as an ident. The actual const integer size will be determined in the hir pass and resolution
+                Ok(UnresolvedArraySize::FixedVariable(Ident::new(token, span)))
+            }
+            _ => {
+                let message = "Expected an integer for the length of the array".to_string();
+                Err(ParserError::with_reason(message, span))
+            }
+        })
+        .or(array_size.delimited_by(just(Token::LeftParen), just(Token::RightParen)));
+
+        let binary = |lhs, (operator, rhs): (ArrayLengthOperator, UnresolvedArraySize), span| {
+            UnresolvedArraySize::Binary(Box::new(lhs), operator, Box::new(rhs), span)
+        };
+
+        let product_operator = just(Token::Star).to(ArrayLengthOperator::Multiply);
+        let product = foldl_with_span(atom.clone(), product_operator.then(atom), binary);
+
+        let sum_operator = just(Token::Plus)
+            .to(ArrayLengthOperator::Add)
+            .or(just(Token::Minus).to(ArrayLengthOperator::Subtract));
+        foldl_with_span(product.clone(), sum_operator.then(product), binary)
+    })
 }
 
 fn try_field_to_u64(x: acvm::FieldElement, span: Span) -> Result<u64, ParserError> {
@@ -938,7 +1318,7 @@ mod test {
 
     #[test]
     fn parse_infix() {
-        let valid = vec!["x + 6", "x - k", "x + (x + a)", " x * (x + a) + (x - 4)"];
+        let valid = vec!["x + 6", "x - k", "x + (x + a)", " x * (x + a) + (x - 4)", "x % 3 + y"];
         parse_all(expression(), valid);
         parse_all_failing(expression(), vec!["y ! x"]);
     }
@@ -1042,6 +1422,7 @@ mod test {
             BinaryOpKind::And,
             BinaryOpKind::Subtract,
             BinaryOpKind::Divide,
+            BinaryOpKind::Modulo,
             BinaryOpKind::Multiply,
             BinaryOpKind::Or,
         ];
@@ -1069,6 +1450,66 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_constrain_with_message() {
+        let statements = parse_all(
+            constrain(expression()),
+            vec!["constrain x == y, \"balance underflow\"", "constrain x, \"\""],
+        );
+        let messages = vecmap(statements, |statement| match statement {
+            Statement::Constrain(ConstrainStatement(_, message)) => message,
+            other => panic!("Expected a constrain statement, found {}", other),
+        });
+        assert_eq!(messages, vec![Some("balance underflow".to_owned()), Some(String::new())]);
+
+        parse_all_failing(
+            constrain(expression()),
+            vec!["constrain x == y,", "constrain x == y, y", "constrain x == y, \"a\", \"b\""],
+        );
+    }
+
+    #[test]
+    fn parse_compound_assignment() {
+        let statements = parse_all(
+            assignment(expression()),
+            vec!["x += 1", "x -= y", "s.arr[i] += 1", "a[i][j] <<= 2", "s.x ^= s.y", "x %= 3"],
+        );
+        let statements = vecmap(statements, |statement| statement.to_string());
+        assert_eq!(
+            statements,
+            vec![
+                "x = (x + 1)",
+                "x = (x - y)",
+                "s.arr[i] = ((s.arr)[i] + 1)",
+                "a[i][j] = (a[i][j] << 2)",
+                "s.x = ((s.x) ^ (s.y))",
+                "x = (x % 3)",
+            ]
+        );
+
+        // The desugared lvalue expression keeps the span of the original lvalue
+        let statement = parse_with(assignment(expression()), "s.arr[i] += 1").unwrap();
+        match statement {
+            Statement::Assign(AssignStatement { expression, .. }) => match expression.kind {
+                ExpressionKind::Infix(infix) => {
+                    assert_eq!(infix.lhs.span, Span::new(0..7));
+                    assert_eq!(infix.operator.span(), Span::new(9..10));
+                }
+                other => panic!("Expected an infix expression, found {}", other),
+            },
+            other => panic!("Expected an assignment, found {}", other),
+        }
+
+        // Indices which are not variables or literals are only evaluated once
+        let statement = parse_with(assignment(expression()), "a[i + 1][2] *= 3").unwrap();
+        assert_eq!(
+            statement.to_string(),
+            "{\n    let $index0: unspecified = (i + 1)\n    a[$index0][2] = (a[$index0][2] * 3)\n};"
+        );
+
+        parse_all_failing(assignment(expression()), vec!["x + = 1", "x += ", "x == 1"]);
+    }
+
     #[test]
     fn parse_let() {
         // Why is it valid to specify a let declaration as having type u8?
@@ -1089,15 +1530,57 @@ mod test {
     fn parse_for_loop() {
         parse_all(
             for_expr(expression()),
-            vec!["for i in x+y..z {}", "for i in 0..100 { foo; bar }"],
+            vec![
+                "for i in x+y..z {}",
+                "for i in 0..100 { foo; bar }",
+                "for i in 0..n max 10 { foo; }",
+                "for i in start..start+len max 2*N { if i == n { break; } }",
+            ],
         );
 
         parse_all_failing(
             for_expr(expression()),
             vec![
-                "for 1 in x+y..z {}",  // Cannot have a literal as the loop identifier
-                "for i in 0...100 {}", // Only '..' is supported, there are no inclusive ranges yet
-                "for i in 0..=100 {}", // Only '..' is supported, there are no inclusive ranges yet
+                "for 1 in x+y..z {}",   // Cannot have a literal as the loop identifier
+                "for i in 0...100 {}",  // Only '..' is supported, there are no inclusive ranges yet
+                "for i in 0..=100 {}",  // Only '..' is supported, there are no inclusive ranges yet
+                "for i in 0..n max {}", // The maximum number of iterations is missing
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_while_loop() {
+        parse_all(
+            while_expr(expression()),
+            vec![
+                "while i < n max 10 {}",
+                "while x max N { if x { break; } else { continue; } }",
+                "while max < 3 max 3 { max = max + 1; }",
+            ],
+        );
+
+        parse_all_failing(
+            while_expr(expression()),
+            vec![
+                "while i < n {}", // The maximum number of iterations is required
+                "while max 10 {}",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_return() {
+        parse_all(
+            return_expr(expression()),
+            vec!["return", "return x", "return (a, b)", "return if x { 1 } else { 2 }"],
+        );
+
+        parse_all(
+            function_definition(false),
+            vec![
+                "fn f(x: Field) -> Field { if x == 0 { return 1; } x }",
+                "fn f(x: Field) { for i in 0..3 { if i == x { return; } } }",
             ],
         );
     }
@@ -1238,15 +1721,59 @@ mod test {
     fn parse_use() {
         parse_all(
             use_statement(),
-            vec!["use std::hash", "use std", "use foo::bar as hello", "use bar as bar"],
+            vec![
+                "use std::hash",
+                "use std",
+                "use foo::bar as hello",
+                "use bar as bar",
+                "use dep::std::{hash, ec::*}",
+                "use crate::foo::{bar as baz, qux::{a, b,},}",
+                "use dep::std::*",
+            ],
         );
 
         parse_all_failing(
             use_statement(),
-            vec!["use std as ;", "use foobar as as;", "use hello:: as foo;"],
+            vec![
+                "use std as ;",
+                "use foobar as as;",
+                "use hello:: as foo;",
+                "use foo::{bar",
+                "use foo::* as bar",
+                "use *",
+                "use dep::*",
+                "use crate::*",
+            ],
         );
     }
 
+    #[test]
+    fn parse_nested_use_tree() {
+        let statement =
+            parse_with(use_statement(), "use dep::std::{hash::pedersen, ec::*}").unwrap();
+        let imports = match statement {
+            TopLevelStatement::Import(imports) => imports,
+            other => panic!("Expected an import, found {}", other),
+        };
+
+        let imports = vecmap(imports, |import| import.to_string());
+        assert_eq!(imports, vec!["use dep::std::hash::pedersen", "use dep::std::ec::*"]);
+    }
+
+    #[test]
+    fn parse_type_aliases() {
+        let cases = vec![
+            "type Point = (Field, Field)",
+            "type Bytes = [u8; 32]",
+            "type Pair<T> = (T, T)",
+            "type Foo = dep::std::Bar<Field>",
+        ];
+        parse_all(type_alias_definition(), cases);
+
+        let failing = vec!["type = Field", "type Foo Field", "type Foo<> = Field"];
+        parse_all_failing(type_alias_definition(), failing);
+    }
+
     #[test]
     fn parse_structs() {
         let cases = vec![
@@ -1260,6 +1787,143 @@ mod test {
         parse_all_failing(struct_definition(), failing);
     }
 
+    #[test]
+    fn parse_enums() {
+        let cases = vec![
+            "enum Foo { A }",
+            "enum Bar { A, B(Field), }",
+            "enum Option<T> { None, Some(T) }",
+            "enum Op { Push(u8, [Field; 2]), Pop, Jump((u32, bool)), }",
+        ];
+        parse_all(enum_definition(), cases);
+
+        let failing = vec!["enum {  }", "enum Foo { }", "enum Foo { A(pub Field) }"];
+        parse_all_failing(enum_definition(), failing);
+    }
+
+    #[test]
+    fn parse_traits() {
+        let cases = vec![
+            "trait Empty { }",
+            "trait Hash { fn hash(self) -> Field; }",
+            "trait Serialize { fn serialize(self) -> [Field; 2]; fn size(x: Field, y: Self); }",
+        ];
+        parse_all(trait_definition(), cases);
+
+        let failing = vec!["trait { }", "trait Foo { fn foo(self) {} }", "trait Foo { fn foo() }"];
+        parse_all_failing(trait_definition(), failing);
+    }
+
+    #[test]
+    fn parse_trait_impls() {
+        let cases = vec![
+            "impl Hash for Field { fn hash(self) -> Field { self } }",
+            "impl foo::Hash for Point { }",
+            "impl Serialize for [u8; 2] { fn serialize(self) -> [Field; 2] { [0, 1] } }",
+        ];
+        parse_all(implementation(), cases);
+
+        parse_all_failing(implementation(), vec!["impl Hash for { }", "impl for Field { }"]);
+    }
+
+    #[test]
+    fn parse_trait_bounds() {
+        parse_all(
+            function_definition(false),
+            vec![
+                "fn hash_all<T: Hash>(x: T) -> Field { x.hash() }",
+                "fn f<T: foo::Hash + Eq, U, V: Serialize,>(x: T, y: U, z: V) {}",
+            ],
+        );
+
+        parse_all_failing(
+            function_definition(false),
+            vec!["fn f<T:>(x: T) {}", "fn f<T: Hash +>(x: T) {}"],
+        );
+    }
+
+    #[test]
+    fn parse_match() {
+        parse_all(
+            match_expr(expression()),
+            vec![
+                "match x { Foo::A => 1, Foo::B(y) => y }",
+                "match op { Op::Push(a, _) => { a } Op::Pop => 0, _ => 1, }",
+                "match crate::foo::Shape::Square(2) { foo::Shape::Square(side) => side * side, other => 0 }",
+                "match pair { (a, b) => a + b }",
+                "match p { foo::Point { x, y } => x + y }",
+            ],
+        );
+
+        parse_all_failing(
+            match_expr(expression()),
+            vec!["match x { }", "match x { Foo::A }", "match x { Foo::A(1) => 1 }"],
+        );
+    }
+
+    #[test]
+    fn parse_lambdas() {
+        parse_all(
+            lambda(expression()),
+            vec![
+                "|x| x * 2",
+                "|x: Field, y| x + y",
+                "|(a, b), c: [u8; 3],| a + c[b]",
+                "|x| -> Field { x }",
+                "|x| |y| x + y",
+            ],
+        );
+
+        parse_all(expression(), vec!["apply(|x| x + 1, 2)", "fold(arr, 0, |acc, x| acc + x)"]);
+
+        parse_all_failing(lambda(expression()), vec!["|| 1", "|x|", "|x: | x", "|x| -> Field"]);
+    }
+
+    #[test]
+    fn parse_function_types() {
+        parse_all(
+            parse_type(),
+            vec![
+                "fn(Field) -> Field",
+                "fn(u8, bool,) -> (u8, bool)",
+                "fn()",
+                "fn(fn(u8) -> u8) -> u8",
+            ],
+        );
+
+        parse_all(
+            function_definition(false),
+            vec!["fn map<T>(array: [T; 3], f: fn(T) -> T) -> [T; 3] { array }"],
+        );
+    }
+
+    #[test]
+    fn parse_string_types() {
+        parse_all(parse_type(), vec!["str<5>", "str<N>", "str", "[str<2>; 3]", "(str<1>, u8)"]);
+        parse_all_failing(parse_type(), vec!["str<>", "str<u8>", "str<-1>"]);
+
+        parse_all(
+            function_definition(false),
+            vec!["fn hash_message(message: str<5>) -> [u8; 32] { std::hash::sha256(message) }"],
+        );
+    }
+
+    #[test]
+    fn parse_array_length_arithmetic() {
+        parse_all(
+            parse_type(),
+            vec!["[Field; N + 1]", "[u8; 2 * N - 1]", "[Field; (N + M) * 2]", "str<N + 1>"],
+        );
+        parse_all_failing(parse_type(), vec!["[Field; N +]", "[Field; * 2]", "[Field; N / 2]"]);
+
+        parse_all(
+            function_definition(false),
+            vec![
+                "fn push<N>(array: [Field; N], x: Field) -> [Field; N + 1] { concat(array, [x]) }",
+            ],
+        );
+    }
+
     #[test]
     fn parse_member_access() {
         let cases = vec!["a.b", "a + b.c", "foo.bar as i32"];